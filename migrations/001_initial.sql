@@ -1,53 +1,34 @@
--- Initial schema for j9s local cache
--- This file is kept for reference; the schema is embedded in the binary
-
--- Schema version tracking
-CREATE TABLE IF NOT EXISTS schema_version (
-    version INTEGER PRIMARY KEY
-);
-
--- Cached issues
-CREATE TABLE IF NOT EXISTS issues (
-    key TEXT PRIMARY KEY,
-    project TEXT NOT NULL,
-    summary TEXT NOT NULL,
-    description TEXT,
-    status TEXT NOT NULL,
-    issue_type TEXT NOT NULL,
-    assignee TEXT,
-    reporter TEXT,
-    priority TEXT,
-    labels TEXT,  -- JSON array
-    created TEXT NOT NULL,
-    updated TEXT NOT NULL,
-    cached_at TEXT NOT NULL DEFAULT (datetime('now'))
+-- Generic entity cache (stores serialized JSON)
+-- IF NOT EXISTS: databases from before versioning already have these tables
+CREATE TABLE IF NOT EXISTS entity_cache (
+    entity_type TEXT NOT NULL,
+    entity_key TEXT NOT NULL,
+    data BLOB NOT NULL,
+    updated_at TEXT,
+    cached_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (entity_type, entity_key)
 );
 
-CREATE INDEX IF NOT EXISTS idx_issues_project ON issues(project);
-CREATE INDEX IF NOT EXISTS idx_issues_status ON issues(status);
-CREATE INDEX IF NOT EXISTS idx_issues_assignee ON issues(assignee);
+CREATE INDEX IF NOT EXISTS idx_entity_cache_updated
+    ON entity_cache(entity_type, updated_at);
 
--- Cached boards
-CREATE TABLE IF NOT EXISTS boards (
-    id INTEGER PRIMARY KEY,
-    name TEXT NOT NULL,
-    board_type TEXT NOT NULL,
-    cached_at TEXT NOT NULL DEFAULT (datetime('now'))
-);
-
--- Cached sprints
-CREATE TABLE IF NOT EXISTS sprints (
-    id INTEGER PRIMARY KEY,
-    board_id INTEGER NOT NULL,
-    name TEXT NOT NULL,
-    state TEXT NOT NULL,
-    start_date TEXT,
-    end_date TEXT,
+-- Query result tracking
+CREATE TABLE IF NOT EXISTS query_cache (
+    query_hash TEXT PRIMARY KEY,
+    query_description TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    max_updated TEXT,
     cached_at TEXT NOT NULL DEFAULT (datetime('now')),
-    FOREIGN KEY (board_id) REFERENCES boards(id)
+    result_count INTEGER NOT NULL
 );
 
-CREATE INDEX IF NOT EXISTS idx_sprints_board ON sprints(board_id);
+-- Query to entity mapping (preserves order)
+CREATE TABLE IF NOT EXISTS query_results (
+    query_hash TEXT NOT NULL,
+    entity_key TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (query_hash, entity_key),
+    FOREIGN KEY (query_hash) REFERENCES query_cache(query_hash) ON DELETE CASCADE
+);
 
--- Insert initial schema version
-INSERT OR IGNORE INTO schema_version (version) VALUES (1);
+CREATE INDEX IF NOT EXISTS idx_query_results_hash ON query_results(query_hash);
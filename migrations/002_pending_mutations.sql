@@ -0,0 +1,11 @@
+-- Mutations recorded while offline, replayed in id order
+CREATE TABLE pending_mutations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity_key TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    base_updated TEXT,
+    state TEXT NOT NULL DEFAULT 'pending',
+    error TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,3 @@
+-- Last use of cached data, for LRU eviction
+ALTER TABLE entity_cache ADD COLUMN accessed_at TEXT;
+ALTER TABLE query_cache ADD COLUMN accessed_at TEXT;
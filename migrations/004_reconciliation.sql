@@ -0,0 +1,2 @@
+-- When a cached query was last checked against the full server result
+ALTER TABLE query_cache ADD COLUMN reconciled_at TEXT;
@@ -0,0 +1,6 @@
+-- Commands entered in the command palette, for up/down history
+CREATE TABLE command_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    command TEXT NOT NULL UNIQUE,
+    used_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -1,21 +1,104 @@
+use crate::browser;
 use crate::cache::{CacheLayer, SqliteStorage};
-use crate::config::Config;
+use crate::clipboard;
+use crate::commands::{self, Alias, ArgKind};
+use crate::config::{AlertMethod, Config};
 use crate::db;
 use crate::event::{Event, EventHandler};
+use crate::export::{self, ExportFormat};
+use crate::git;
+use crate::jira::mutations::ReplaySummary;
+use crate::jira::types::IssueSummary;
 use crate::jira::JiraClient;
-use crate::ui;
-use crate::ui::components::{CommandEvent, CommandInput, KeyResult};
+use crate::query::Query;
+use crate::ui::components::{CommandEvent, CommandInput, KeyResult, Suggestion};
+use crate::ui::renderfns::{extract_domain, format_age};
 use crate::ui::view::{ShortcutInfo, View, ViewAction};
-use crate::ui::views::{BoardListView, EpicListView, IssueListView};
-use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::ui::views::{
+  BoardListView, BoardView, BurndownView, CacheView, EpicDetailView, EpicListView, IssueDetailView,
+  IssueListView, MetricsView, NotificationsView, PendingMutationsView, TreeView,
+};
+use crate::ui::{self, Action, Keymap};
+use chrono::{Local, TimeDelta, Utc};
+use color_eyre::{eyre::eyre, Result};
+use crossterm::event::KeyEvent;
 use crossterm::terminal::{
   disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use crossterm::ExecutableCommand;
 use ratatui::prelude::*;
-use std::io::stdout;
-use std::time::Duration;
+use std::collections::BTreeSet;
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// How often to retry replaying queued offline changes
+const REPLAY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a flash message stays in the footer
+const FLASH_DURATION: Duration = Duration::from_secs(5);
+
+/// Alert the user about new notifications outside the app.
+///
+/// Escape sequences go straight to the terminal; ratatui doesn't track them.
+fn send_alert(method: AlertMethod, body: &str) {
+  // Control characters would end the sequence early
+  let body: String = body.chars().filter(|c| !c.is_control()).collect();
+  let sequence = match method {
+    AlertMethod::None => return,
+    AlertMethod::Bell => "\x07".to_string(),
+    AlertMethod::Osc9 => format!("\x1b]9;{}\x07", body),
+    AlertMethod::Osc777 => format!("\x1b]777;notify;j9s;{}\x07", body.replace(';', ",")),
+  };
+  let mut out = stdout();
+  if let Err(e) = out
+    .write_all(sequence.as_bytes())
+    .and_then(|()| out.flush())
+  {
+    warn!("Failed to send alert: {}", e);
+  }
+}
+
+/// Create the cache layer with the configured stale times
+fn build_cache(storage: SqliteStorage, config: &Config) -> CacheLayer<SqliteStorage> {
+  let to_delta = |d: Duration| TimeDelta::from_std(d).unwrap_or(TimeDelta::MAX);
+
+  // The field list rarely changes and is needed before every issue search;
+  // the status list rarely changes either.
+  // Histories are cached per issue update, so they never go stale.
+  let mut cache = CacheLayer::new(storage)
+    .with_offline(config.offline)
+    .with_stale_time_for("field", TimeDelta::days(1))
+    .with_stale_time_for("status", TimeDelta::days(1))
+    .with_stale_time_for("changelog", TimeDelta::MAX);
+  if let Some(stale_time) = config.cache.stale_time {
+    cache = cache.with_stale_time(to_delta(stale_time));
+  }
+  for (entity_type, stale_time) in &config.cache.stale_times {
+    cache = cache.with_stale_time_for(entity_type, to_delta(*stale_time));
+  }
+  if let Some(interval) = config.cache.reconcile_interval {
+    cache = cache.with_reconcile_interval(to_delta(interval));
+  }
+  cache
+}
+
+/// Open the cache database and create the caching Jira client.
+///
+/// Shared by the TUI and the non-interactive subcommands.
+pub fn connect(config: &Config) -> Result<JiraClient> {
+  let conn = db::open_connection()?;
+  let cache_storage = SqliteStorage::new(conn);
+  match cache_storage.maintain(config.cache.max_size_bytes()) {
+    Ok(report) => info!(
+      "Cache maintenance evicted {} entities, {} queries",
+      report.entities, report.queries
+    ),
+    Err(e) => warn!("Cache maintenance failed: {}", e),
+  }
+  JiraClient::new(config, build_cache(cache_storage, config))
+}
 
 /// Main application state
 pub struct App {
@@ -25,32 +108,85 @@ pub struct App {
   /// Command input component
   command: CommandInput,
 
+  /// User-defined commands from the config
+  aliases: Vec<Alias>,
+
+  /// Global key bindings (views resolve their own)
+  keys: Keymap,
+
   /// Application configuration
   config: Config,
 
   /// Jira client (with caching)
   jira: JiraClient,
 
+  /// In-flight replay of the offline mutation queue
+  replay: Option<Query<ReplaySummary>>,
+  last_replay: Option<Instant>,
+  /// Number of queued offline changes, counted on each tick
+  pending_mutations: usize,
+
+  /// In-flight check of the notification watch query
+  watch: Option<Query<Vec<(String, String)>>>,
+  last_watch: Option<Instant>,
+  /// Number of unseen notifications, counted on each tick
+  unread_notifications: usize,
+
+  /// Issue key found in the current git branch name
+  branch_issue: Option<String>,
+
+  /// Transient message shown in the footer
+  flash: Option<(String, Instant)>,
+
   /// Whether to quit
   should_quit: bool,
 }
 
 impl App {
-  pub async fn new(config: Config) -> Result<Self> {
-    let conn = db::open_connection()?;
-    let cache_storage = SqliteStorage::new(conn)?;
-    let cache = CacheLayer::new(cache_storage);
-    let jira = JiraClient::new(&config, cache)?;
+  /// Create the app; with `open_current`, start on the issue of the
+  /// current git branch
+  pub async fn new(config: Config, open_current: bool) -> Result<Self> {
+    let jira = connect(&config)?;
 
     let default_project = config.default_project.clone().unwrap_or_default();
+    let mut issues = IssueListView::new(default_project, jira.clone());
+    issues.configure(&config);
+
+    let branch_issue = git::current_branch()
+      .and_then(|branch| git::issue_key_in(&branch, config.default_project.as_deref()));
+
+    let aliases = Alias::from_config(&config.aliases);
+    let keys = Keymap::new(&config.keys.global, None);
+    let mut command = CommandInput::new();
+    command.set_aliases(aliases.clone());
+    command.set_keymap(&keys);
 
-    Ok(Self {
-      view_stack: vec![Box::new(IssueListView::new(default_project, jira.clone()))],
-      command: CommandInput::new(),
+    let mut app = Self {
+      view_stack: vec![Box::new(issues)],
+      command,
+      aliases,
+      keys,
       config,
       jira,
+      replay: None,
+      last_replay: None,
+      pending_mutations: 0,
+      watch: None,
+      last_watch: None,
+      unread_notifications: 0,
+      branch_issue,
+      flash: None,
       should_quit: false,
-    })
+    };
+    match (&app.branch_issue, open_current) {
+      (_, true) => app.open_branch_issue(),
+      (Some(key), false) => {
+        let hint = format!("Current git branch is for {} (:current to open)", key);
+        app.set_flash(hint);
+      }
+      (None, false) => {}
+    }
+    Ok(app)
   }
 
   pub async fn run(&mut self) -> Result<()> {
@@ -93,12 +229,150 @@ impl App {
     for view in &mut self.view_stack {
       view.tick();
     }
+
+    self.tick_auto_refresh();
+
+    if let Some(msg) = self.view_stack.last_mut().and_then(|v| v.take_message()) {
+      self.set_flash(msg);
+    }
+    if self
+      .flash
+      .as_ref()
+      .is_some_and(|(_, at)| at.elapsed() > FLASH_DURATION)
+    {
+      self.flash = None;
+    }
+
+    self.tick_replay();
+    self.tick_watch();
+  }
+
+  /// Periodically check the notification watch query while online
+  fn tick_watch(&mut self) {
+    self.unread_notifications = self.jira.unread_notification_count().unwrap_or(0);
+
+    let Some(jql) = self.config.notifications.jql.clone() else {
+      return;
+    };
+
+    if let Some(query) = &mut self.watch {
+      query.poll();
+      if query.is_loading() {
+        return;
+      }
+      match (query.data(), query.error()) {
+        (_, Some(err)) => warn!("Notification check failed: {}", err),
+        (Some(added), _) if !added.is_empty() => {
+          let body = match added.as_slice() {
+            [(key, message)] => format!("{} {}", key, message),
+            _ => format!("{} new notifications", added.len()),
+          };
+          send_alert(self.config.notifications.alert, &body);
+          self.set_flash(format!("{} - see :notifications", body));
+        }
+        _ => {}
+      }
+      self.watch = None;
+      return;
+    }
+
+    let due = self
+      .last_watch
+      .is_none_or(|at| at.elapsed() >= self.config.notifications.interval());
+    if !due || self.jira.is_offline() {
+      return;
+    }
+    self.last_watch = Some(Instant::now());
+
+    let jira = self.jira.clone();
+    let mut query = Query::new(move || {
+      let jira = jira.clone();
+      let jql = jql.clone();
+      async move { jira.check_watched(&jql).await.map_err(|e| e.to_string()) }
+    });
+    query.fetch();
+    self.watch = Some(query);
+  }
+
+  /// Refetch the visible view's data in the background once it's older
+  /// than its configured refresh interval
+  fn tick_auto_refresh(&mut self) {
+    if self.jira.is_offline() {
+      return;
+    }
+    let Some(view) = self.view_stack.last_mut() else {
+      return;
+    };
+    let interval = view
+      .refresh_name()
+      .and_then(|name| self.config.refresh.interval_for(name));
+    if let Some(interval) = interval {
+      view.auto_refresh(interval);
+    }
+  }
+
+  /// Periodically replay queued offline changes while online
+  fn tick_replay(&mut self) {
+    self.pending_mutations = self.jira.pending_mutation_count().unwrap_or(0);
+
+    if let Some(query) = &mut self.replay {
+      query.poll();
+      if query.is_loading() {
+        return;
+      }
+      match (query.data(), query.error()) {
+        (_, Some(err)) => self.flash = Some((format!("Replay failed: {}", err), Instant::now())),
+        (Some(summary), _) if summary.applied + summary.conflicts + summary.failed > 0 => {
+          self.flash = Some((summary.to_string(), Instant::now()));
+          if let Some(view) = self.view_stack.last_mut() {
+            view.refresh();
+          }
+        }
+        _ => {}
+      }
+      self.replay = None;
+      return;
+    }
+
+    let due = self
+      .last_replay
+      .is_none_or(|at| at.elapsed() >= REPLAY_INTERVAL);
+    if due {
+      self.start_replay();
+    }
+  }
+
+  /// Start replaying the offline queue if there is anything to send
+  fn start_replay(&mut self) {
+    self.last_replay = Some(Instant::now());
+    if self.replay.is_some() || self.jira.is_offline() {
+      return;
+    }
+    if !self.jira.pending_mutation_count().is_ok_and(|n| n > 0) {
+      return;
+    }
+
+    let jira = self.jira.clone();
+    let mut query = Query::new(move || {
+      let jira = jira.clone();
+      async move { jira.replay_mutations().await.map_err(|e| e.to_string()) }
+    });
+    query.fetch();
+    self.replay = Some(query);
+  }
+
+  fn set_flash(&mut self, msg: String) {
+    self.flash = Some((msg, Instant::now()));
   }
 
   fn handle_key(&mut self, key: KeyEvent) {
     // Let command component try to handle first
     match self.command.handle_key(key) {
       KeyResult::Handled => return,
+      KeyResult::Event(CommandEvent::Activated) => {
+        self.load_command_completions();
+        return;
+      }
       KeyResult::Event(CommandEvent::Submitted(cmd)) => {
         self.execute_command(&cmd);
         return;
@@ -107,8 +381,8 @@ impl App {
       KeyResult::NotHandled => {}
     }
 
-    // Ctrl+C always quits
-    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+    // Quit (Ctrl+C) works in every view
+    if self.keys.is(Action::Quit, &key) {
       self.should_quit = true;
       return;
     }
@@ -117,7 +391,7 @@ impl App {
     if let Some(view) = self.view_stack.last_mut() {
       match view.handle_key(key) {
         ViewAction::Push(new_view) => {
-          self.view_stack.push(new_view);
+          self.push_view(new_view);
         }
         ViewAction::Pop => {
           if self.view_stack.len() > 1 {
@@ -129,37 +403,316 @@ impl App {
     }
   }
 
+  /// Push a view, applying view settings from the config
+  fn push_view(&mut self, mut view: Box<dyn View>) {
+    view.configure(&self.config);
+    self.view_stack.push(view);
+  }
+
+  /// Replace the whole view stack with a single view
+  fn set_root_view(&mut self, view: Box<dyn View>) {
+    self.view_stack.clear();
+    self.push_view(view);
+  }
+
+  /// Give the command palette its history and argument candidates from
+  /// the cache
+  fn load_command_completions(&mut self) {
+    match self.jira.command_history() {
+      Ok(history) => self.command.set_history(history),
+      Err(e) => warn!("Failed to load command history: {}", e),
+    }
+
+    let boards = self.jira.cached_boards().unwrap_or_default();
+    let issues = self.jira.cached_issues().unwrap_or_default();
+
+    let mut projects: BTreeSet<String> = boards
+      .iter()
+      .filter_map(|b| b.project_key.clone())
+      .chain(self.config.default_project.clone())
+      .collect();
+    projects.extend(
+      issues
+        .iter()
+        .filter_map(|i| i.key.rsplit_once('-'))
+        .map(|(project, _)| project.to_string()),
+    );
+    self.command.set_completions(
+      ArgKind::Project,
+      projects
+        .into_iter()
+        .map(|p| Suggestion::new(p, "project"))
+        .collect(),
+    );
+
+    let mut boards: Vec<Suggestion> = boards
+      .into_iter()
+      .map(|b| Suggestion::new(b.id.to_string(), b.name))
+      .collect();
+    boards.sort_by(|a, b| a.description.cmp(&b.description));
+    self.command.set_completions(ArgKind::Board, boards);
+
+    let mut epics: Vec<Suggestion> = issues
+      .into_iter()
+      .filter(|i| i.issue_type == "Epic")
+      .map(|i| Suggestion::new(i.key, i.summary))
+      .collect();
+    epics.sort_by(|a, b| a.value.cmp(&b.value));
+    self.command.set_completions(ArgKind::Epic, epics);
+  }
+
   fn execute_command(&mut self, cmd: &str) {
-    match cmd {
+    let mut parsed = match commands::parse(cmd, &self.aliases) {
+      Ok(parsed) => parsed,
+      Err(e) => {
+        self.set_flash(e.to_string());
+        return;
+      }
+    };
+    // Only commands that parse are worth recalling
+    if let Err(e) = self.jira.add_command_history(cmd) {
+      warn!("Failed to save command history: {}", e);
+    }
+    let current_project = self.current_project().to_string();
+    parsed.arg = parsed
+      .arg
+      .map(|arg| commands::expand_variables(&arg, &current_project, Local::now().date_naive()));
+
+    // Project keys are upper case; accept `:issues proj`
+    let project = parsed
+      .arg
+      .as_deref()
+      .map(str::to_uppercase)
+      .or_else(|| self.config.default_project.clone());
+
+    match parsed.name() {
       "issues" => {
-        let project = self.config.default_project.clone().unwrap_or_default();
-        self.view_stack = vec![Box::new(IssueListView::new(project, self.jira.clone()))];
+        let project = project.unwrap_or_default();
+        self.set_root_view(Box::new(IssueListView::new(project, self.jira.clone())));
       }
       "boards" => {
-        let project = self.config.default_project.clone();
-        let hide_swimlanes = self.config.boards.hide_swimlanes.clone();
-        self.view_stack = vec![Box::new(BoardListView::new(
+        self.set_root_view(Box::new(BoardListView::new(
           project,
           self.jira.clone(),
-          hide_swimlanes,
-        ))];
+          self.config.boards.clone(),
+        )));
       }
+      "board" => self.open_board(parsed.arg.as_deref().unwrap_or_default()),
+      "metrics" => {
+        let boards = self.config.boards.clone();
+        self.open_board_report(
+          parsed.arg.as_deref().unwrap_or_default(),
+          |id, name, jira| Box::new(MetricsView::new(id, name, jira, &boards)),
+        )
+      }
+      "burndown" => self.open_board_report(
+        parsed.arg.as_deref().unwrap_or_default(),
+        |id, name, jira| Box::new(BurndownView::new(id, name, jira)),
+      ),
       "epics" => {
-        let project = self.config.default_project.clone().unwrap_or_default();
-        self.view_stack = vec![Box::new(EpicListView::new(project, self.jira.clone()))];
+        let project = project.unwrap_or_default();
+        self.set_root_view(Box::new(EpicListView::new(project, self.jira.clone())));
+      }
+      "epic" => self.open_epic(parsed.arg.as_deref().unwrap_or_default()),
+      "tree" => {
+        let project = project.unwrap_or_default();
+        self.set_root_view(Box::new(TreeView::new(project, self.jira.clone())));
+      }
+      "jql" => {
+        let label = parsed.alias.unwrap_or_else(|| "JQL".to_string());
+        let jql = parsed.arg.unwrap_or_default();
+        self.set_root_view(Box::new(IssueListView::search(
+          label,
+          current_project,
+          jql,
+          self.jira.clone(),
+        )));
+      }
+      "export" => {
+        let message = match self.export(parsed.arg.as_deref().unwrap_or_default()) {
+          Ok(message) => message,
+          Err(e) => format!("Export failed: {}", e),
+        };
+        self.set_flash(message);
+      }
+      "offline" => {
+        self.jira.set_offline(!self.jira.is_offline());
+        // Reload the current view so it reflects the new mode
+        if let Some(view) = self.view_stack.last_mut() {
+          view.refresh();
+        }
+        // Send anything queued while offline right away
+        self.start_replay();
+      }
+      "cache" => {
+        self.push_view(Box::new(CacheView::new(
+          self.jira.clone(),
+          self.config.cache.max_size_bytes(),
+        )));
       }
-      "searches" => {
-        // TODO: Implement saved searches view
+      "current" => self.open_branch_issue(),
+      "notifications" => {
+        self.push_view(Box::new(NotificationsView::new(self.jira.clone())));
       }
+      "pending" => {
+        self.push_view(Box::new(PendingMutationsView::new(self.jira.clone())));
+      }
+      // There is no saved filter view yet, so hand over to the web UI
+      "searches" => match parsed.arg.as_deref().map(str::parse::<u64>).transpose() {
+        Ok(id) => {
+          let url = self.jira.filter_url(id);
+          self.set_flash(browser::open_with_message(&url));
+        }
+        Err(_) => self.set_flash("Filter ids are numbers, e.g. :searches 10042".to_string()),
+      },
       "quit" => {
         self.should_quit = true;
       }
-      _ => {
-        // Unknown command
+      _ => {}
+    }
+  }
+
+  /// Open a board by id or name (`:board 42`, `:board Team A`).
+  ///
+  /// Names are matched against cached boards, exactly first and then by
+  /// substring. Without a single match the board list opens filtered to
+  /// the name instead.
+  fn open_board(&mut self, arg: &str) {
+    let list = BoardListView::new(
+      self.config.default_project.clone(),
+      self.jira.clone(),
+      self.config.boards.clone(),
+    );
+    match self.find_board(arg) {
+      Some((id, name)) => {
+        self.set_root_view(Box::new(list));
+        self.push_view(Box::new(BoardView::new(
+          id,
+          name,
+          self.jira.clone(),
+          &self.config.boards,
+        )));
+      }
+      None => self.set_root_view(Box::new(list.with_filter(arg))),
+    }
+  }
+
+  /// Open a view of a board's metrics or sprint (`:metrics Team A`) on
+  /// top of the board
+  fn open_board_report(
+    &mut self,
+    arg: &str,
+    view: impl FnOnce(u64, String, JiraClient) -> Box<dyn View>,
+  ) {
+    let Some((id, name)) = self.find_board(arg) else {
+      self.set_flash(format!("Unknown board: {}", arg));
+      return;
+    };
+    self.open_board(&id.to_string());
+    self.push_view(view(id, name, self.jira.clone()));
+  }
+
+  /// A board by id, exact name or unambiguous part of the name, from the
+  /// cached board list
+  fn find_board(&self, arg: &str) -> Option<(u64, String)> {
+    let boards = self.jira.cached_boards().unwrap_or_default();
+    let needle = arg.to_lowercase();
+
+    match arg.parse::<u64>() {
+      Ok(id) => Some((
+        id,
+        boards
+          .iter()
+          .find(|b| b.id == id)
+          .map(|b| b.name.clone())
+          .unwrap_or_else(|| format!("Board {}", id)),
+      )),
+      Err(_) => {
+        let exact = boards.iter().find(|b| b.name.to_lowercase() == needle);
+        let mut partial = boards
+          .iter()
+          .filter(|b| b.name.to_lowercase().contains(&needle));
+        exact
+          .or_else(|| match (partial.next(), partial.next()) {
+            (Some(board), None) => Some(board),
+            _ => None,
+          })
+          .map(|b| (b.id, b.name.clone()))
       }
     }
   }
 
+  /// Open an epic by key (`:epic PROJ-12`) on top of its project's epics
+  fn open_epic(&mut self, key: &str) {
+    let key = key.to_uppercase();
+    let epic = self
+      .jira
+      .cache()
+      .get_entity::<IssueSummary>(&key)
+      .ok()
+      .flatten()
+      .unwrap_or_else(|| IssueSummary {
+        key: key.clone(),
+        issue_type: "Epic".to_string(),
+        ..Default::default()
+      });
+    let project = key
+      .rsplit_once('-')
+      .map(|(project, _)| project.to_string())
+      .unwrap_or_default();
+
+    self.set_root_view(Box::new(EpicListView::new(project, self.jira.clone())));
+    self.push_view(Box::new(EpicDetailView::new(epic, self.jira.clone())));
+  }
+
+  /// Show the issue of the current git branch
+  fn open_branch_issue(&mut self) {
+    match self.branch_issue.clone() {
+      Some(key) => self.push_view(Box::new(IssueDetailView::new(key, self.jira.clone()))),
+      None => self.set_flash("No issue key in the current git branch".to_string()),
+    }
+  }
+
+  /// Write the current issue list to a file (`:export csv|json|md [path]`)
+  /// or copy it to the clipboard as a Markdown table (`:export clip`)
+  fn export(&self, args: &str) -> Result<String> {
+    let (issues, columns) = self
+      .view_stack
+      .last()
+      .and_then(|v| v.visible_issues())
+      .ok_or_else(|| eyre!("this view has no issue list"))?;
+
+    let (format, path) = args.trim().split_once(' ').unwrap_or((args.trim(), ""));
+    if format == "clip" {
+      clipboard::copy(&export::render(ExportFormat::Markdown, &columns, &issues))?;
+      return Ok(format!(
+        "Copied {} issues as a Markdown table",
+        issues.len()
+      ));
+    }
+
+    let format = ExportFormat::parse(format)
+      .ok_or_else(|| eyre!("usage: export csv|json|md [path], or export clip"))?;
+    let path = match path.trim() {
+      "" => PathBuf::from(format!(
+        "j9s-{}.{}",
+        Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+      )),
+      path => match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+      },
+    };
+    std::fs::write(&path, export::render(format, &columns, &issues))
+      .map_err(|e| eyre!("{}: {}", path.display(), e))?;
+    Ok(format!(
+      "Exported {} issues to {}",
+      issues.len(),
+      path.display()
+    ))
+  }
+
   // Accessors for UI rendering
   pub fn current_view_mut(&mut self) -> Option<&mut dyn View> {
     match self.view_stack.last_mut() {
@@ -193,6 +746,56 @@ impl App {
       .collect()
   }
 
+  /// Badge shown in the header when data isn't coming from the network.
+  ///
+  /// Shown when explicit offline mode is on, or when the current view is
+  /// displaying cached data because the network was unreachable.
+  pub fn connection_badge(&self) -> Option<String> {
+    let source = self.view_stack.last().and_then(|v| v.data_source());
+    let view_offline = source.is_some_and(|s| s.is_offline());
+
+    if !self.jira.is_offline() && !view_offline {
+      return None;
+    }
+
+    let mut badge = match source.and_then(|s| s.cached_at) {
+      Some(cached_at) => format!("OFFLINE · cached {}", format_age(Utc::now() - cached_at)),
+      None => "OFFLINE".to_string(),
+    };
+    if self.pending_mutations > 0 {
+      badge.push_str(&format!(" · {} queued", self.pending_mutations));
+    }
+    Some(badge)
+  }
+
+  /// How long ago the current view's data was fetched, for the footer.
+  ///
+  /// Data served from the cache counts from when it was cached.
+  pub fn updated_indicator(&self) -> Option<String> {
+    let view = self.view_stack.last()?;
+    let fetched_at = view.fetched_at()?;
+    let age = match view.data_source().and_then(|s| s.cached_at) {
+      Some(cached_at) => Utc::now() - cached_at,
+      None => TimeDelta::from_std(fetched_at.elapsed()).unwrap_or(TimeDelta::MAX),
+    };
+    let secs = age.num_seconds().max(0);
+    Some(if secs < 60 {
+      format!("updated {}s ago", secs)
+    } else {
+      format!("updated {}", format_age(age))
+    })
+  }
+
+  /// Number of notifications not yet seen in the notification center
+  pub fn unread_notifications(&self) -> usize {
+    self.unread_notifications
+  }
+
+  /// Recent status message to show in the footer
+  pub fn flash_message(&self) -> Option<&str> {
+    self.flash.as_ref().map(|(msg, _)| msg.as_str())
+  }
+
   /// Render command overlay if active
   pub fn render_command_overlay(&self, frame: &mut Frame, area: Rect) {
     self.command.render_overlay(frame, area);
@@ -206,21 +809,16 @@ impl App {
       .map(|v| v.shortcuts())
       .unwrap_or_else(|| {
         vec![
-          ShortcutInfo::new(":", "command").with_priority(10),
-          ShortcutInfo::new("/", "search").with_priority(20),
-          ShortcutInfo::new("q", "back").with_priority(30),
+          self
+            .keys
+            .shortcut(Action::Command, "command")
+            .with_priority(10),
+          self
+            .keys
+            .shortcut(Action::Search, "search")
+            .with_priority(20),
+          self.keys.shortcut(Action::Back, "back").with_priority(30),
         ]
       })
   }
 }
-
-/// Extract domain from Jira URL
-fn extract_domain(url: &str) -> &str {
-  url
-    .strip_prefix("https://")
-    .or_else(|| url.strip_prefix("http://"))
-    .unwrap_or(url)
-    .split('/')
-    .next()
-    .unwrap_or(url)
-}
@@ -0,0 +1,43 @@
+//! Opening web pages in the user's browser.
+
+use color_eyre::{eyre::eyre, Result};
+use std::process::{Command, Stdio};
+
+/// Open a URL with `$BROWSER`, or the platform's default handler.
+///
+/// `$BROWSER` may contain `%s` for the URL; otherwise the URL is appended.
+pub fn open(url: &str) -> Result<()> {
+  let command = match std::env::var("BROWSER") {
+    Ok(browser) if !browser.trim().is_empty() => browser,
+    _ if cfg!(target_os = "macos") => "open".to_string(),
+    _ if cfg!(target_os = "windows") => "explorer".to_string(),
+    _ => "xdg-open".to_string(),
+  };
+
+  let mut words = command.split_whitespace();
+  let program = words.next().unwrap_or_default();
+  let mut args: Vec<String> = words.map(|w| w.replace("%s", url)).collect();
+  if !command.contains("%s") {
+    args.push(url.to_string());
+  }
+
+  // Output would draw over the TUI
+  let mut child = Command::new(program)
+    .args(&args)
+    .stdin(Stdio::null())
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .spawn()
+    .map_err(|e| eyre!("Failed to run {}: {}", program, e))?;
+  // Reap the process without blocking the UI
+  std::thread::spawn(move || child.wait());
+  Ok(())
+}
+
+/// Open a URL, returning a message for the footer
+pub fn open_with_message(url: &str) -> String {
+  match open(url) {
+    Ok(()) => format!("Opened {}", url),
+    Err(e) => format!("Open failed: {}", e),
+  }
+}
@@ -0,0 +1,58 @@
+//! Command palette history.
+
+use color_eyre::{eyre::eyre, Result};
+use rusqlite::params;
+
+use super::storage::SqliteStorage;
+
+/// Oldest commands beyond this many are dropped
+const MAX_HISTORY: i64 = 200;
+
+impl SqliteStorage {
+  /// Record a command as the most recent one. Repeated commands move to
+  /// the end instead of being stored twice.
+  pub fn add_command_history(&self, command: &str) -> Result<()> {
+    let conn = self.lock()?;
+    conn
+      .execute(
+        "INSERT OR REPLACE INTO command_history (command) VALUES (?)",
+        params![command],
+      )
+      .map_err(|e| eyre!("Failed to add command history: {}", e))?;
+    conn
+      .execute(
+        "DELETE FROM command_history WHERE id <= (SELECT MAX(id) FROM command_history) - ?",
+        params![MAX_HISTORY],
+      )
+      .map_err(|e| eyre!("Failed to trim command history: {}", e))?;
+    Ok(())
+  }
+
+  /// Get command history, oldest first.
+  pub fn command_history(&self) -> Result<Vec<String>> {
+    let conn = self.lock()?;
+    let mut stmt = conn
+      .prepare("SELECT command FROM command_history ORDER BY id")
+      .map_err(|e| eyre!("Failed to prepare query: {}", e))?;
+    let commands = stmt
+      .query_map([], |row| row.get(0))
+      .map_err(|e| eyre!("Failed to query command history: {}", e))?
+      .filter_map(|r| r.ok())
+      .collect();
+    Ok(commands)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_command_history_moves_repeats_to_end() {
+    let storage = SqliteStorage::new(crate::db::open_in_memory().unwrap());
+    storage.add_command_history("issues").unwrap();
+    storage.add_command_history("board 12").unwrap();
+    storage.add_command_history("issues").unwrap();
+    assert_eq!(storage.command_history().unwrap(), ["board 12", "issues"]);
+  }
+}
@@ -1,12 +1,29 @@
 //! Cache layer that orchestrates caching logic with network fetching.
 
 use chrono::{Duration, Utc};
-use color_eyre::Result;
+use color_eyre::{eyre::eyre, Result};
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use tracing::{info, warn};
 
 use super::storage::CacheStorage;
-use super::traits::{CacheResult, Cacheable};
+use super::traits::{CacheResult, CacheSource, Cacheable};
+
+tokio::task_local! {
+  /// Set while revalidating: the maximum age of cached data to use
+  static MAX_AGE: Duration;
+}
+
+/// Run fetches that go to the network for cached data older than `max_age`,
+/// even when it isn't stale yet, e.g. for auto-refresh at a shorter
+/// interval than the stale time. Data cached for good (a stale time of
+/// `Duration::MAX`, like histories cached per version) is still used.
+pub async fn revalidate<F: Future>(max_age: std::time::Duration, fetch: F) -> F::Output {
+  let max_age = Duration::from_std(max_age).unwrap_or(Duration::MAX);
+  MAX_AGE.scope(max_age, fetch).await
+}
 
 /// Cache layer that manages caching logic and network fetching.
 ///
@@ -16,6 +33,13 @@ pub struct CacheLayer<S: CacheStorage> {
   storage: Arc<S>,
   /// How long before cached data is considered stale
   stale_time: Duration,
+  /// Stale time overrides by entity type
+  stale_times: HashMap<String, Duration>,
+  /// How often incrementally fetched lists are checked for removed entries
+  reconcile_interval: Duration,
+  /// Explicit offline mode - never call fetchers, serve cache only.
+  /// Shared between clones so toggling affects every holder of the layer.
+  offline: Arc<AtomicBool>,
 }
 
 impl<S: CacheStorage> CacheLayer<S> {
@@ -24,24 +48,104 @@ impl<S: CacheStorage> CacheLayer<S> {
     Self {
       storage: Arc::new(storage),
       stale_time: Duration::minutes(5),
+      stale_times: HashMap::new(),
+      reconcile_interval: Duration::minutes(15),
+      offline: Arc::new(AtomicBool::new(false)),
     }
   }
 
+  /// Start the cache layer in explicit offline mode.
+  pub fn with_offline(self, offline: bool) -> Self {
+    self.set_offline(offline);
+    self
+  }
+
+  /// Check if explicit offline mode is enabled.
+  pub fn is_offline(&self) -> bool {
+    self.offline.load(Ordering::Relaxed)
+  }
+
+  /// Enable or disable explicit offline mode.
+  pub fn set_offline(&self, offline: bool) {
+    self.offline.store(offline, Ordering::Relaxed);
+  }
+
   /// Set the stale time for cached data.
-  #[allow(dead_code)]
   pub fn with_stale_time(mut self, stale_time: Duration) -> Self {
     self.stale_time = stale_time;
     self
   }
 
+  /// Set the stale time for one entity type (see `Cacheable::entity_type`).
+  pub fn with_stale_time_for(mut self, entity_type: &str, stale_time: Duration) -> Self {
+    self.stale_times.insert(entity_type.to_string(), stale_time);
+    self
+  }
+
+  /// Get the stale time that applies to an entity type.
+  pub fn stale_time_for(&self, entity_type: &str) -> Duration {
+    self
+      .stale_times
+      .get(entity_type)
+      .copied()
+      .unwrap_or(self.stale_time)
+  }
+
+  /// Set how often incrementally fetched lists are reconciled.
+  pub fn with_reconcile_interval(mut self, interval: Duration) -> Self {
+    self.reconcile_interval = interval;
+    self
+  }
+
   /// Check if cached data is stale based on cached_at timestamp.
-  fn is_stale(&self, cached_at: chrono::DateTime<Utc>) -> bool {
-    Utc::now() - cached_at > self.stale_time
+  fn is_stale<T: Cacheable>(&self, cached_at: chrono::DateTime<Utc>) -> bool {
+    let stale_time = self.stale_time_for(T::entity_type());
+    let max_age = match MAX_AGE.try_with(|max_age| *max_age) {
+      Ok(max_age) if stale_time != Duration::MAX => stale_time.min(max_age),
+      _ => stale_time,
+    };
+    Utc::now() - cached_at > max_age
+  }
+
+  /// Get the underlying storage backend.
+  pub fn storage(&self) -> &S {
+    &self.storage
+  }
+
+  /// Read a cached entity without touching the network.
+  pub fn get_entity<T: Cacheable>(&self, entity_key: &str) -> Result<Option<T>> {
+    Ok(self.storage.get_entity::<T>(entity_key)?.map(|c| c.entity))
+  }
+
+  /// Modify a cached entity in place, e.g. to apply an optimistic update.
+  ///
+  /// Returns false if the entity isn't cached.
+  pub fn update_entity<T, F>(&self, entity_key: &str, update: F) -> Result<bool>
+  where
+    T: Cacheable,
+    F: FnOnce(&mut T),
+  {
+    match self.storage.get_entity::<T>(entity_key)? {
+      Some(mut cached) => {
+        update(&mut cached.entity);
+        self.storage.store_entity(&cached.entity)?;
+        Ok(true)
+      }
+      None => Ok(false),
+    }
+  }
+
+  /// Fail with a descriptive error when a cache miss can't go to the network.
+  fn ensure_online(&self, key: &str) -> Result<()> {
+    if self.is_offline() {
+      return Err(eyre!("Not available offline (nothing cached for {})", key));
+    }
+    Ok(())
   }
 
   /// Fetch a list with cache-first strategy.
   ///
-  /// 1. Check cache - if fresh, return immediately
+  /// 1. Check cache - if fresh (or in explicit offline mode), return immediately
   /// 2. If stale/missing, fetch from network
   /// 3. On network failure, return stale cache (offline mode)
   /// 4. Update cache with new data
@@ -55,7 +159,11 @@ impl<S: CacheStorage> CacheLayer<S> {
   {
     // Check cache first
     if let Some(cached) = self.storage.get_query_result::<T>(key)? {
-      if !self.is_stale(cached.cached_at) {
+      if self.is_offline() {
+        return Ok(CacheResult::offline(cached.entities, cached.cached_at));
+      }
+
+      if !self.is_stale::<T>(cached.cached_at) {
         // Cache is fresh, return immediately
         return Ok(CacheResult::from_cache(
           cached.entities,
@@ -78,6 +186,7 @@ impl<S: CacheStorage> CacheLayer<S> {
       }
     } else {
       // No cache, must fetch from network
+      self.ensure_online(key)?;
       let data = fetcher().await?;
       self.storage.store_query_result(key, &data)?;
       Ok(CacheResult::from_network(data))
@@ -106,7 +215,11 @@ impl<S: CacheStorage> CacheLayer<S> {
 
     // Check cache first
     if let Some(cached) = self.storage.get_query_result::<T>(key)? {
-      if !self.is_stale(cached.cached_at) {
+      if self.is_offline() {
+        return Ok(CacheResult::offline(cached.entities, cached.cached_at));
+      }
+
+      if !self.is_stale::<T>(cached.cached_at) {
         // Cache is fresh, return immediately
         return Ok(CacheResult::from_cache(
           cached.entities,
@@ -142,12 +255,135 @@ impl<S: CacheStorage> CacheLayer<S> {
       }
     } else {
       // No cache, must do full fetch (no updated_since filter)
+      self.ensure_online(key)?;
       let data = fetcher(None).await?;
       self.storage.store_query_result(key, &data)?;
+      // A full fetch is as good as a reconciliation
+      self.storage.mark_reconciled(key)?;
       Ok(CacheResult::from_network(data))
     }
   }
 
+  /// Incremental fetch that periodically reconciles the cached list.
+  ///
+  /// Incremental fetches only see entities that changed, so ones that were
+  /// deleted or no longer match the query would stay cached forever. When the
+  /// reconcile interval has passed, `keys_fetcher` gets just the keys matching
+  /// the query; cached entries not among them are pruned, and `by_keys_fetcher`
+  /// loads any keys missing from the cache (which also covers renamed keys).
+  pub async fn fetch_reconciled<T, F, Fut, KF, KFut, BF, BFut>(
+    &self,
+    key: &str,
+    fetcher: F,
+    keys_fetcher: KF,
+    by_keys_fetcher: BF,
+  ) -> Result<CacheResult<Vec<T>>>
+  where
+    T: Cacheable,
+    F: FnOnce(Option<&str>) -> Fut,
+    Fut: Future<Output = Result<Vec<T>>>,
+    KF: FnOnce() -> KFut,
+    KFut: Future<Output = Result<Vec<String>>>,
+    BF: FnOnce(Vec<String>) -> BFut,
+    BFut: Future<Output = Result<Vec<T>>>,
+  {
+    let result = self.fetch_incremental(key, fetcher).await?;
+    if result.source == CacheSource::Offline || !self.is_reconcile_due(key)? {
+      return Ok(result);
+    }
+
+    let reconciled = async {
+      let keys = keys_fetcher().await?;
+      let cached: HashSet<String> = result.data.iter().map(|e| e.cache_key()).collect();
+      let missing: Vec<String> = keys
+        .iter()
+        .filter(|k| !cached.contains(*k))
+        .cloned()
+        .collect();
+      let fetched = if missing.is_empty() {
+        Vec::new()
+      } else {
+        by_keys_fetcher(missing).await?
+      };
+      self.storage.reconcile_query_result(key, &keys, &fetched)
+    };
+
+    match reconciled.await {
+      Ok(pruned) => {
+        if !pruned.is_empty() {
+          info!("Pruned {} stale entries from {}", pruned.len(), key);
+        }
+        match self.storage.get_query_result::<T>(key)? {
+          Some(cached) => Ok(CacheResult {
+            data: cached.entities,
+            ..result
+          }),
+          None => Ok(result),
+        }
+      }
+      Err(e) => {
+        // Not fatal: the incremental result is still usable
+        warn!("Failed to reconcile {}: {}", key, e);
+        Ok(result)
+      }
+    }
+  }
+
+  /// Fetch what changed in a list since the last fetch, whether or not the
+  /// cache is stale, and merge it into the cache.
+  ///
+  /// Returns each changed entity paired with its previously cached version
+  /// (None if it's new to the list). The first fetch only records a baseline
+  /// and reports nothing, so a fresh cache doesn't report every entity.
+  pub async fn fetch_changes<T, F, Fut>(&self, key: &str, fetcher: F) -> Result<Vec<(Option<T>, T)>>
+  where
+    T: Cacheable,
+    F: FnOnce(Option<&str>) -> Fut,
+    Fut: Future<Output = Result<Vec<T>>>,
+  {
+    self.ensure_online(key)?;
+
+    let Some(cached) = self.storage.get_query_result::<T>(key)? else {
+      let data = fetcher(None).await?;
+      self.storage.store_query_result(key, &data)?;
+      return Ok(Vec::new());
+    };
+
+    let max_updated = self.storage.get_max_updated(key)?;
+    let fetched = fetcher(max_updated.as_deref()).await?;
+    if fetched.is_empty() {
+      return Ok(Vec::new());
+    }
+    self.storage.merge_query_result(key, &fetched)?;
+
+    let mut previous: HashMap<String, T> = cached
+      .entities
+      .into_iter()
+      .map(|e| (e.cache_key(), e))
+      .collect();
+
+    // Timestamp filters can return entities we've already seen
+    Ok(
+      fetched
+        .into_iter()
+        .map(|e| (previous.remove(&e.cache_key()), e))
+        .filter(|(old, new)| {
+          old
+            .as_ref()
+            .is_none_or(|old| old.updated_at() != new.updated_at())
+        })
+        .collect(),
+    )
+  }
+
+  /// Check whether a cached list is due for reconciliation.
+  fn is_reconcile_due(&self, key: &str) -> Result<bool> {
+    Ok(match self.storage.get_reconciled_at(key)? {
+      Some(at) => Utc::now() - at > self.reconcile_interval,
+      None => true,
+    })
+  }
+
   /// Fetch a single entity with caching.
   pub async fn fetch_one<T, F, Fut>(&self, entity_key: &str, fetcher: F) -> Result<CacheResult<T>>
   where
@@ -157,7 +393,11 @@ impl<S: CacheStorage> CacheLayer<S> {
   {
     // Check cache first
     if let Some(cached) = self.storage.get_entity::<T>(entity_key)? {
-      if !self.is_stale(cached.cached_at) {
+      if self.is_offline() {
+        return Ok(CacheResult::offline(cached.entity, cached.cached_at));
+      }
+
+      if !self.is_stale::<T>(cached.cached_at) {
         // Cache is fresh
         return Ok(CacheResult::from_cache(
           cached.entity,
@@ -179,6 +419,7 @@ impl<S: CacheStorage> CacheLayer<S> {
       }
     } else {
       // No cache, must fetch from network
+      self.ensure_online(entity_key)?;
       let data = fetcher().await?;
       self.storage.store_entity(&data)?;
       Ok(CacheResult::from_network(data))
@@ -191,6 +432,54 @@ impl<S: CacheStorage> Clone for CacheLayer<S> {
     Self {
       storage: Arc::clone(&self.storage),
       stale_time: self.stale_time,
+      stale_times: self.stale_times.clone(),
+      reconcile_interval: self.reconcile_interval,
+      offline: Arc::clone(&self.offline),
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::cache::SqliteStorage;
+  use serde::{Deserialize, Serialize};
+
+  #[derive(Debug, Clone, Serialize, Deserialize)]
+  struct Item;
+
+  impl Cacheable for Item {
+    fn cache_key(&self) -> String {
+      String::new()
+    }
+
+    fn updated_at(&self) -> Option<&str> {
+      None
+    }
+
+    fn entity_type() -> &'static str {
+      "item"
+    }
+  }
+
+  fn layer(stale_time: Duration) -> CacheLayer<SqliteStorage> {
+    CacheLayer::new(SqliteStorage::new(crate::db::open_in_memory().unwrap()))
+      .with_stale_time(stale_time)
+  }
+
+  #[tokio::test]
+  async fn test_revalidate() {
+    let cached_at = Utc::now() - Duration::seconds(30);
+    let layer = layer(Duration::minutes(5));
+    assert!(!layer.is_stale::<Item>(cached_at));
+
+    let max_age = std::time::Duration::from_secs(10);
+    assert!(revalidate(max_age, async { layer.is_stale::<Item>(cached_at) }).await);
+    let minute = std::time::Duration::from_secs(60);
+    assert!(!revalidate(minute, async { layer.is_stale::<Item>(cached_at) }).await);
+
+    // Cached for good
+    let layer = self::layer(Duration::MAX);
+    assert!(!revalidate(max_age, async { layer.is_stale::<Item>(cached_at) }).await);
+  }
+}
@@ -0,0 +1,508 @@
+//! Cache housekeeping: statistics, size limits and pruning.
+//!
+//! Entities referenced by a cached query are kept as long as the query is.
+//! Everything else (single-entity fetches, entities dropped from queries)
+//! is "unreferenced" and is the first to go when the cache grows too large.
+
+use chrono::{DateTime, Duration, Utc};
+use color_eyre::{eyre::eyre, Result};
+use rusqlite::{params, Connection};
+
+use super::storage::{parse_datetime, SqliteStorage};
+
+/// Unreferenced entities unused for this long are pruned on startup
+const ORPHAN_MAX_AGE_DAYS: i64 = 30;
+
+/// Matches entities not referenced by any cached query of their type
+const UNREFERENCED: &str = "NOT EXISTS (
+  SELECT 1 FROM query_results qr JOIN query_cache qc ON qc.query_hash = qr.query_hash
+  WHERE qr.entity_key = ec.entity_key AND qc.entity_type = ec.entity_type)";
+
+/// Per-query cache statistics.
+#[derive(Debug, Clone)]
+pub struct QueryStats {
+  pub query_hash: String,
+  pub query_description: String,
+  pub entity_type: String,
+  pub result_count: usize,
+  pub cached_at: DateTime<Utc>,
+}
+
+/// Per-entity-type cache statistics.
+#[derive(Debug, Clone)]
+pub struct EntityStats {
+  pub entity_type: String,
+  pub count: usize,
+  /// Total serialized size of the entities
+  pub bytes: u64,
+}
+
+/// Overview of what's in the cache.
+#[derive(Debug, Clone, Default)]
+pub struct CacheStats {
+  /// Bytes used by the database (excluding free pages)
+  pub size_bytes: u64,
+  /// Bytes that `vacuum` would give back to the filesystem
+  pub free_bytes: u64,
+  pub entities: Vec<EntityStats>,
+  /// Entities not referenced by any cached query
+  pub unreferenced: usize,
+  /// Queries, most recently cached first
+  pub queries: Vec<QueryStats>,
+  pub pending_mutations: usize,
+}
+
+/// What a maintenance pass removed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvictionReport {
+  pub entities: usize,
+  pub queries: usize,
+}
+
+impl SqliteStorage {
+  /// Routine maintenance: prune stale orphans, then enforce the size limit.
+  pub fn maintain(&self, max_bytes: Option<u64>) -> Result<EvictionReport> {
+    let pruned = self.prune_orphans(Duration::days(ORPHAN_MAX_AGE_DAYS))?;
+    let mut report = match max_bytes {
+      Some(max) => self.enforce_size_limit(max)?,
+      None => EvictionReport::default(),
+    };
+    report.entities += pruned;
+    Ok(report)
+  }
+
+  /// Collect cache statistics.
+  pub fn stats(&self) -> Result<CacheStats> {
+    let conn = self.lock()?;
+    let (size_bytes, free_bytes) = db_size(&conn)?;
+
+    let entities = conn
+      .prepare(
+        "SELECT entity_type, COUNT(*), COALESCE(SUM(LENGTH(data)), 0)
+         FROM entity_cache GROUP BY entity_type ORDER BY entity_type",
+      )
+      .and_then(|mut stmt| {
+        stmt
+          .query_map([], |row| {
+            Ok(EntityStats {
+              entity_type: row.get(0)?,
+              count: row.get(1)?,
+              bytes: row.get(2)?,
+            })
+          })?
+          .collect::<rusqlite::Result<Vec<_>>>()
+      })
+      .map_err(|e| eyre!("Failed to query entity stats: {}", e))?;
+
+    let unreferenced = conn
+      .query_row(
+        &format!(
+          "SELECT COUNT(*) FROM entity_cache ec WHERE {}",
+          UNREFERENCED
+        ),
+        [],
+        |row| row.get(0),
+      )
+      .map_err(|e| eyre!("Failed to count unreferenced entities: {}", e))?;
+
+    let rows = conn
+      .prepare(
+        "SELECT query_hash, query_description, entity_type, result_count, cached_at
+         FROM query_cache ORDER BY cached_at DESC",
+      )
+      .and_then(|mut stmt| {
+        stmt
+          .query_map([], |row| {
+            Ok((
+              row.get::<_, String>(0)?,
+              row.get::<_, String>(1)?,
+              row.get::<_, String>(2)?,
+              row.get::<_, usize>(3)?,
+              row.get::<_, String>(4)?,
+            ))
+          })?
+          .collect::<rusqlite::Result<Vec<_>>>()
+      })
+      .map_err(|e| eyre!("Failed to query cached queries: {}", e))?;
+
+    let queries = rows
+      .into_iter()
+      .map(
+        |(query_hash, query_description, entity_type, result_count, cached_at)| {
+          Ok(QueryStats {
+            query_hash,
+            query_description,
+            entity_type,
+            result_count,
+            cached_at: parse_datetime(&cached_at)?,
+          })
+        },
+      )
+      .collect::<Result<Vec<_>>>()?;
+
+    let pending_mutations = conn
+      .query_row("SELECT COUNT(*) FROM pending_mutations", [], |row| {
+        row.get(0)
+      })
+      .map_err(|e| eyre!("Failed to count mutations: {}", e))?;
+
+    Ok(CacheStats {
+      size_bytes,
+      free_bytes,
+      entities,
+      unreferenced,
+      queries,
+      pending_mutations,
+    })
+  }
+
+  /// Remove all cached data. Queued offline changes are kept.
+  pub fn clear(&self) -> Result<()> {
+    let conn = self.lock()?;
+    conn
+      .execute_batch(
+        "BEGIN;
+         DELETE FROM query_results;
+         DELETE FROM query_cache;
+         DELETE FROM entity_cache;
+         COMMIT;",
+      )
+      .map_err(|e| eyre!("Failed to clear cache: {}", e))
+  }
+
+  /// Rebuild the database file to reclaim free space.
+  ///
+  /// Returns the file size before and after, in bytes.
+  pub fn vacuum(&self) -> Result<(u64, u64)> {
+    let conn = self.lock()?;
+    let before = file_size(&conn)?;
+    conn
+      .execute("VACUUM", [])
+      .map_err(|e| eyre!("Failed to vacuum cache: {}", e))?;
+    Ok((before, file_size(&conn)?))
+  }
+
+  /// Drop a single cached query (its entities become unreferenced).
+  pub fn remove_query(&self, query_hash: &str) -> Result<()> {
+    let conn = self.lock()?;
+    remove_query(&conn, query_hash)
+  }
+
+  /// Drop the cached queries starting with `prefix` except `current`, e.g.
+  /// older versions of a result that's cached per version.
+  pub fn remove_superseded(&self, prefix: &str, current: &str) -> Result<()> {
+    let conn = self.lock()?;
+    let superseded: Vec<String> = conn
+      .prepare(
+        "SELECT query_hash FROM query_cache
+         WHERE substr(query_hash, 1, length(?1)) = ?1 AND query_hash != ?2",
+      )
+      .and_then(|mut stmt| {
+        stmt
+          .query_map(params![prefix, current], |row| row.get(0))?
+          .collect::<rusqlite::Result<Vec<_>>>()
+      })
+      .map_err(|e| eyre!("Failed to read cached queries: {}", e))?;
+    for query_hash in superseded {
+      remove_query(&conn, &query_hash)?;
+    }
+    Ok(())
+  }
+
+  /// Remove dangling rows and unreferenced entities unused for `max_age`.
+  pub fn prune_orphans(&self, max_age: Duration) -> Result<usize> {
+    let conn = self.lock()?;
+
+    // Foreign keys aren't enforced, so the cascade never fires
+    conn
+      .execute(
+        "DELETE FROM query_results
+         WHERE query_hash NOT IN (SELECT query_hash FROM query_cache)",
+        [],
+      )
+      .map_err(|e| eyre!("Failed to prune query results: {}", e))?;
+
+    conn
+      .execute(
+        &format!(
+          "DELETE FROM entity_cache AS ec WHERE {}
+           AND COALESCE(accessed_at, cached_at) < datetime('now', ?)",
+          UNREFERENCED
+        ),
+        params![format!("-{} seconds", max_age.num_seconds())],
+      )
+      .map_err(|e| eyre!("Failed to prune entities: {}", e))
+  }
+
+  /// Evict least recently used data until the database fits in `max_bytes`.
+  ///
+  /// Unreferenced entities go first. If that isn't enough, whole queries
+  /// are evicted in LRU order along with entities only they referenced.
+  pub fn enforce_size_limit(&self, max_bytes: u64) -> Result<EvictionReport> {
+    let mut report = EvictionReport::default();
+    let conn = self.lock()?;
+
+    let (size, _) = db_size(&conn)?;
+    let Some(mut excess) = size.checked_sub(max_bytes).filter(|&n| n > 0) else {
+      return Ok(report);
+    };
+
+    // Unreferenced entities, least recently used first
+    let candidates = conn
+      .prepare(&format!(
+        "SELECT rowid, LENGTH(data) + LENGTH(entity_key) FROM entity_cache AS ec
+         WHERE {} ORDER BY COALESCE(accessed_at, cached_at)",
+        UNREFERENCED
+      ))
+      .and_then(|mut stmt| {
+        stmt
+          .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, u64>(1)?)))?
+          .collect::<rusqlite::Result<Vec<_>>>()
+      })
+      .map_err(|e| eyre!("Failed to find eviction candidates: {}", e))?;
+
+    for (rowid, bytes) in candidates {
+      if excess == 0 {
+        break;
+      }
+      conn
+        .execute("DELETE FROM entity_cache WHERE rowid = ?", params![rowid])
+        .map_err(|e| eyre!("Failed to evict entity: {}", e))?;
+      report.entities += 1;
+      excess = excess.saturating_sub(bytes);
+    }
+
+    // Whole queries, least recently used first
+    while excess > 0 {
+      let query_hash: Option<String> = conn
+        .query_row(
+          "SELECT query_hash FROM query_cache
+           ORDER BY COALESCE(accessed_at, cached_at) LIMIT 1",
+          [],
+          |row| row.get(0),
+        )
+        .ok();
+      let Some(query_hash) = query_hash else {
+        break;
+      };
+
+      remove_query(&conn, &query_hash)?;
+      report.queries += 1;
+
+      let freed: u64 = conn
+        .query_row(
+          &format!(
+            "SELECT COALESCE(SUM(LENGTH(data) + LENGTH(entity_key)), 0)
+             FROM entity_cache AS ec WHERE {}",
+            UNREFERENCED
+          ),
+          [],
+          |row| row.get(0),
+        )
+        .map_err(|e| eyre!("Failed to measure evicted entities: {}", e))?;
+      report.entities += conn
+        .execute(
+          &format!("DELETE FROM entity_cache AS ec WHERE {}", UNREFERENCED),
+          [],
+        )
+        .map_err(|e| eyre!("Failed to evict entities: {}", e))?;
+      excess = excess.saturating_sub(freed);
+    }
+
+    Ok(report)
+  }
+}
+
+/// Delete a query and its result mapping.
+fn remove_query(conn: &Connection, query_hash: &str) -> Result<()> {
+  conn
+    .execute(
+      "DELETE FROM query_results WHERE query_hash = ?",
+      params![query_hash],
+    )
+    .map_err(|e| eyre!("Failed to remove query results: {}", e))?;
+  conn
+    .execute(
+      "DELETE FROM query_cache WHERE query_hash = ?",
+      params![query_hash],
+    )
+    .map_err(|e| eyre!("Failed to remove query: {}", e))?;
+  Ok(())
+}
+
+/// Bytes in use and bytes on the free list.
+fn db_size(conn: &Connection) -> Result<(u64, u64)> {
+  conn
+    .query_row(
+      "SELECT (p.page_count - f.freelist_count) * s.page_size, f.freelist_count * s.page_size
+       FROM pragma_page_count() p, pragma_freelist_count() f, pragma_page_size() s",
+      [],
+      |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .map_err(|e| eyre!("Failed to measure cache size: {}", e))
+}
+
+/// Total size of the database file in bytes.
+fn file_size(conn: &Connection) -> Result<u64> {
+  let (used, free) = db_size(conn)?;
+  Ok(used + free)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::cache::storage::CacheStorage;
+  use crate::cache::Cacheable;
+  use serde::{Deserialize, Serialize};
+
+  #[derive(Debug, Clone, Serialize, Deserialize)]
+  struct Item {
+    key: String,
+    body: String,
+  }
+
+  impl Cacheable for Item {
+    fn cache_key(&self) -> String {
+      self.key.clone()
+    }
+
+    fn updated_at(&self) -> Option<&str> {
+      None
+    }
+
+    fn entity_type() -> &'static str {
+      "item"
+    }
+  }
+
+  /// Another entity type, with keys that can clash with items
+  #[derive(Debug, Clone, Serialize, Deserialize)]
+  struct Tag {
+    key: String,
+  }
+
+  impl Cacheable for Tag {
+    fn cache_key(&self) -> String {
+      self.key.clone()
+    }
+
+    fn updated_at(&self) -> Option<&str> {
+      None
+    }
+
+    fn entity_type() -> &'static str {
+      "tag"
+    }
+  }
+
+  fn item(key: &str) -> Item {
+    Item {
+      key: key.to_string(),
+      body: "x".repeat(4096),
+    }
+  }
+
+  fn storage() -> SqliteStorage {
+    SqliteStorage::new(crate::db::open_in_memory().unwrap())
+  }
+
+  #[test]
+  fn test_stats_and_clear() {
+    let storage = storage();
+    storage
+      .store_query_result("q1", &[item("A"), item("B")])
+      .unwrap();
+    storage.store_entity(&item("C")).unwrap();
+
+    let stats = storage.stats().unwrap();
+    assert_eq!(stats.queries.len(), 1);
+    assert_eq!(stats.queries[0].result_count, 2);
+    assert_eq!(stats.entities[0].count, 3);
+    assert_eq!(stats.unreferenced, 1);
+
+    storage.clear().unwrap();
+    let stats = storage.stats().unwrap();
+    assert!(stats.queries.is_empty());
+    assert!(stats.entities.is_empty());
+  }
+
+  #[test]
+  fn test_unreferenced_matches_entity_type() {
+    let storage = storage();
+    storage.store_query_result("q1", &[item("A")]).unwrap();
+    storage
+      .store_entity(&Tag {
+        key: "A".to_string(),
+      })
+      .unwrap();
+
+    // The item query doesn't keep a tag with the same key
+    assert_eq!(storage.stats().unwrap().unreferenced, 1);
+  }
+
+  #[test]
+  fn test_prune_orphans() {
+    let storage = storage();
+    storage.store_query_result("q1", &[item("A")]).unwrap();
+    storage.store_entity(&item("B")).unwrap();
+
+    // Recently used orphans survive
+    assert_eq!(storage.prune_orphans(Duration::days(1)).unwrap(), 0);
+
+    storage.remove_query("q1").unwrap();
+    {
+      let conn = storage.lock().unwrap();
+      conn
+        .execute(
+          "UPDATE entity_cache SET accessed_at = '2000-01-01 00:00:00'",
+          [],
+        )
+        .unwrap();
+    }
+    assert_eq!(storage.prune_orphans(Duration::days(1)).unwrap(), 2);
+  }
+
+  #[test]
+  fn test_size_limit_evicts_unreferenced_first() {
+    let storage = storage();
+    storage.store_query_result("q1", &[item("A")]).unwrap();
+    for key in ["B", "C", "D"] {
+      storage.store_entity(&item(key)).unwrap();
+    }
+
+    let size = storage.stats().unwrap().size_bytes;
+    let report = storage.enforce_size_limit(size - 1).unwrap();
+    assert_eq!(report.queries, 0);
+    assert!(report.entities >= 1);
+    assert!(storage.get_query_result::<Item>("q1").unwrap().is_some());
+
+    // A tiny limit evicts queries too
+    let report = storage.enforce_size_limit(1).unwrap();
+    assert_eq!(report.queries, 1);
+    assert!(storage.stats().unwrap().entities.is_empty());
+  }
+
+  #[test]
+  fn test_remove_superseded() {
+    let storage = storage();
+    for key in ["h:P-1:1", "h:P-1:2", "h:P-12:1"] {
+      storage.store_query_result(key, &[item(key)]).unwrap();
+    }
+    storage.remove_superseded("h:P-1:", "h:P-1:2").unwrap();
+
+    assert!(storage
+      .get_query_result::<Item>("h:P-1:1")
+      .unwrap()
+      .is_none());
+    assert!(storage
+      .get_query_result::<Item>("h:P-1:2")
+      .unwrap()
+      .is_some());
+    assert!(storage
+      .get_query_result::<Item>("h:P-12:1")
+      .unwrap()
+      .is_some());
+    // The old version's entities can now be pruned
+    assert_eq!(storage.stats().unwrap().unreferenced, 1);
+  }
+}
@@ -5,11 +5,22 @@
 //! - Handles full lists, partial lists, and individual item queries
 //! - Supports incremental fetching via `updated_at > last_fetched_updated_at`
 //! - Provides basic offline mode (serve stale cache when network unavailable)
+//! - Queues mutations made while offline for later replay
+//! - Keeps the database within a size limit by evicting unused data
+//! - Stores notifications about changes to watched entities
+//! - Keeps the command palette history
 
+mod history;
 mod layer;
+mod maintenance;
+mod notifications;
+mod queue;
 mod storage;
 mod traits;
 
-pub use layer::CacheLayer;
+pub use layer::{revalidate, CacheLayer};
+pub use maintenance::CacheStats;
+pub use notifications::Notification;
+pub use queue::{MutationState, PendingMutation};
 pub use storage::SqliteStorage;
-pub use traits::Cacheable;
+pub use traits::{CacheInfo, CacheResult, Cacheable};
@@ -0,0 +1,140 @@
+//! Notification center storage.
+//!
+//! Like the mutation queue this is kind-agnostic: callers decide what a
+//! notification's `kind` means and format the message themselves.
+
+use chrono::{DateTime, Utc};
+use color_eyre::{eyre::eyre, Result};
+use rusqlite::params;
+
+use super::storage::{parse_datetime, SqliteStorage};
+
+/// Oldest notifications beyond this many are dropped
+const MAX_NOTIFICATIONS: i64 = 500;
+
+/// A stored notification.
+#[derive(Debug, Clone)]
+pub struct Notification {
+  pub entity_key: String,
+  pub kind: String,
+  pub message: String,
+  pub read: bool,
+  pub created_at: DateTime<Utc>,
+}
+
+impl SqliteStorage {
+  /// Add a notification, dropping the oldest ones past the limit.
+  pub fn add_notification(&self, entity_key: &str, kind: &str, message: &str) -> Result<()> {
+    let conn = self.lock()?;
+    conn
+      .execute(
+        "INSERT INTO notifications (entity_key, kind, message) VALUES (?, ?, ?)",
+        params![entity_key, kind, message],
+      )
+      .map_err(|e| eyre!("Failed to add notification: {}", e))?;
+    conn
+      .execute(
+        "DELETE FROM notifications WHERE id <= (SELECT MAX(id) FROM notifications) - ?",
+        params![MAX_NOTIFICATIONS],
+      )
+      .map_err(|e| eyre!("Failed to trim notifications: {}", e))?;
+    Ok(())
+  }
+
+  /// Get all notifications, newest first.
+  pub fn notifications(&self) -> Result<Vec<Notification>> {
+    let conn = self.lock()?;
+
+    let mut stmt = conn
+      .prepare(
+        "SELECT entity_key, kind, message, read, created_at
+         FROM notifications ORDER BY id DESC",
+      )
+      .map_err(|e| eyre!("Failed to prepare query: {}", e))?;
+
+    let rows: Vec<_> = stmt
+      .query_map([], |row| {
+        Ok((
+          row.get::<_, String>(0)?,
+          row.get::<_, String>(1)?,
+          row.get::<_, String>(2)?,
+          row.get::<_, bool>(3)?,
+          row.get::<_, String>(4)?,
+        ))
+      })
+      .map_err(|e| eyre!("Failed to query notifications: {}", e))?
+      .filter_map(|r| r.ok())
+      .collect();
+
+    rows
+      .into_iter()
+      .map(|(entity_key, kind, message, read, created_at)| {
+        Ok(Notification {
+          entity_key,
+          kind,
+          message,
+          read,
+          created_at: parse_datetime(&created_at)?,
+        })
+      })
+      .collect()
+  }
+
+  /// Count notifications that haven't been seen yet.
+  pub fn unread_notification_count(&self) -> Result<usize> {
+    let conn = self.lock()?;
+    conn
+      .query_row(
+        "SELECT COUNT(*) FROM notifications WHERE read = 0",
+        [],
+        |row| row.get(0),
+      )
+      .map_err(|e| eyre!("Failed to count notifications: {}", e))
+  }
+
+  /// Mark every notification as read.
+  pub fn mark_notifications_read(&self) -> Result<()> {
+    let conn = self.lock()?;
+    conn
+      .execute("UPDATE notifications SET read = 1 WHERE read = 0", [])
+      .map_err(|e| eyre!("Failed to mark notifications read: {}", e))?;
+    Ok(())
+  }
+
+  /// Delete all notifications.
+  pub fn clear_notifications(&self) -> Result<()> {
+    let conn = self.lock()?;
+    conn
+      .execute("DELETE FROM notifications", [])
+      .map_err(|e| eyre!("Failed to clear notifications: {}", e))?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_notifications_roundtrip() {
+    let storage = SqliteStorage::new(crate::db::open_in_memory().unwrap());
+    storage
+      .add_notification("A-1", "assigned", "Assigned to you")
+      .unwrap();
+    storage
+      .add_notification("A-2", "status", "To Do → Done")
+      .unwrap();
+
+    let all = storage.notifications().unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].entity_key, "A-2");
+    assert_eq!(storage.unread_notification_count().unwrap(), 2);
+
+    storage.mark_notifications_read().unwrap();
+    assert_eq!(storage.unread_notification_count().unwrap(), 0);
+    assert!(storage.notifications().unwrap().iter().all(|n| n.read));
+
+    storage.clear_notifications().unwrap();
+    assert!(storage.notifications().unwrap().is_empty());
+  }
+}
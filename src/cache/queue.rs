@@ -0,0 +1,195 @@
+//! Durable queue of mutations recorded while offline.
+//!
+//! The queue is payload-agnostic: callers serialize their own mutation type
+//! and the queue only tracks ordering, the entity it applies to, and the
+//! entity's `updated_at` at the time it was recorded (for conflict detection).
+
+use chrono::{DateTime, Utc};
+use color_eyre::{eyre::eyre, Result};
+use rusqlite::params;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::storage::{parse_datetime, SqliteStorage};
+
+/// Replay state of a queued mutation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutationState {
+  /// Waiting to be replayed
+  Pending,
+  /// The entity changed on the server since the mutation was recorded
+  Conflict,
+  /// The server rejected the mutation
+  Failed,
+}
+
+impl MutationState {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      MutationState::Pending => "pending",
+      MutationState::Conflict => "conflict",
+      MutationState::Failed => "failed",
+    }
+  }
+
+  fn parse(s: &str) -> Self {
+    match s {
+      "conflict" => MutationState::Conflict,
+      "failed" => MutationState::Failed,
+      _ => MutationState::Pending,
+    }
+  }
+}
+
+/// A mutation waiting in the queue.
+#[derive(Debug, Clone)]
+pub struct PendingMutation<M> {
+  pub id: i64,
+  pub entity_key: String,
+  pub mutation: M,
+  /// The entity's updated_at when the mutation was recorded
+  pub base_updated: Option<String>,
+  pub state: MutationState,
+  pub error: Option<String>,
+  pub created_at: DateTime<Utc>,
+}
+
+impl SqliteStorage {
+  /// Append a mutation to the queue.
+  pub fn enqueue_mutation<M: Serialize>(
+    &self,
+    entity_key: &str,
+    kind: &str,
+    mutation: &M,
+    base_updated: Option<&str>,
+  ) -> Result<i64> {
+    let conn = self.lock()?;
+    let payload =
+      serde_json::to_string(mutation).map_err(|e| eyre!("Failed to serialize mutation: {}", e))?;
+
+    conn
+      .execute(
+        "INSERT INTO pending_mutations (entity_key, kind, payload, base_updated)
+         VALUES (?, ?, ?, ?)",
+        params![entity_key, kind, payload, base_updated],
+      )
+      .map_err(|e| eyre!("Failed to queue mutation: {}", e))?;
+
+    Ok(conn.last_insert_rowid())
+  }
+
+  /// Get all queued mutations in replay order.
+  pub fn pending_mutations<M: DeserializeOwned>(&self) -> Result<Vec<PendingMutation<M>>> {
+    let conn = self.lock()?;
+
+    let mut stmt = conn
+      .prepare(
+        "SELECT id, entity_key, payload, base_updated, state, error, created_at
+         FROM pending_mutations ORDER BY id",
+      )
+      .map_err(|e| eyre!("Failed to prepare query: {}", e))?;
+
+    let rows: Vec<_> = stmt
+      .query_map([], |row| {
+        Ok((
+          row.get::<_, i64>(0)?,
+          row.get::<_, String>(1)?,
+          row.get::<_, String>(2)?,
+          row.get::<_, Option<String>>(3)?,
+          row.get::<_, String>(4)?,
+          row.get::<_, Option<String>>(5)?,
+          row.get::<_, String>(6)?,
+        ))
+      })
+      .map_err(|e| eyre!("Failed to query mutations: {}", e))?
+      .filter_map(|r| r.ok())
+      .collect();
+
+    rows
+      .into_iter()
+      .map(
+        |(id, entity_key, payload, base_updated, state, error, created_at)| {
+          Ok(PendingMutation {
+            id,
+            entity_key,
+            mutation: serde_json::from_str(&payload)
+              .map_err(|e| eyre!("Failed to parse queued mutation {}: {}", id, e))?,
+            base_updated,
+            state: MutationState::parse(&state),
+            error,
+            created_at: parse_datetime(&created_at)?,
+          })
+        },
+      )
+      .collect()
+  }
+
+  /// Count mutations still waiting to be replayed.
+  pub fn pending_mutation_count(&self) -> Result<usize> {
+    let conn = self.lock()?;
+    conn
+      .query_row(
+        "SELECT COUNT(*) FROM pending_mutations WHERE state = 'pending'",
+        [],
+        |row| row.get(0),
+      )
+      .map_err(|e| eyre!("Failed to count mutations: {}", e))
+  }
+
+  /// Update the replay state of a queued mutation.
+  pub fn set_mutation_state(
+    &self,
+    id: i64,
+    state: MutationState,
+    error: Option<&str>,
+  ) -> Result<()> {
+    let conn = self.lock()?;
+    conn
+      .execute(
+        "UPDATE pending_mutations SET state = ?, error = ? WHERE id = ?",
+        params![state.as_str(), error, id],
+      )
+      .map_err(|e| eyre!("Failed to update mutation: {}", e))?;
+    Ok(())
+  }
+
+  /// Record the entity's new updated_at for its remaining pending mutations.
+  ///
+  /// Called after replaying one of several queued mutations for the same
+  /// entity, so our own change isn't mistaken for a conflict.
+  pub fn rebase_mutations(&self, entity_key: &str, updated_at: &str) -> Result<()> {
+    let conn = self.lock()?;
+    conn
+      .execute(
+        "UPDATE pending_mutations SET base_updated = ?
+         WHERE entity_key = ? AND state = 'pending' AND base_updated IS NOT NULL",
+        params![updated_at, entity_key],
+      )
+      .map_err(|e| eyre!("Failed to rebase mutations: {}", e))?;
+    Ok(())
+  }
+
+  /// Reset a mutation for another replay attempt.
+  ///
+  /// Clearing `base_updated` skips the conflict check, so a retried conflict
+  /// overwrites whatever changed on the server.
+  pub fn retry_mutation(&self, id: i64) -> Result<()> {
+    let conn = self.lock()?;
+    conn
+      .execute(
+        "UPDATE pending_mutations SET state = 'pending', error = NULL, base_updated = NULL
+         WHERE id = ?",
+        params![id],
+      )
+      .map_err(|e| eyre!("Failed to retry mutation: {}", e))?;
+    Ok(())
+  }
+
+  /// Remove a mutation from the queue (after replay or when discarded).
+  pub fn remove_mutation(&self, id: i64) -> Result<()> {
+    let conn = self.lock()?;
+    conn
+      .execute("DELETE FROM pending_mutations WHERE id = ?", params![id])
+      .map_err(|e| eyre!("Failed to remove mutation: {}", e))?;
+    Ok(())
+  }
+}
@@ -3,7 +3,8 @@
 use chrono::{DateTime, Utc};
 use color_eyre::{eyre::eyre, Result};
 use rusqlite::{params, Connection};
-use std::sync::{Arc, Mutex};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 use super::traits::Cacheable;
 
@@ -46,6 +47,23 @@ pub trait CacheStorage: Send + Sync {
 
   /// Merge new entities into an existing query result (upsert by key).
   fn merge_query_result<T: Cacheable>(&self, key: &str, new_entities: &[T]) -> Result<()>;
+
+  /// Get when a query was last reconciled against the server.
+  fn get_reconciled_at(&self, key: &str) -> Result<Option<DateTime<Utc>>>;
+
+  /// Record that a query's cached result matches the server.
+  fn mark_reconciled(&self, key: &str) -> Result<()>;
+
+  /// Replace a query's membership with the server's keys (in server order).
+  ///
+  /// `fetched` holds entities for keys that weren't cached yet. Returns the
+  /// keys that were pruned from the query.
+  fn reconcile_query_result<T: Cacheable>(
+    &self,
+    key: &str,
+    keys: &[String],
+    fetched: &[T],
+  ) -> Result<Vec<String>>;
 }
 
 /// Storage implementation that doesn't cache anything.
@@ -76,6 +94,23 @@ impl CacheStorage for NoopStorage {
   fn merge_query_result<T: Cacheable>(&self, _key: &str, _new_entities: &[T]) -> Result<()> {
     Ok(()) // Discard
   }
+
+  fn get_reconciled_at(&self, _key: &str) -> Result<Option<DateTime<Utc>>> {
+    Ok(None) // Never reconciled
+  }
+
+  fn mark_reconciled(&self, _key: &str) -> Result<()> {
+    Ok(()) // Discard
+  }
+
+  fn reconcile_query_result<T: Cacheable>(
+    &self,
+    _key: &str,
+    _keys: &[String],
+    _fetched: &[T],
+  ) -> Result<Vec<String>> {
+    Ok(Vec::new()) // Nothing cached, nothing pruned
+  }
 }
 
 /// SQLite-based cache storage implementation.
@@ -86,64 +121,122 @@ pub struct SqliteStorage {
 
 impl SqliteStorage {
   /// Create a new SQLite storage using the provided connection.
-  /// Runs migrations on initialization.
-  pub fn new(conn: Arc<Mutex<Connection>>) -> Result<Self> {
-    let storage = Self { conn };
-    storage.run_migrations()?;
-    Ok(storage)
+  /// The schema is expected to be migrated already (see `db::open_connection`).
+  pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+    Self { conn }
   }
 
-  /// Run database migrations for cache tables.
-  fn run_migrations(&self) -> Result<()> {
-    let conn = self
-      .conn
-      .lock()
-      .map_err(|e| eyre!("Lock poisoned: {}", e))?;
+  /// Lock the underlying connection (for storage extensions in sibling modules).
+  pub(super) fn lock(&self) -> Result<MutexGuard<'_, Connection>> {
+    self.conn.lock().map_err(|e| eyre!("Lock poisoned: {}", e))
+  }
+
+  /// Get every cached entity of a type, in no particular order.
+  ///
+  /// For lookups across queries, like command completion. Doesn't count
+  /// as a use for LRU eviction.
+  pub fn all_entities<T: Cacheable>(&self) -> Result<Vec<T>> {
+    let conn = self.lock()?;
+
+    let mut stmt = conn
+      .prepare("SELECT data FROM entity_cache WHERE entity_type = ?")
+      .map_err(|e| eyre!("Failed to prepare query: {}", e))?;
+
+    let rows: Vec<Vec<u8>> = stmt
+      .query_map(params![T::entity_type()], |row| row.get(0))
+      .map_err(|e| eyre!("Failed to query entities: {}", e))?
+      .filter_map(|r| r.ok())
+      .collect();
+
+    // Skip entities cached by an older, incompatible version
+    Ok(
+      rows
+        .iter()
+        .filter_map(|data| serde_json::from_slice(data).ok())
+        .collect(),
+    )
+  }
+
+  /// Mark an entity and every query containing it as stale.
+  ///
+  /// The next fetch goes to the network and replaces the cached copies,
+  /// without dropping the entity from cached lists in the meantime.
+  pub fn invalidate_entity(&self, entity_key: &str) -> Result<()> {
+    let conn = self.lock()?;
 
     conn
-      .execute_batch(CACHE_SCHEMA)
-      .map_err(|e| eyre!("Failed to run cache migrations: {}", e))?;
+      .execute(
+        "UPDATE entity_cache SET cached_at = '1970-01-01 00:00:00' WHERE entity_key = ?",
+        params![entity_key],
+      )
+      .map_err(|e| eyre!("Failed to invalidate entity: {}", e))?;
+
+    conn
+      .execute(
+        "UPDATE query_cache SET cached_at = '1970-01-01 00:00:00', max_updated = NULL
+         WHERE query_hash IN (SELECT query_hash FROM query_results WHERE entity_key = ?)",
+        params![entity_key],
+      )
+      .map_err(|e| eyre!("Failed to invalidate queries: {}", e))?;
 
     Ok(())
   }
-}
 
-/// Schema for cache tables.
-const CACHE_SCHEMA: &str = r#"
--- Generic entity cache (stores serialized JSON)
-CREATE TABLE IF NOT EXISTS entity_cache (
-    entity_type TEXT NOT NULL,
-    entity_key TEXT NOT NULL,
-    data BLOB NOT NULL,
-    updated_at TEXT,
-    cached_at TEXT NOT NULL DEFAULT (datetime('now')),
-    PRIMARY KEY (entity_type, entity_key)
-);
-
-CREATE INDEX IF NOT EXISTS idx_entity_cache_updated
-    ON entity_cache(entity_type, updated_at);
-
--- Query result tracking
-CREATE TABLE IF NOT EXISTS query_cache (
-    query_hash TEXT PRIMARY KEY,
-    query_description TEXT NOT NULL,
-    entity_type TEXT NOT NULL,
-    max_updated TEXT,
-    cached_at TEXT NOT NULL DEFAULT (datetime('now')),
-    result_count INTEGER NOT NULL
-);
-
--- Query to entity mapping (preserves order)
-CREATE TABLE IF NOT EXISTS query_results (
-    query_hash TEXT NOT NULL,
-    entity_key TEXT NOT NULL,
-    position INTEGER NOT NULL,
-    PRIMARY KEY (query_hash, entity_key),
-    FOREIGN KEY (query_hash) REFERENCES query_cache(query_hash) ON DELETE CASCADE
-);
-
-CREATE INDEX IF NOT EXISTS idx_query_results_hash ON query_results(query_hash);
-"#;
+  /// Move an entity right before or after another one in every cached
+  /// query containing both, e.g. after ranking an issue.
+  pub fn move_entity(&self, entity_key: &str, other_key: &str, before: bool) -> Result<()> {
+    let mut conn = self.lock()?;
+    let tx = conn
+      .transaction()
+      .map_err(|e| eyre!("Failed to begin transaction: {}", e))?;
+
+    let queries: Vec<String> = tx
+      .prepare(
+        "SELECT a.query_hash FROM query_results a
+         INNER JOIN query_results b ON a.query_hash = b.query_hash
+         WHERE a.entity_key = ? AND b.entity_key = ?",
+      )
+      .and_then(|mut stmt| {
+        stmt
+          .query_map(params![entity_key, other_key], |row| row.get(0))?
+          .collect::<rusqlite::Result<Vec<_>>>()
+      })
+      .map_err(|e| eyre!("Failed to read query results: {}", e))?;
+
+    for query in queries {
+      let mut keys: Vec<String> = tx
+        .prepare("SELECT entity_key FROM query_results WHERE query_hash = ? ORDER BY position")
+        .and_then(|mut stmt| {
+          stmt
+            .query_map(params![query], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .map_err(|e| eyre!("Failed to read query results: {}", e))?;
+
+      keys.retain(|key| key != entity_key);
+      let Some(index) = keys.iter().position(|key| key == other_key) else {
+        continue;
+      };
+      keys.insert(
+        if before { index } else { index + 1 },
+        entity_key.to_string(),
+      );
+
+      for (position, key) in keys.iter().enumerate() {
+        tx.execute(
+          "UPDATE query_results SET position = ? WHERE query_hash = ? AND entity_key = ?",
+          params![position, query, key],
+        )
+        .map_err(|e| eyre!("Failed to store query result: {}", e))?;
+      }
+    }
+
+    tx.commit()
+      .map_err(|e| eyre!("Failed to commit transaction: {}", e))?;
+
+    Ok(())
+  }
+}
 
 impl CacheStorage for SqliteStorage {
   fn store_query_result<T: Cacheable>(&self, key: &str, entities: &[T]) -> Result<()> {
@@ -176,8 +269,14 @@ impl CacheStorage for SqliteStorage {
     // Insert/update query cache
     conn
       .execute(
-        "INSERT OR REPLACE INTO query_cache (query_hash, query_description, entity_type, max_updated, cached_at, result_count)
-         VALUES (?, ?, ?, ?, datetime('now'), ?)",
+        "INSERT INTO query_cache (query_hash, query_description, entity_type, max_updated, cached_at, result_count)
+         VALUES (?, ?, ?, ?, datetime('now'), ?)
+         ON CONFLICT(query_hash) DO UPDATE SET
+           query_description = excluded.query_description,
+           entity_type = excluded.entity_type,
+           max_updated = excluded.max_updated,
+           cached_at = excluded.cached_at,
+           result_count = excluded.result_count",
         params![key, key, entity_type, max_updated, entities.len()],
       )
       .map_err(|e| eyre!("Failed to update query cache: {}", e))?;
@@ -246,6 +345,14 @@ impl CacheStorage for SqliteStorage {
 
     let cached_at = parse_datetime(&cached_at_str)?;
 
+    // Track last use for LRU eviction
+    conn
+      .execute(
+        "UPDATE query_cache SET accessed_at = datetime('now') WHERE query_hash = ?",
+        params![query_hash],
+      )
+      .map_err(|e| eyre!("Failed to update query access time: {}", e))?;
+
     // Get entities in order
     let mut stmt = conn
       .prepare(
@@ -298,6 +405,16 @@ impl CacheStorage for SqliteStorage {
         let entity: T = serde_json::from_slice(&data)
           .map_err(|e| eyre!("Failed to deserialize entity: {}", e))?;
         let cached_at = parse_datetime(&cached_at_str)?;
+
+        // Track last use for LRU eviction
+        conn
+          .execute(
+            "UPDATE entity_cache SET accessed_at = datetime('now')
+             WHERE entity_type = ? AND entity_key = ?",
+            params![entity_type, entity_key],
+          )
+          .map_err(|e| eyre!("Failed to update entity access time: {}", e))?;
+
         Ok(Some(CachedEntity { entity, cached_at }))
       }
       None => Ok(None),
@@ -400,12 +517,193 @@ impl CacheStorage for SqliteStorage {
     // Store the merged result
     self.store_query_result(key, &existing_entities)
   }
+
+  fn get_reconciled_at(&self, key: &str) -> Result<Option<DateTime<Utc>>> {
+    let conn = self.lock()?;
+
+    let result: Option<Option<String>> = conn
+      .query_row(
+        "SELECT reconciled_at FROM query_cache WHERE query_hash = ?",
+        params![key],
+        |row| row.get(0),
+      )
+      .ok();
+
+    result.flatten().map(|s| parse_datetime(&s)).transpose()
+  }
+
+  fn mark_reconciled(&self, key: &str) -> Result<()> {
+    let conn = self.lock()?;
+    conn
+      .execute(
+        "UPDATE query_cache SET reconciled_at = datetime('now') WHERE query_hash = ?",
+        params![key],
+      )
+      .map_err(|e| eyre!("Failed to mark query reconciled: {}", e))?;
+    Ok(())
+  }
+
+  fn reconcile_query_result<T: Cacheable>(
+    &self,
+    key: &str,
+    keys: &[String],
+    fetched: &[T],
+  ) -> Result<Vec<String>> {
+    let mut conn = self.lock()?;
+    let entity_type = T::entity_type();
+
+    let tx = conn
+      .transaction()
+      .map_err(|e| eyre!("Failed to begin transaction: {}", e))?;
+
+    for entity in fetched {
+      let data =
+        serde_json::to_vec(entity).map_err(|e| eyre!("Failed to serialize entity: {}", e))?;
+      tx.execute(
+        "INSERT OR REPLACE INTO entity_cache (entity_type, entity_key, data, updated_at, cached_at)
+         VALUES (?, ?, ?, ?, datetime('now'))",
+        params![entity_type, entity.cache_key(), data, entity.updated_at()],
+      )
+      .map_err(|e| eyre!("Failed to store entity: {}", e))?;
+    }
+
+    let existing: Vec<String> = tx
+      .prepare("SELECT entity_key FROM query_results WHERE query_hash = ?")
+      .and_then(|mut stmt| {
+        stmt
+          .query_map(params![key], |row| row.get(0))?
+          .collect::<rusqlite::Result<Vec<_>>>()
+      })
+      .map_err(|e| eyre!("Failed to read query results: {}", e))?;
+
+    let server_keys: HashSet<&str> = keys.iter().map(String::as_str).collect();
+    let pruned: Vec<String> = existing
+      .into_iter()
+      .filter(|k| !server_keys.contains(k.as_str()))
+      .collect();
+
+    tx.execute(
+      "DELETE FROM query_results WHERE query_hash = ?",
+      params![key],
+    )
+    .map_err(|e| eyre!("Failed to delete old query results: {}", e))?;
+
+    // Keys whose entity couldn't be fetched are left out until the next pass
+    let mut position = 0;
+    for entity_key in keys {
+      let inserted = tx
+        .execute(
+          "INSERT OR IGNORE INTO query_results (query_hash, entity_key, position)
+           SELECT ?, entity_key, ? FROM entity_cache WHERE entity_type = ? AND entity_key = ?",
+          params![key, position, entity_type, entity_key],
+        )
+        .map_err(|e| eyre!("Failed to store query result: {}", e))?;
+      position += inserted;
+    }
+
+    tx.execute(
+      "UPDATE query_cache SET
+         result_count = ?,
+         reconciled_at = datetime('now'),
+         max_updated = (
+           SELECT MAX(ec.updated_at) FROM entity_cache ec
+           INNER JOIN query_results qr ON ec.entity_type = ? AND ec.entity_key = qr.entity_key
+           WHERE qr.query_hash = ?
+         )
+       WHERE query_hash = ?",
+      params![position, entity_type, key, key],
+    )
+    .map_err(|e| eyre!("Failed to update query cache: {}", e))?;
+
+    tx.commit()
+      .map_err(|e| eyre!("Failed to commit transaction: {}", e))?;
+
+    Ok(pruned)
+  }
 }
 
 /// Parse a datetime string from SQLite format.
-fn parse_datetime(s: &str) -> Result<DateTime<Utc>> {
+pub(super) fn parse_datetime(s: &str) -> Result<DateTime<Utc>> {
   // SQLite stores as "YYYY-MM-DD HH:MM:SS"
   chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
     .map(|dt| dt.and_utc())
     .map_err(|e| eyre!("Failed to parse datetime '{}': {}", s, e))
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde::{Deserialize, Serialize};
+
+  #[derive(Debug, Clone, Serialize, Deserialize)]
+  struct Item {
+    key: String,
+  }
+
+  impl Cacheable for Item {
+    fn cache_key(&self) -> String {
+      self.key.clone()
+    }
+
+    fn updated_at(&self) -> Option<&str> {
+      None
+    }
+
+    fn entity_type() -> &'static str {
+      "item"
+    }
+  }
+
+  fn item(key: &str) -> Item {
+    Item {
+      key: key.to_string(),
+    }
+  }
+
+  fn storage() -> SqliteStorage {
+    SqliteStorage::new(crate::db::open_in_memory().unwrap())
+  }
+
+  #[test]
+  fn test_reconcile_prunes_missing_keys() {
+    let storage = storage();
+    storage
+      .store_query_result("q1", &[item("A"), item("B"), item("C")])
+      .unwrap();
+    assert!(storage.get_reconciled_at("q1").unwrap().is_none());
+
+    // B was deleted, D was moved into the query, order follows the server
+    let keys = vec!["D".to_string(), "C".to_string(), "A".to_string()];
+    let pruned = storage
+      .reconcile_query_result("q1", &keys, &[item("D")])
+      .unwrap();
+    assert_eq!(pruned, vec!["B".to_string()]);
+
+    let cached = storage.get_query_result::<Item>("q1").unwrap().unwrap();
+    let cached: Vec<_> = cached.entities.iter().map(|i| i.key.as_str()).collect();
+    assert_eq!(cached, ["D", "C", "A"]);
+    assert!(storage.get_reconciled_at("q1").unwrap().is_some());
+  }
+
+  #[test]
+  fn test_move_entity() {
+    let storage = storage();
+    storage
+      .store_query_result("q1", &[item("A"), item("B"), item("C"), item("D")])
+      .unwrap();
+    storage
+      .store_query_result("q2", &[item("C"), item("E")])
+      .unwrap();
+    let keys = |query: &str| -> Vec<String> {
+      let cached = storage.get_query_result::<Item>(query).unwrap().unwrap();
+      cached.entities.into_iter().map(|i| i.key).collect()
+    };
+
+    storage.move_entity("C", "A", true).unwrap();
+    assert_eq!(keys("q1"), ["C", "A", "B", "D"]);
+    storage.move_entity("C", "D", false).unwrap();
+    assert_eq!(keys("q1"), ["A", "B", "D", "C"]);
+    // Queries without the other entity keep their order
+    assert_eq!(keys("q2"), ["C", "E"]);
+  }
+}
@@ -61,6 +61,31 @@ impl<T> CacheResult<T> {
       cached_at: Some(cached_at),
     }
   }
+
+  /// Get the source metadata of this result, without the data.
+  pub fn info(&self) -> CacheInfo {
+    CacheInfo {
+      source: self.source,
+      cached_at: self.cached_at,
+    }
+  }
+}
+
+/// Where a cache result came from and when it was cached.
+///
+/// Carried alongside query data so views can tell the user when they are
+/// looking at stale or offline data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheInfo {
+  pub source: CacheSource,
+  pub cached_at: Option<DateTime<Utc>>,
+}
+
+impl CacheInfo {
+  /// Check if the data was served without reaching the network.
+  pub fn is_offline(&self) -> bool {
+    self.source == CacheSource::Offline
+  }
 }
 
 /// Indicates where cached data came from.
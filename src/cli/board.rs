@@ -0,0 +1,29 @@
+use super::output::{print, print_table, warn_if_offline};
+use super::BoardAction;
+use crate::config::Config;
+use crate::jira::types::Board;
+use crate::jira::JiraClient;
+use color_eyre::Result;
+
+pub async fn run(action: BoardAction, jira: &JiraClient, config: &Config) -> Result<()> {
+  match action {
+    BoardAction::List { all, output } => {
+      let project = if all {
+        None
+      } else {
+        config.default_project.as_deref()
+      };
+      let result = jira.get_boards(project).await?;
+      warn_if_offline(result.info());
+      print(output, result.data.as_slice(), print_boards)
+    }
+  }
+}
+
+fn print_boards(boards: &[Board]) {
+  let rows: Vec<Vec<String>> = boards
+    .iter()
+    .map(|b| vec![b.id.to_string(), b.board_type.clone(), b.name.clone()])
+    .collect();
+  print_table(&["ID", "TYPE", "NAME"], &rows);
+}
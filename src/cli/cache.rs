@@ -0,0 +1,60 @@
+use super::CacheAction;
+use crate::cache::SqliteStorage;
+use crate::db;
+use crate::ui::renderfns::{format_age, format_size};
+use chrono::Utc;
+use color_eyre::Result;
+
+pub fn run(action: CacheAction) -> Result<()> {
+  let storage = SqliteStorage::new(db::open_connection()?);
+
+  match action {
+    CacheAction::Stats => print_stats(&storage),
+    CacheAction::Clear => {
+      storage.clear()?;
+      println!("Cache cleared");
+      Ok(())
+    }
+    CacheAction::Vacuum => {
+      let (before, after) = storage.vacuum()?;
+      println!("{} -> {}", format_size(before), format_size(after));
+      Ok(())
+    }
+  }
+}
+
+fn print_stats(storage: &SqliteStorage) -> Result<()> {
+  let stats = storage.stats()?;
+  let now = Utc::now();
+
+  println!("Path:             {}", db::default_path()?.display());
+  println!("Size:             {}", format_size(stats.size_bytes));
+  println!("Reclaimable:      {}", format_size(stats.free_bytes));
+  println!("Unreferenced:     {}", stats.unreferenced);
+  println!("Pending changes:  {}", stats.pending_mutations);
+
+  println!();
+  println!("{:<16} {:>8} {:>10}", "ENTITY TYPE", "COUNT", "SIZE");
+  for entity in &stats.entities {
+    println!(
+      "{:<16} {:>8} {:>10}",
+      entity.entity_type,
+      entity.count,
+      format_size(entity.bytes)
+    );
+  }
+
+  println!();
+  println!("{:<14} {:>7} {:<10} QUERY", "TYPE", "RESULTS", "CACHED");
+  for query in &stats.queries {
+    println!(
+      "{:<14} {:>7} {:<10} {}",
+      query.entity_type,
+      query.result_count,
+      format_age(now - query.cached_at),
+      query.query_description
+    );
+  }
+
+  Ok(())
+}
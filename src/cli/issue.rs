@@ -0,0 +1,117 @@
+use super::output::{print, print_table, warn_if_offline};
+use super::IssueAction;
+use crate::config::Config;
+use crate::jira::mutations::MutationOutcome;
+use crate::jira::types::{Issue, IssueSummary};
+use crate::jira::JiraClient;
+use color_eyre::{eyre::eyre, Result};
+use serde::Serialize;
+
+/// Result of a change, for JSON/YAML output
+#[derive(Serialize)]
+struct ChangeResult {
+  key: String,
+  change: String,
+  /// "applied", or "queued" when Jira was unreachable
+  outcome: &'static str,
+}
+
+pub async fn run(action: IssueAction, jira: &JiraClient, config: &Config) -> Result<()> {
+  match action {
+    IssueAction::Get { key, output } => {
+      let result = jira.get_issue(&key).await?;
+      warn_if_offline(result.info());
+      print(output, &result.data, print_issue)
+    }
+    IssueAction::List { jql, output } => {
+      let jql = match (jql, &config.default_project) {
+        (Some(jql), _) => jql,
+        (None, Some(project)) => format!(
+          "project = {} AND resolution = unresolved ORDER BY updated DESC",
+          project
+        ),
+        (None, None) => return Err(eyre!("Pass --jql or set a default project")),
+      };
+      let result = jira.search_issues(&jql).await?;
+      warn_if_offline(result.info());
+      print(output, result.data.as_slice(), print_issues)
+    }
+    IssueAction::Transition {
+      key,
+      status,
+      output,
+    } => {
+      let outcome = jira.transition_to(&key, &status).await?;
+      print_change(output, key, format!("Moved to {}", status), outcome)
+    }
+    IssueAction::Assign { key, user, output } => {
+      let outcome = jira.assign(&key, &user).await?;
+      let change = match user.as_str() {
+        "none" => "Unassigned".to_string(),
+        user => format!("Assigned to {}", user),
+      };
+      print_change(output, key, change, outcome)
+    }
+  }
+}
+
+fn print_change(
+  output: super::OutputFormat,
+  key: String,
+  change: String,
+  outcome: MutationOutcome,
+) -> Result<()> {
+  let result = ChangeResult {
+    key,
+    change,
+    outcome: match outcome {
+      MutationOutcome::Applied => "applied",
+      MutationOutcome::Queued => "queued",
+    },
+  };
+  print(output, &result, |r| match outcome {
+    MutationOutcome::Applied => println!("{}: {}", r.key, r.change),
+    MutationOutcome::Queued => println!(
+      "{}: {} (queued, Jira not reachable; sent on the next run)",
+      r.key, r.change
+    ),
+  })
+}
+
+fn print_issues(issues: &[IssueSummary]) {
+  let rows: Vec<Vec<String>> = issues
+    .iter()
+    .map(|i| {
+      vec![
+        i.key.clone(),
+        i.status.clone(),
+        i.assignee.clone().unwrap_or_default(),
+        i.summary.clone(),
+      ]
+    })
+    .collect();
+  print_table(&["KEY", "STATUS", "ASSIGNEE", "SUMMARY"], &rows);
+}
+
+fn print_issue(issue: &Issue) {
+  let unset = || "-".to_string();
+  let fields = [
+    ("Key", issue.key.clone()),
+    ("Summary", issue.summary.clone()),
+    ("Status", issue.status.clone()),
+    ("Type", issue.issue_type.clone()),
+    ("Assignee", issue.assignee.clone().unwrap_or_else(unset)),
+    ("Reporter", issue.reporter.clone().unwrap_or_else(unset)),
+    ("Priority", issue.priority.clone().unwrap_or_else(unset)),
+    ("Labels", issue.labels.join(", ")),
+    ("Created", issue.created.clone()),
+    ("Updated", issue.updated.clone()),
+  ];
+  for (name, value) in fields {
+    println!("{:<10} {}", format!("{}:", name), value);
+  }
+  if let Some(description) = &issue.description {
+    println!();
+    println!("{}", description);
+  }
+}
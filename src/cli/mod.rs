@@ -0,0 +1,103 @@
+//! Non-interactive subcommands (`j9s <command>`).
+
+mod board;
+mod cache;
+mod issue;
+mod output;
+
+use crate::config::Config;
+use clap::Subcommand;
+use color_eyre::Result;
+pub use output::OutputFormat;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+  /// Inspect and maintain the local cache
+  Cache {
+    #[command(subcommand)]
+    action: CacheAction,
+  },
+  /// Get, list and change issues
+  Issue {
+    #[command(subcommand)]
+    action: IssueAction,
+  },
+  /// List agile boards
+  Board {
+    #[command(subcommand)]
+    action: BoardAction,
+  },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheAction {
+  /// Show cache size and cached queries
+  Stats,
+  /// Remove all cached data (queued offline changes are kept)
+  Clear,
+  /// Rebuild the database file to reclaim free space
+  Vacuum,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum IssueAction {
+  /// Show a single issue
+  Get {
+    key: String,
+    #[arg(short, long, value_enum, default_value_t)]
+    output: OutputFormat,
+  },
+  /// List issues matching a JQL query (default: open issues in the project)
+  List {
+    #[arg(long)]
+    jql: Option<String>,
+    #[arg(short, long, value_enum, default_value_t)]
+    output: OutputFormat,
+  },
+  /// Move an issue to a status, e.g. "In Review"
+  Transition {
+    key: String,
+    status: String,
+    #[arg(short, long, value_enum, default_value_t)]
+    output: OutputFormat,
+  },
+  /// Assign an issue to a user ("me" for yourself, "none" to unassign)
+  Assign {
+    key: String,
+    user: String,
+    #[arg(short, long, value_enum, default_value_t)]
+    output: OutputFormat,
+  },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BoardAction {
+  /// List boards of the default project
+  List {
+    /// List boards of all projects
+    #[arg(long)]
+    all: bool,
+    #[arg(short, long, value_enum, default_value_t)]
+    output: OutputFormat,
+  },
+}
+
+/// Run a subcommand to completion.
+///
+/// The config is only loaded for commands that talk to Jira, so cache
+/// maintenance works even with a broken config.
+pub async fn run(command: Command, load_config: impl FnOnce() -> Result<Config>) -> Result<()> {
+  match command {
+    Command::Cache { action } => cache::run(action),
+    Command::Issue { action } => {
+      let config = load_config()?;
+      let jira = crate::app::connect(&config)?;
+      issue::run(action, &jira, &config).await
+    }
+    Command::Board { action } => {
+      let config = load_config()?;
+      let jira = crate::app::connect(&config)?;
+      board::run(action, &jira, &config).await
+    }
+  }
+}
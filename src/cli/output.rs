@@ -0,0 +1,83 @@
+use crate::cache::CacheInfo;
+use crate::ui::renderfns::format_age;
+use chrono::Utc;
+use clap::ValueEnum;
+use color_eyre::{eyre::eyre, Result};
+use serde::Serialize;
+
+/// How subcommands print their results
+#[derive(ValueEnum, Debug, Clone, Copy, Default)]
+pub enum OutputFormat {
+  /// Human-readable table
+  #[default]
+  Table,
+  Json,
+  Yaml,
+}
+
+/// Print a value as JSON or YAML, or call `table` to print it for humans
+pub fn print<T: Serialize + ?Sized>(
+  format: OutputFormat,
+  value: &T,
+  table: impl FnOnce(&T),
+) -> Result<()> {
+  match format {
+    OutputFormat::Table => table(value),
+    OutputFormat::Json => {
+      let json =
+        serde_json::to_string_pretty(value).map_err(|e| eyre!("Failed to encode JSON: {}", e))?;
+      println!("{}", json);
+    }
+    OutputFormat::Yaml => {
+      let yaml = serde_yaml::to_string(value).map_err(|e| eyre!("Failed to encode YAML: {}", e))?;
+      print!("{}", yaml);
+    }
+  }
+  Ok(())
+}
+
+/// Print rows in aligned columns; the last column isn't padded
+pub fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+  let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+  for row in rows {
+    for (width, cell) in widths.iter_mut().zip(row) {
+      *width = (*width).max(cell.chars().count());
+    }
+  }
+
+  let format_row = |cells: Vec<&str>| {
+    let last = cells.len().saturating_sub(1);
+    cells
+      .iter()
+      .enumerate()
+      .map(|(i, cell)| {
+        if i == last {
+          cell.to_string()
+        } else {
+          format!("{:<width$}", cell, width = widths[i])
+        }
+      })
+      .collect::<Vec<_>>()
+      .join("  ")
+  };
+
+  println!("{}", format_row(headers.to_vec()));
+  for row in rows {
+    println!("{}", format_row(row.iter().map(String::as_str).collect()));
+  }
+}
+
+/// Tell the user on stderr when results came from the cache because Jira
+/// couldn't be reached
+pub fn warn_if_offline(source: CacheInfo) {
+  if !source.is_offline() {
+    return;
+  }
+  match source.cached_at {
+    Some(cached_at) => eprintln!(
+      "warning: showing data cached {} (Jira not reachable)",
+      format_age(Utc::now() - cached_at)
+    ),
+    None => eprintln!("warning: showing cached data (Jira not reachable)"),
+  }
+}
@@ -0,0 +1,67 @@
+//! Copying text to the system clipboard.
+
+use base64::Engine;
+use color_eyre::{eyre::eyre, Result};
+use std::io::{stdout, Write};
+use std::process::{Command, Stdio};
+
+/// Copy text to the clipboard.
+///
+/// Always sends an OSC 52 escape sequence, which the terminal turns into a
+/// copy; this works over SSH and in tmux (with `set-clipboard on`). Many
+/// terminals ignore OSC 52 by default, so on a local session the text is
+/// also piped to `wl-copy`, `xclip` or `pbcopy` when one is available.
+pub fn copy(text: &str) -> Result<()> {
+  let osc52 = copy_osc52(text);
+  if is_remote_session() {
+    return osc52;
+  }
+  // A local tool is the more reliable of the two
+  copy_with_tool(text).or(osc52)
+}
+
+fn copy_osc52(text: &str) -> Result<()> {
+  let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+  let mut out = stdout();
+  out
+    .write_all(format!("\x1b]52;c;{}\x07", encoded).as_bytes())
+    .and_then(|()| out.flush())
+    .map_err(|e| eyre!("Failed to write to terminal: {}", e))
+}
+
+/// Over SSH, local tools would copy to the remote machine's clipboard
+fn is_remote_session() -> bool {
+  std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
+fn copy_with_tool(text: &str) -> Result<()> {
+  let (program, args): (&str, &[&str]) = if cfg!(target_os = "macos") {
+    ("pbcopy", &[])
+  } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+    ("wl-copy", &[])
+  } else if std::env::var_os("DISPLAY").is_some() {
+    ("xclip", &["-selection", "clipboard"])
+  } else {
+    return Err(eyre!("No clipboard tool for this session"));
+  };
+
+  let mut child = Command::new(program)
+    .args(args)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .spawn()
+    .map_err(|e| eyre!("Failed to run {}: {}", program, e))?;
+  if let Some(mut stdin) = child.stdin.take() {
+    stdin
+      .write_all(text.as_bytes())
+      .map_err(|e| eyre!("Failed to write to {}: {}", program, e))?;
+  }
+  let status = child
+    .wait()
+    .map_err(|e| eyre!("Failed to run {}: {}", program, e))?;
+  if !status.success() {
+    return Err(eyre!("{} exited with {}", program, status));
+  }
+  Ok(())
+}
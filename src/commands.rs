@@ -1,89 +1,328 @@
-/// Available commands and autocomplete logic
+//! Available commands, argument parsing and autocomplete logic
+
+use chrono::NaiveDate;
+use color_eyre::{eyre::eyre, Result};
+use std::collections::BTreeMap;
+
+/// What a command argument refers to, for completion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArgKind {
+  Project,
+  Board,
+  Epic,
+  /// Free text, no completion
+  Text,
+}
+
+/// A command's argument
+#[derive(Debug, Clone)]
+pub struct CommandArg {
+  /// Placeholder shown in the palette, e.g. "<name|id>"
+  pub usage: &'static str,
+  pub kind: ArgKind,
+  pub required: bool,
+}
+
+impl CommandArg {
+  const fn required(usage: &'static str, kind: ArgKind) -> Option<Self> {
+    Some(Self {
+      usage,
+      kind,
+      required: true,
+    })
+  }
+
+  const fn optional(usage: &'static str, kind: ArgKind) -> Option<Self> {
+    Some(Self {
+      usage,
+      kind,
+      required: false,
+    })
+  }
+}
 
 #[derive(Debug, Clone)]
-pub struct Command {
-  pub name: &'static str,
-  pub aliases: &'static [&'static str],
-  pub description: &'static str,
+pub struct Command<'a> {
+  pub name: &'a str,
+  pub aliases: &'a [&'a str],
+  pub description: &'a str,
+  pub arg: Option<CommandArg>,
 }
 
 /// All available commands
-pub const COMMANDS: &[Command] = &[
+pub const COMMANDS: &[Command<'static>] = &[
   Command {
     name: "issues",
     aliases: &["i", "issue"],
     description: "Browse project issues",
+    arg: CommandArg::optional("[project]", ArgKind::Project),
   },
   Command {
     name: "boards",
-    aliases: &["b", "board"],
+    aliases: &["b"],
     description: "View agile boards",
+    arg: CommandArg::optional("[project]", ArgKind::Project),
+  },
+  Command {
+    name: "board",
+    aliases: &[],
+    description: "Open a board",
+    arg: CommandArg::required("<name|id>", ArgKind::Board),
+  },
+  Command {
+    name: "metrics",
+    aliases: &[],
+    description: "Cycle time, throughput and flow of a board",
+    arg: CommandArg::required("<name|id>", ArgKind::Board),
+  },
+  Command {
+    name: "burndown",
+    aliases: &["burnup", "sprint"],
+    description: "Burndown of a board's active sprint",
+    arg: CommandArg::required("<name|id>", ArgKind::Board),
   },
   Command {
     name: "epics",
-    aliases: &["e", "epic"],
+    aliases: &["e"],
     description: "Browse epics",
+    arg: CommandArg::optional("[project]", ArgKind::Project),
+  },
+  Command {
+    name: "epic",
+    aliases: &[],
+    description: "Open an epic",
+    arg: CommandArg::required("<key>", ArgKind::Epic),
+  },
+  Command {
+    name: "tree",
+    aliases: &[],
+    description: "Browse the project hierarchy",
+    arg: CommandArg::optional("[project]", ArgKind::Project),
+  },
+  Command {
+    name: "jql",
+    aliases: &[],
+    description: "Search issues with JQL",
+    arg: CommandArg::required("<query>", ArgKind::Text),
   },
   Command {
     name: "searches",
     aliases: &["s", "search", "filters"],
-    description: "Saved searches/filters",
+    description: "Open a saved filter (or the filter list) in the browser",
+    arg: CommandArg::optional("[filter id]", ArgKind::Text),
+  },
+  Command {
+    name: "export",
+    aliases: &[],
+    description: "Export the issue list (or clip: copy as Markdown)",
+    arg: CommandArg::required("csv|json|md|clip [path]", ArgKind::Text),
+  },
+  Command {
+    name: "current",
+    aliases: &["branch"],
+    description: "Open the issue of the current git branch",
+    arg: None,
+  },
+  Command {
+    name: "offline",
+    aliases: &["off"],
+    description: "Toggle offline mode",
+    arg: None,
+  },
+  Command {
+    name: "cache",
+    aliases: &[],
+    description: "Cache statistics and maintenance",
+    arg: None,
+  },
+  Command {
+    name: "notifications",
+    aliases: &["notif"],
+    description: "Changes to watched issues",
+    arg: None,
+  },
+  Command {
+    name: "pending",
+    aliases: &["queue"],
+    description: "Changes queued while offline",
+    arg: None,
   },
   Command {
     name: "quit",
     aliases: &["q", "exit"],
     description: "Exit j9s",
+    arg: None,
   },
 ];
 
-/// Get autocomplete suggestions for a given input
-pub fn get_suggestions(input: &str) -> Vec<&'static Command> {
-  let input_lower = input.to_lowercase();
+/// A user-defined command from the config `aliases:` section.
+///
+/// The expansion is a built-in command line, e.g. `board 42` or
+/// `jql "assignee = {me} AND project = {project}"`.
+#[derive(Debug, Clone)]
+pub struct Alias {
+  pub name: String,
+  pub expansion: String,
+}
 
-  if input_lower.is_empty() {
-    return COMMANDS.iter().collect();
+impl Alias {
+  pub fn from_config(aliases: &BTreeMap<String, String>) -> Vec<Self> {
+    aliases
+      .iter()
+      .map(|(name, expansion)| Self {
+        name: name.clone(),
+        expansion: expansion.clone(),
+      })
+      .collect()
   }
 
-  let mut matches: Vec<(&Command, u32)> = Vec::new();
-
-  for cmd in COMMANDS {
-    // Exact match on name
-    if cmd.name == input_lower {
-      matches.push((cmd, 0)); // Highest priority
-      continue;
+  /// The alias as a palette entry, described by its expansion
+  fn command(&self) -> Command<'_> {
+    Command {
+      name: &self.name,
+      aliases: &[],
+      description: &self.expansion,
+      arg: None,
     }
+  }
+}
 
-    // Exact match on alias
-    if cmd.aliases.contains(&input_lower.as_str()) {
-      matches.push((cmd, 1));
-      continue;
-    }
+/// A command with its argument, ready to execute
+#[derive(Debug, Clone)]
+pub struct ParsedCommand {
+  pub command: &'static Command<'static>,
+  pub arg: Option<String>,
+  /// The user alias this was expanded from
+  pub alias: Option<String>,
+}
 
-    // Prefix match on name
-    if cmd.name.starts_with(&input_lower) {
-      matches.push((cmd, 2));
-      continue;
-    }
+impl ParsedCommand {
+  pub fn name(&self) -> &'static str {
+    self.command.name
+  }
+}
 
-    // Prefix match on alias
-    if cmd.aliases.iter().any(|a| a.starts_with(&input_lower)) {
-      matches.push((cmd, 3));
-      continue;
+/// Parse palette input like `board Team A` or `issues PROJ`.
+///
+/// The name must be a command, one of its aliases or a prefix of only one
+/// command; the substring matches offered as suggestions are not accepted.
+/// User aliases are replaced by their expansion. The argument is the rest of the input, with spaces kept and
+/// surrounding quotes removed.
+pub fn parse(input: &str, aliases: &[Alias]) -> Result<ParsedCommand> {
+  let input = input.trim();
+  let (name, arg) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+  if name.is_empty() {
+    return Err(eyre!("Empty command"));
+  }
+  let found = resolve(name, aliases)?;
+
+  let arg = arg.trim();
+  let arg = arg
+    .strip_prefix('"')
+    .and_then(|a| a.strip_suffix('"'))
+    .unwrap_or(arg);
+  let arg = Some(arg.to_string()).filter(|a| !a.is_empty());
+
+  let Some(command) = COMMANDS.iter().find(|c| c.name == found.name) else {
+    // Built-ins come first, so this is an alias
+    if arg.is_some() {
+      return Err(eyre!("{} takes no argument", found.name));
     }
+    // Expansions can't refer to other aliases, so they can't loop
+    let mut parsed = parse(found.description, &[])?;
+    parsed.alias = Some(found.name.to_string());
+    return Ok(parsed);
+  };
 
-    // Fuzzy match (contains)
-    if cmd.name.contains(&input_lower) {
-      matches.push((cmd, 4));
-      continue;
+  match (&command.arg, &arg) {
+    (None, Some(_)) => Err(eyre!("{} takes no argument", command.name)),
+    (Some(spec), None) if spec.required => Err(eyre!("Usage: {} {}", command.name, spec.usage)),
+    _ => Ok(ParsedCommand {
+      command,
+      arg,
+      alias: None,
+    }),
+  }
+}
+
+/// Find the command a typed name refers to: an exact match on a name or
+/// alias, or else the only command it is a prefix of
+fn resolve<'a>(name: &str, aliases: &'a [Alias]) -> Result<Command<'a>> {
+  let mut matches: Vec<(Command, u32)> = COMMANDS
+    .iter()
+    .cloned()
+    .chain(aliases.iter().map(Alias::command))
+    .filter_map(|cmd| match_rank(name, cmd.name, cmd.aliases).map(|rank| (cmd, rank)))
+    .filter(|(_, rank)| *rank <= 3)
+    .collect();
+  matches.sort_by_key(|(_, rank)| *rank);
+
+  match matches.as_slice() {
+    [] => Err(eyre!("Unknown command: {}", name)),
+    [(cmd, rank), ..] if *rank <= 1 => Ok(cmd.clone()),
+    [(cmd, _)] => Ok(cmd.clone()),
+    _ => {
+      let names: Vec<&str> = matches.iter().map(|(cmd, _)| cmd.name).collect();
+      Err(eyre!("Ambiguous command: {} ({})", name, names.join(", ")))
     }
+  }
+}
+
+/// Fill in `{project}`, `{me}` and `{today}` in a command argument
+pub fn expand_variables(text: &str, project: &str, today: NaiveDate) -> String {
+  text
+    .replace("{project}", project)
+    .replace("{me}", "currentUser()")
+    .replace("{today}", &today.format("%Y-%m-%d").to_string())
+}
 
+/// How well `input` matches a name and its aliases; lower is better.
+///
+/// Shared by command and argument completion.
+pub fn match_rank(input: &str, name: &str, aliases: &[&str]) -> Option<u32> {
+  let input = input.to_lowercase();
+  let name = name.to_lowercase();
+  let aliases: Vec<String> = aliases.iter().map(|a| a.to_lowercase()).collect();
+
+  if name == input {
+    // Exact match on name
+    Some(0)
+  } else if aliases.contains(&input) {
+    // Exact match on alias
+    Some(1)
+  } else if name.starts_with(&input) {
+    // Prefix match on name
+    Some(2)
+  } else if aliases.iter().any(|a| a.starts_with(&input)) {
+    // Prefix match on alias
+    Some(3)
+  } else if name.contains(&input) {
+    // Fuzzy match (contains)
+    Some(4)
+  } else if aliases.iter().any(|a| a.contains(&input)) {
     // Fuzzy match on alias
-    if cmd.aliases.iter().any(|a| a.contains(&input_lower)) {
-      matches.push((cmd, 5));
-    }
+    Some(5)
+  } else {
+    None
   }
+}
 
-  // Sort by priority
+/// Get autocomplete suggestions for a given input, built-in commands
+/// before user aliases
+pub fn get_suggestions<'a>(input: &str, aliases: &'a [Alias]) -> Vec<Command<'a>> {
+  let commands = COMMANDS
+    .iter()
+    .cloned()
+    .chain(aliases.iter().map(Alias::command));
+  if input.is_empty() {
+    return commands.collect();
+  }
+
+  let mut matches: Vec<(Command, u32)> = commands
+    .filter_map(|cmd| match_rank(input, cmd.name, cmd.aliases).map(|rank| (cmd, rank)))
+    .collect();
+
+  // Sort by priority (stable, so ties keep the list order)
   matches.sort_by_key(|(_, priority)| *priority);
 
   matches.into_iter().map(|(cmd, _)| cmd).collect()
@@ -95,34 +334,81 @@ mod tests {
 
   #[test]
   fn test_empty_input_returns_all() {
-    let suggestions = get_suggestions("");
+    let suggestions = get_suggestions("", &[]);
     assert_eq!(suggestions.len(), COMMANDS.len());
   }
 
   #[test]
   fn test_exact_match() {
-    let suggestions = get_suggestions("issues");
+    let suggestions = get_suggestions("issues", &[]);
     assert!(!suggestions.is_empty());
     assert_eq!(suggestions[0].name, "issues");
   }
 
   #[test]
   fn test_alias_match() {
-    let suggestions = get_suggestions("i");
+    let suggestions = get_suggestions("i", &[]);
     assert!(!suggestions.is_empty());
     assert_eq!(suggestions[0].name, "issues");
   }
 
   #[test]
   fn test_prefix_match() {
-    let suggestions = get_suggestions("iss");
+    let suggestions = get_suggestions("iss", &[]);
     assert!(!suggestions.is_empty());
     assert_eq!(suggestions[0].name, "issues");
   }
 
+  #[test]
+  fn test_parse_arguments() {
+    let parsed = parse("board Team A", &[]).unwrap();
+    assert_eq!(parsed.name(), "board");
+    assert_eq!(parsed.arg.as_deref(), Some("Team A"));
+
+    let parsed = parse("i PROJ", &[]).unwrap();
+    assert_eq!(parsed.name(), "issues");
+    assert_eq!(parsed.arg.as_deref(), Some("PROJ"));
+
+    assert!(parse("board", &[]).is_err());
+    assert!(parse("quit now", &[]).is_err());
+    assert!(parse("xyz", &[]).is_err());
+  }
+
+  #[test]
+  fn test_parse_requires_exact_or_unique_prefix() {
+    assert_eq!(parse("iss", &[]).unwrap().name(), "issues");
+    assert_eq!(parse("notif", &[]).unwrap().name(), "notifications");
+    // Substring matches are only suggestions
+    assert!(parse("sue", &[]).is_err());
+    let err = parse("ep PROJ", &[]).unwrap_err().to_string();
+    assert_eq!(err, "Ambiguous command: ep (epics, epic)");
+    // An exact name wins over longer names it is a prefix of
+    assert_eq!(parse("epic A-1", &[]).unwrap().name(), "epic");
+  }
+
+  #[test]
+  fn test_user_aliases() {
+    let aliases = vec![Alias {
+      name: "mine".to_string(),
+      expansion: "jql \"assignee = {me} AND project = {project}\"".to_string(),
+    }];
+    assert_eq!(get_suggestions("mi", &aliases)[0].name, "mine");
+
+    let parsed = parse("mine", &aliases).unwrap();
+    assert_eq!(parsed.name(), "jql");
+    assert_eq!(parsed.alias.as_deref(), Some("mine"));
+    let jql = expand_variables(
+      parsed.arg.as_deref().unwrap(),
+      "PROJ",
+      NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+    );
+    assert_eq!(jql, "assignee = currentUser() AND project = PROJ");
+    assert!(parse("mine extra", &aliases).is_err());
+  }
+
   #[test]
   fn test_fuzzy_match() {
-    let suggestions = get_suggestions("sue");
+    let suggestions = get_suggestions("sue", &[]);
     assert!(!suggestions.is_empty());
     assert_eq!(suggestions[0].name, "issues");
   }
@@ -1,7 +1,11 @@
+use crate::git::DEFAULT_BRANCH_TEMPLATE;
+use crate::ui::keymap::Bindings;
+use crate::ui::{Keymap, ListColumn};
 use color_eyre::{eyre::eyre, Result};
 use serde::Deserialize;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -11,6 +15,170 @@ pub struct Config {
   pub title: Option<String>,
   #[serde(default)]
   pub boards: BoardsConfig,
+  #[serde(default)]
+  pub list: ListConfig,
+  #[serde(default)]
+  pub git: GitConfig,
+  /// User-defined palette commands: name -> command line, e.g.
+  /// `mine: jql "assignee = {me} AND project = {project}"`
+  #[serde(default)]
+  pub aliases: BTreeMap<String, String>,
+  /// Key bindings; replaced by hotkeys.yaml when there is one
+  #[serde(default)]
+  pub keys: KeysConfig,
+  /// Color skin: `dark` (default), `light`, a skin name from
+  /// `~/.config/j9s/skins/` or a path to a skin file
+  pub skin: Option<String>,
+  /// Start in offline mode: serve cached data only, never touch the network
+  #[serde(default)]
+  pub offline: bool,
+  #[serde(default)]
+  pub cache: CacheConfig,
+  #[serde(default)]
+  pub refresh: RefreshConfig,
+  #[serde(default)]
+  pub notifications: NotificationsConfig,
+}
+
+/// Key bindings: action -> keys, for all views and per view
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct KeysConfig {
+  #[serde(default)]
+  pub global: Bindings,
+  /// Overrides per view (issues, board, boards, epics, epic, tree, metrics,
+  /// burndown, issue, cache, notifications, pending)
+  #[serde(default)]
+  pub views: BTreeMap<String, Bindings>,
+}
+
+impl KeysConfig {
+  /// The effective keymap of a view
+  pub fn keymap(&self, view: &str) -> Keymap {
+    Keymap::new(&self.global, self.views.get(view))
+  }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CacheConfig {
+  /// How long cached data is fresh before refetching (e.g. "5m")
+  #[serde(default, deserialize_with = "deserialize_opt_duration")]
+  pub stale_time: Option<Duration>,
+  /// Stale time overrides per entity type (issue_summary, issue, board, board_config,
+  /// sprint, current_user, field, status)
+  #[serde(default, deserialize_with = "deserialize_duration_map")]
+  pub stale_times: BTreeMap<String, Duration>,
+  /// Maximum size of the cache database in megabytes
+  #[serde(default, deserialize_with = "deserialize_size_mb")]
+  pub max_size_mb: Option<u64>,
+  /// How often cached issue lists are checked for deleted or moved issues
+  #[serde(default, deserialize_with = "deserialize_opt_duration")]
+  pub reconcile_interval: Option<Duration>,
+}
+
+impl CacheConfig {
+  /// Maximum cache size in bytes, if limited
+  pub fn max_size_bytes(&self) -> Option<u64> {
+    self.max_size_mb.and_then(mb_to_bytes)
+  }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RefreshConfig {
+  /// How often the visible view reloads its data in the background (off if unset)
+  #[serde(default, deserialize_with = "deserialize_opt_duration")]
+  pub interval: Option<Duration>,
+  /// Interval overrides per view (issues, board, boards, epics, epic, tree, metrics,
+  /// burndown, issue); "0s" disables
+  #[serde(default, deserialize_with = "deserialize_duration_map")]
+  pub views: BTreeMap<String, Duration>,
+}
+
+impl RefreshConfig {
+  /// Auto-refresh interval for a view, if auto-refresh is enabled for it
+  pub fn interval_for(&self, view: &str) -> Option<Duration> {
+    self
+      .views
+      .get(view)
+      .copied()
+      .or(self.interval)
+      .filter(|interval| !interval.is_zero())
+  }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NotificationsConfig {
+  /// Issues to watch for changes (the watcher is off if unset)
+  pub jql: Option<String>,
+  /// How often to check for changes (default: 2m)
+  #[serde(default, deserialize_with = "deserialize_opt_duration")]
+  pub interval: Option<Duration>,
+  /// How to alert about new notifications besides the notification center
+  #[serde(default)]
+  pub alert: AlertMethod,
+}
+
+impl NotificationsConfig {
+  pub fn interval(&self) -> Duration {
+    self.interval.unwrap_or(Duration::from_secs(120))
+  }
+}
+
+/// Terminal alert for new notifications
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertMethod {
+  /// In-app only
+  #[default]
+  None,
+  /// Ring the terminal bell
+  Bell,
+  /// OSC 9 desktop notification (iTerm2, Windows Terminal, kitty, ...)
+  Osc9,
+  /// OSC 777 desktop notification (urxvt, foot, some VTE terminals)
+  Osc777,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListConfig {
+  /// Columns shown in issue lists and written by `:export`
+  #[serde(default = "default_list_columns")]
+  pub columns: Vec<ListColumn>,
+  /// Start issue lists with done issues hidden (toggle with `x`)
+  #[serde(default)]
+  pub hide_done: bool,
+}
+
+impl Default for ListConfig {
+  fn default() -> Self {
+    Self {
+      columns: default_list_columns(),
+      hide_done: false,
+    }
+  }
+}
+
+fn default_list_columns() -> Vec<ListColumn> {
+  ListColumn::DEFAULT.to_vec()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitConfig {
+  /// Name of branches created with `B`; placeholders: {key}, {type},
+  /// {summary-slug}, {project}
+  #[serde(default = "default_branch_template")]
+  pub branch_template: String,
+}
+
+impl Default for GitConfig {
+  fn default() -> Self {
+    Self {
+      branch_template: default_branch_template(),
+    }
+  }
+}
+
+fn default_branch_template() -> String {
+  DEFAULT_BRANCH_TEMPLATE.to_string()
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -18,8 +186,47 @@ pub struct BoardsConfig {
   /// Swimlane names to hide in board views (case-insensitive)
   #[serde(default, deserialize_with = "deserialize_lowercase_set")]
   pub hide_swimlanes: BTreeSet<String>,
+  /// How long resolved issues stay on boards (default: 14d); "0s" shows
+  /// only unresolved issues
+  #[serde(default, deserialize_with = "deserialize_opt_duration")]
+  pub done_window: Option<Duration>,
+  /// How far back `:metrics` looks (default: 12 weeks, i.e. 84d)
+  #[serde(default, deserialize_with = "deserialize_opt_duration")]
+  pub metrics_window: Option<Duration>,
+}
+
+impl BoardsConfig {
+  /// Issues to load for a board: unresolved ones, plus those resolved within
+  /// the done window
+  pub fn issue_jql(&self) -> String {
+    let window = self.done_window.unwrap_or(DEFAULT_DONE_WINDOW);
+    let since = match window.as_secs() {
+      0..60 => return "resolution IS EMPTY".to_string(),
+      secs if secs % 86400 == 0 => format!("-{}d", secs / 86400),
+      secs => format!("-{}m", secs / 60),
+    };
+    format!("resolution IS EMPTY OR resolved >= {}", since)
+  }
+
+  /// Days of history `:metrics` covers, at least one
+  pub fn metrics_days(&self) -> u64 {
+    let window = self.metrics_window.unwrap_or(DEFAULT_METRICS_WINDOW);
+    (window.as_secs() / 86400).max(1)
+  }
+
+  /// Issues `:metrics` needs: everything that moved in the window, plus
+  /// what's still open (it's counted as work in progress)
+  pub fn metrics_jql(&self) -> String {
+    format!(
+      "statusCategory != Done OR updated >= -{}d",
+      self.metrics_days()
+    )
+  }
 }
 
+const DEFAULT_DONE_WINDOW: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+const DEFAULT_METRICS_WINDOW: Duration = Duration::from_secs(84 * 24 * 60 * 60);
+
 fn deserialize_lowercase_set<'de, D>(deserializer: D) -> Result<BTreeSet<String>, D::Error>
 where
   D: serde::Deserializer<'de>,
@@ -28,6 +235,66 @@ where
   Ok(v.into_iter().map(|s| s.to_lowercase()).collect())
 }
 
+/// Parse a duration like "30s", "5m", "2h" or "1d" (bare numbers are seconds)
+pub fn parse_duration(s: &str) -> Result<Duration> {
+  let s = s.trim();
+  let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+  let (num, unit) = s.split_at(split);
+  let num: u64 = num.parse().map_err(|_| eyre!("Invalid duration '{}'", s))?;
+  let unit_secs = match unit.trim() {
+    "" | "s" => 1,
+    "m" => 60,
+    "h" => 60 * 60,
+    "d" => 60 * 60 * 24,
+    _ => return Err(eyre!("Invalid duration unit in '{}' (use s, m, h or d)", s)),
+  };
+  let secs = num
+    .checked_mul(unit_secs)
+    .ok_or_else(|| eyre!("Duration '{}' is too long", s))?;
+  Ok(Duration::from_secs(secs))
+}
+
+fn mb_to_bytes(mb: u64) -> Option<u64> {
+  mb.checked_mul(1024 * 1024)
+}
+
+fn deserialize_size_mb<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+  D: serde::Deserializer<'de>,
+{
+  let v: Option<u64> = Option::deserialize(deserializer)?;
+  match v {
+    Some(mb) if mb_to_bytes(mb).is_none() => Err(serde::de::Error::custom(format!(
+      "Cache size {}MB is too large",
+      mb
+    ))),
+    v => Ok(v),
+  }
+}
+
+fn deserialize_opt_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+  D: serde::Deserializer<'de>,
+{
+  let v: Option<String> = Option::deserialize(deserializer)?;
+  v.map(|s| parse_duration(&s).map_err(serde::de::Error::custom))
+    .transpose()
+}
+
+fn deserialize_duration_map<'de, D>(deserializer: D) -> Result<BTreeMap<String, Duration>, D::Error>
+where
+  D: serde::Deserializer<'de>,
+{
+  let v: BTreeMap<String, String> = BTreeMap::deserialize(deserializer)?;
+  v.into_iter()
+    .map(|(k, s)| {
+      parse_duration(&s)
+        .map(|d| (k, d))
+        .map_err(serde::de::Error::custom)
+    })
+    .collect()
+}
+
 #[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum AuthType {
@@ -44,8 +311,17 @@ pub enum AuthType {
 pub struct JiraConfig {
   pub url: String,
   pub email: String,
-  /// Custom field name for epic link (e.g., "customfield_10014")
+  /// Custom field name for epic link (e.g., "customfield_10014"); found in
+  /// Jira's field list when unset
   pub epic_field: Option<String>,
+  /// Story points field id (e.g. "customfield_10016"); found in Jira's
+  /// field list when unset
+  pub story_points_field: Option<String>,
+  /// Custom fields to load, by name as shown in Jira or by id
+  /// (e.g. "Story Points", "customfield_10020"); they can be list columns
+  /// and filters, and are shown and edited in the issue detail
+  #[serde(default)]
+  pub custom_fields: Vec<String>,
   /// Authentication type: auto, cloud, or onpremise
   #[serde(default)]
   pub auth_type: AuthType,
@@ -101,12 +377,28 @@ impl Config {
     let contents = std::fs::read_to_string(path)
       .map_err(|e| eyre!("Failed to read config file {}: {}", path.display(), e))?;
 
-    let config: Config = serde_yaml::from_str(&contents)
+    let mut config: Config = serde_yaml::from_str(&contents)
       .map_err(|e| eyre!("Failed to parse config file {}: {}", path.display(), e))?;
 
+    if let Some(path) = Self::find_hotkeys_file(path) {
+      let contents = std::fs::read_to_string(&path)
+        .map_err(|e| eyre!("Failed to read hotkeys file {}: {}", path.display(), e))?;
+      config.keys = serde_yaml::from_str(&contents)
+        .map_err(|e| eyre!("Failed to parse hotkeys file {}: {}", path.display(), e))?;
+    }
+
     Ok(config)
   }
 
+  /// Find hotkeys.yaml next to the config file, or in $XDG_CONFIG_HOME/j9s
+  fn find_hotkeys_file(config_path: &Path) -> Option<PathBuf> {
+    let sibling = config_path.with_file_name("hotkeys.yaml");
+    let xdg = dirs::config_dir().map(|dir| dir.join("j9s").join("hotkeys.yaml"));
+    std::iter::once(sibling)
+      .chain(xdg)
+      .find(|path| path.exists())
+  }
+
   /// Get the Jira API token from environment variables.
   ///
   /// Checks J9S_JIRA_TOKEN first, then JIRA_API_TOKEN as fallback.
@@ -128,3 +420,112 @@ impl Config {
       .map_err(|_| eyre!("Jira password not found. Set J9S_JIRA_PASSWORD environment variable."))
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::ui::Action;
+  use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+  #[test]
+  fn test_parse_duration() {
+    assert_eq!(parse_duration("30").unwrap(), Duration::from_secs(30));
+    assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+    assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+    assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+    assert_eq!(parse_duration("1d").unwrap(), Duration::from_secs(86400));
+    assert!(parse_duration("5x").is_err());
+    assert!(parse_duration("m").is_err());
+    // Too many seconds for a u64 is an error, not a wrapped value
+    assert!(parse_duration("18446744073709551615d").is_err());
+    assert_eq!(
+      parse_duration("18446744073709551615").unwrap(),
+      Duration::from_secs(u64::MAX)
+    );
+  }
+
+  #[test]
+  fn test_cache_config() {
+    let cache: CacheConfig = serde_yaml::from_str(
+      "stale_time: 10m\nstale_times:\n  board: 1h\nmax_size_mb: 50\nreconcile_interval: 30m\n",
+    )
+    .unwrap();
+    assert_eq!(cache.stale_time, Some(Duration::from_secs(600)));
+    assert_eq!(cache.stale_times["board"], Duration::from_secs(3600));
+    assert_eq!(cache.reconcile_interval, Some(Duration::from_secs(1800)));
+    assert_eq!(cache.max_size_bytes(), Some(50 * 1024 * 1024));
+
+    // Sizes that don't fit in bytes are rejected instead of wrapping
+    assert!(serde_yaml::from_str::<CacheConfig>("max_size_mb: 18446744073709551615\n").is_err());
+    let cache = CacheConfig {
+      max_size_mb: Some(u64::MAX),
+      ..Default::default()
+    };
+    assert_eq!(cache.max_size_bytes(), None);
+  }
+
+  #[test]
+  fn test_refresh_config() {
+    let refresh: RefreshConfig =
+      serde_yaml::from_str("interval: 1m\nviews:\n  board: 30s\n  issue: 0s\n").unwrap();
+    assert_eq!(refresh.interval_for("board"), Some(Duration::from_secs(30)));
+    assert_eq!(
+      refresh.interval_for("issues"),
+      Some(Duration::from_secs(60))
+    );
+    assert_eq!(refresh.interval_for("issue"), None);
+    assert_eq!(RefreshConfig::default().interval_for("board"), None);
+  }
+
+  #[test]
+  fn test_boards_done_window() {
+    let jql = |yaml: &str| {
+      serde_yaml::from_str::<BoardsConfig>(yaml)
+        .unwrap()
+        .issue_jql()
+    };
+    assert_eq!(jql("{}"), "resolution IS EMPTY OR resolved >= -14d");
+    assert_eq!(
+      jql("done_window: 12h"),
+      "resolution IS EMPTY OR resolved >= -720m"
+    );
+    assert_eq!(jql("done_window: 0s"), "resolution IS EMPTY");
+  }
+
+  #[test]
+  fn test_boards_metrics_window() {
+    let boards = |yaml: &str| serde_yaml::from_str::<BoardsConfig>(yaml).unwrap();
+    assert_eq!(
+      boards("{}").metrics_jql(),
+      "statusCategory != Done OR updated >= -84d"
+    );
+    assert_eq!(boards("metrics_window: 30d").metrics_days(), 30);
+    assert_eq!(boards("metrics_window: 1h").metrics_days(), 1);
+  }
+
+  #[test]
+  fn test_notifications_config() {
+    let notifications: NotificationsConfig =
+      serde_yaml::from_str("jql: assignee = currentUser()\nalert: osc777\n").unwrap();
+    assert_eq!(notifications.alert, AlertMethod::Osc777);
+    assert_eq!(notifications.interval(), Duration::from_secs(120));
+  }
+
+  #[test]
+  fn test_keys_config() {
+    // The example spells out the defaults
+    let example: KeysConfig =
+      serde_yaml::from_str(include_str!("../hotkeys.example.yaml")).unwrap();
+    let left = KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT);
+    assert_eq!(
+      example.keymap("board").action(&left),
+      Keymap::default().action(&left)
+    );
+
+    let keys: KeysConfig =
+      serde_yaml::from_str("views:\n  board:\n    move_left: [a, Left]\n").unwrap();
+    let a = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+    assert_eq!(keys.keymap("board").action(&a), Some(Action::MoveLeft));
+    assert_eq!(keys.keymap("issue").action(&a), Some(Action::AssignToMe));
+  }
+}
@@ -0,0 +1,160 @@
+//! Versioned schema migrations, embedded in the binary.
+//!
+//! The schema version is the number of applied migrations, stored in
+//! `PRAGMA user_version`. Migrations are never edited once released; add a
+//! new file to `migrations/` and append it to [`MIGRATIONS`] instead.
+//!
+//! A migration whose SQL fails returns a [`MigrationError`], which callers
+//! can tell apart from the database being unreadable, busy or locked.
+
+use color_eyre::{eyre::eyre, Report, Result};
+use rusqlite::{Connection, ErrorCode};
+use std::fmt;
+use tracing::{info, warn};
+
+/// Migrations in order; migration N (1-based) brings the schema to version N
+const MIGRATIONS: &[(&str, &str)] = &[
+  (
+    "001_initial",
+    include_str!("../../migrations/001_initial.sql"),
+  ),
+  (
+    "002_pending_mutations",
+    include_str!("../../migrations/002_pending_mutations.sql"),
+  ),
+  (
+    "003_access_tracking",
+    include_str!("../../migrations/003_access_tracking.sql"),
+  ),
+  (
+    "004_reconciliation",
+    include_str!("../../migrations/004_reconciliation.sql"),
+  ),
+  (
+    "005_notifications",
+    include_str!("../../migrations/005_notifications.sql"),
+  ),
+  (
+    "006_command_history",
+    include_str!("../../migrations/006_command_history.sql"),
+  ),
+];
+
+/// A migration's SQL failed against the database's schema
+#[derive(Debug)]
+pub struct MigrationError {
+  pub name: &'static str,
+  pub source: rusqlite::Error,
+}
+
+impl fmt::Display for MigrationError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Migration {} failed: {}", self.name, self.source)
+  }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// A failed migration as a `MigrationError`, unless the database was just
+/// busy or locked (e.g. by another j9s) and migrating later may work
+fn migration_error(name: &'static str, e: rusqlite::Error) -> Report {
+  match e.sqlite_error_code() {
+    Some(ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked) => {
+      eyre!("Migration {} failed: {}", name, e)
+    }
+    _ => MigrationError { name, source: e }.into(),
+  }
+}
+
+/// Schema version this binary expects
+pub fn latest_version() -> u32 {
+  MIGRATIONS.len() as u32
+}
+
+/// Get the schema version of a database
+pub fn current_version(conn: &Connection) -> Result<u32> {
+  conn
+    .pragma_query_value(None, "user_version", |row| row.get(0))
+    .map_err(|e| eyre!("Failed to read schema version: {}", e))
+}
+
+/// Apply pending migrations in a single transaction.
+///
+/// On failure nothing is applied and the database keeps its old version.
+pub fn migrate(conn: &mut Connection) -> Result<()> {
+  let version = current_version(conn)?;
+  let latest = latest_version();
+
+  if version > latest {
+    // Written by a newer j9s; leave it alone rather than destroying its data
+    warn!(
+      "Database schema version {} is newer than supported version {}",
+      version, latest
+    );
+    return Ok(());
+  }
+  if version == latest {
+    return Ok(());
+  }
+
+  let tx = conn
+    .transaction()
+    .map_err(|e| eyre!("Failed to begin migration: {}", e))?;
+
+  for (i, (name, sql)) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+    info!("Applying migration {}", name);
+    tx.execute_batch(sql)
+      .map_err(|e| migration_error(name, e))?;
+    tx.pragma_update(None, "user_version", i as u32 + 1)
+      .map_err(|e| eyre!("Failed to record schema version: {}", e))?;
+  }
+
+  tx.commit()
+    .map_err(|e| eyre!("Failed to commit migrations: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_migrate_fresh_database() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrate(&mut conn).unwrap();
+    assert_eq!(current_version(&conn).unwrap(), latest_version());
+
+    // Running again is a no-op
+    migrate(&mut conn).unwrap();
+    assert_eq!(current_version(&conn).unwrap(), latest_version());
+  }
+
+  #[test]
+  fn test_migrate_unversioned_database() {
+    // Databases from before versioning have the initial tables but version 0
+    let mut conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(MIGRATIONS[0].1).unwrap();
+    migrate(&mut conn).unwrap();
+    assert_eq!(current_version(&conn).unwrap(), latest_version());
+  }
+
+  #[test]
+  fn test_failed_migration_rolls_back() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    // A conflicting view makes migration 005 fail after 001-004 succeeded
+    conn
+      .execute_batch("CREATE VIEW notifications AS SELECT 1")
+      .unwrap();
+
+    let error = migrate(&mut conn).unwrap_err();
+    assert!(error.downcast_ref::<MigrationError>().is_some());
+    assert_eq!(current_version(&conn).unwrap(), 0);
+    let tables: i64 = conn
+      .query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE name = 'entity_cache'",
+        [],
+        |row| row.get(0),
+      )
+      .unwrap();
+    assert_eq!(tables, 0);
+  }
+}
@@ -1,9 +1,21 @@
+mod migrations;
+
+use chrono::Utc;
 use color_eyre::{eyre::eyre, Result};
+use migrations::MigrationError;
 use rusqlite::Connection;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use tracing::warn;
 
-/// Open the application database, creating it if needed.
+/// Open the application database, creating and migrating it if needed.
+///
+/// The database only holds cached data (plus queued offline changes), so
+/// if a migration fails the file is moved aside and a fresh one is created.
+/// Other errors (permissions, a database locked by another j9s, ...) are
+/// returned as they are.
+/// Queued changes are carried over; if that fails the old file is put back
+/// and the error returned, rather than dropping them.
 pub fn open_connection() -> Result<Arc<Mutex<Connection>>> {
   let path = default_path()?;
 
@@ -13,17 +25,212 @@ pub fn open_connection() -> Result<Arc<Mutex<Connection>>> {
       .map_err(|e| eyre!("Failed to create database directory: {}", e))?;
   }
 
-  let conn = Connection::open(&path)
+  Ok(Arc::new(Mutex::new(open_or_rebuild(&path)?)))
+}
+
+/// Open and migrate a database file, rebuilding it if a migration fails
+fn open_or_rebuild(path: &Path) -> Result<Connection> {
+  let error = match open_and_migrate(path) {
+    Ok(conn) => return Ok(conn),
+    Err(e) if e.downcast_ref::<MigrationError>().is_some() => e,
+    Err(e) => return Err(e),
+  };
+
+  let backup = backup_path(path);
+  warn!(
+    "Rebuilding cache database ({}); old copy kept at {}",
+    error,
+    backup.display()
+  );
+  std::fs::rename(path, &backup)
+    .map_err(|e| eyre!("Failed to move aside broken database: {}", e))?;
+
+  let rebuilt = open_and_migrate(path).and_then(|conn| {
+    copy_pending_mutations(&conn, &backup)?;
+    Ok(conn)
+  });
+  match rebuilt {
+    Ok(conn) => Ok(conn),
+    Err(e) => {
+      // Put the old database back so its queued changes aren't lost
+      let _ = std::fs::remove_file(path);
+      std::fs::rename(&backup, path).map_err(|e| {
+        eyre!(
+          "Failed to restore database from {}: {}",
+          backup.display(),
+          e
+        )
+      })?;
+      Err(eyre!(
+        "Cache database {} can't be migrated ({}) or rebuilt with its queued changes: {}",
+        path.display(),
+        error,
+        e
+      ))
+    }
+  }
+}
+
+/// A name for moving a database aside that doesn't replace an earlier
+/// backup, e.g. `cache.db.20240501-120000.bak`
+fn backup_path(path: &Path) -> PathBuf {
+  let stamp = Utc::now().format("%Y%m%d-%H%M%S");
+  (0..)
+    .map(|n| match n {
+      0 => path.with_extension(format!("db.{}.bak", stamp)),
+      n => path.with_extension(format!("db.{}-{}.bak", stamp, n)),
+    })
+    .find(|backup| !backup.exists())
+    .expect("unbounded range")
+}
+
+/// Copy queued offline changes from an old database into a rebuilt one
+fn copy_pending_mutations(conn: &Connection, old: &Path) -> Result<()> {
+  conn
+    .execute("ATTACH DATABASE ? AS old", [old.to_string_lossy().as_ref()])
+    .map_err(|e| eyre!("Failed to open old database: {}", e))?;
+
+  let has_queue: bool = conn
+    .query_row(
+      "SELECT COUNT(*) > 0 FROM old.sqlite_master WHERE type = 'table' AND name = 'pending_mutations'",
+      [],
+      |row| row.get(0),
+    )
+    .map_err(|e| eyre!("Failed to read old database: {}", e))?;
+  let copied = if has_queue {
+    conn
+      .execute(
+        "INSERT INTO pending_mutations
+           (entity_key, kind, payload, base_updated, state, error, created_at)
+         SELECT entity_key, kind, payload, base_updated, state, error, created_at
+         FROM old.pending_mutations ORDER BY id",
+        [],
+      )
+      .map_err(|e| eyre!("Failed to copy queued changes: {}", e))
+  } else {
+    Ok(0)
+  };
+
+  conn
+    .execute("DETACH DATABASE old", [])
+    .map_err(|e| eyre!("Failed to close old database: {}", e))?;
+  let copied = copied?;
+  if copied > 0 {
+    warn!(
+      "Carried {} queued change(s) over to the rebuilt database",
+      copied
+    );
+  }
+  Ok(())
+}
+
+/// Open a database file and bring its schema up to date.
+fn open_and_migrate(path: &Path) -> Result<Connection> {
+  let mut conn = Connection::open(path)
     .map_err(|e| eyre!("Failed to open database at {}: {}", path.display(), e))?;
+  migrations::migrate(&mut conn)?;
+  Ok(conn)
+}
 
+/// Open a migrated in-memory database (for tests).
+#[cfg(test)]
+pub fn open_in_memory() -> Result<Arc<Mutex<Connection>>> {
+  let mut conn =
+    Connection::open_in_memory().map_err(|e| eyre!("Failed to open database: {}", e))?;
+  migrations::migrate(&mut conn)?;
   Ok(Arc::new(Mutex::new(conn)))
 }
 
 /// Get the default database path.
-fn default_path() -> Result<PathBuf> {
+pub fn default_path() -> Result<PathBuf> {
   let data_dir = dirs::data_dir()
     .or_else(|| dirs::home_dir().map(|p| p.join(".local/share")))
     .ok_or_else(|| eyre!("Could not determine data directory"))?;
 
   Ok(data_dir.join("j9s").join("cache.db"))
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_rebuild_keeps_queued_changes() {
+    let dir = std::env::temp_dir().join(format!("j9s-db-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("cache.db");
+
+    // A queue from an old build next to something no migration expects
+    let conn = Connection::open(&path).unwrap();
+    conn
+      .execute_batch(
+        "CREATE TABLE pending_mutations (
+           id INTEGER PRIMARY KEY AUTOINCREMENT, entity_key TEXT NOT NULL,
+           kind TEXT NOT NULL, payload TEXT NOT NULL, base_updated TEXT,
+           state TEXT NOT NULL DEFAULT 'pending', error TEXT,
+           created_at TEXT NOT NULL DEFAULT (datetime('now')));
+         INSERT INTO pending_mutations (entity_key, kind, payload)
+           VALUES ('P-1', 'comment', '{}');
+         CREATE VIEW notifications AS SELECT 1;",
+      )
+      .unwrap();
+    drop(conn);
+
+    let conn = open_or_rebuild(&path).unwrap();
+    assert_eq!(
+      migrations::current_version(&conn).unwrap(),
+      migrations::latest_version()
+    );
+    let queued: String = conn
+      .query_row("SELECT entity_key FROM pending_mutations", [], |row| {
+        row.get(0)
+      })
+      .unwrap();
+    assert_eq!(queued, "P-1");
+    let backups = std::fs::read_dir(&dir)
+      .unwrap()
+      .filter(|entry| {
+        entry
+          .as_ref()
+          .unwrap()
+          .path()
+          .to_string_lossy()
+          .ends_with(".bak")
+      })
+      .count();
+    assert_eq!(backups, 1);
+
+    drop(conn);
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_open_error_keeps_database() {
+    let dir = std::env::temp_dir().join(format!("j9s-db-open-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    // Not a database at all: opening fails before any migration runs
+    let path = dir.join("cache.db");
+    std::fs::create_dir_all(&path).unwrap();
+
+    assert!(open_or_rebuild(&path).is_err());
+    assert!(path.is_dir());
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_backup_path_keeps_earlier_backups() {
+    let dir = std::env::temp_dir().join(format!("j9s-db-backup-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("cache.db");
+
+    let first = backup_path(&path);
+    std::fs::write(&first, "").unwrap();
+    let second = backup_path(&path);
+    assert_ne!(first, second);
+    assert!(second.to_string_lossy().ends_with(".bak"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+}
@@ -0,0 +1,134 @@
+//! Writing issue lists as CSV, JSON or Markdown (`:export`).
+
+use crate::jira::types::IssueSummary;
+use crate::ui::ListColumn;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+  Csv,
+  Json,
+  Markdown,
+}
+
+impl ExportFormat {
+  pub fn parse(s: &str) -> Option<Self> {
+    match s {
+      "csv" => Some(ExportFormat::Csv),
+      "json" => Some(ExportFormat::Json),
+      "md" | "markdown" => Some(ExportFormat::Markdown),
+      _ => None,
+    }
+  }
+
+  pub fn extension(&self) -> &'static str {
+    match self {
+      ExportFormat::Csv => "csv",
+      ExportFormat::Json => "json",
+      ExportFormat::Markdown => "md",
+    }
+  }
+}
+
+/// Render issues with the given columns, in the given order.
+pub fn render(format: ExportFormat, columns: &[ListColumn], issues: &[IssueSummary]) -> String {
+  match format {
+    ExportFormat::Csv => render_csv(columns, issues),
+    ExportFormat::Json => render_json(columns, issues),
+    ExportFormat::Markdown => render_markdown(columns, issues),
+  }
+}
+
+fn render_csv(columns: &[ListColumn], issues: &[IssueSummary]) -> String {
+  // RFC 4180: quote fields containing separators, quotes or line breaks
+  let field = |s: &str| {
+    if s.contains([',', '"', '\n', '\r']) {
+      format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+      s.to_string()
+    }
+  };
+
+  let mut out = String::new();
+  let header: Vec<_> = columns.iter().map(|c| field(c.header())).collect();
+  out.push_str(&header.join(","));
+  out.push_str("\r\n");
+  for issue in issues {
+    let row: Vec<_> = columns.iter().map(|c| field(&c.value(issue))).collect();
+    out.push_str(&row.join(","));
+    out.push_str("\r\n");
+  }
+  out
+}
+
+fn render_json(columns: &[ListColumn], issues: &[IssueSummary]) -> String {
+  let rows: Vec<serde_json::Value> = issues
+    .iter()
+    .map(|issue| {
+      let row: serde_json::Map<_, _> = columns
+        .iter()
+        .map(|c| (c.header().to_lowercase(), c.value(issue).into()))
+        .collect();
+      row.into()
+    })
+    .collect();
+  // Serializing plain strings can't fail
+  serde_json::to_string_pretty(&rows).unwrap_or_default() + "\n"
+}
+
+fn render_markdown(columns: &[ListColumn], issues: &[IssueSummary]) -> String {
+  let cell = |s: &str| s.replace('|', "\\|").replace(['\r', '\n'], " ");
+  let row = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+
+  let mut out = row(columns.iter().map(|c| c.header().to_string()).collect());
+  out.push_str(&row(columns.iter().map(|_| "---".to_string()).collect()));
+  for issue in issues {
+    out.push_str(&row(
+      columns.iter().map(|c| cell(&c.value(issue))).collect(),
+    ));
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::jira::types::test_issue;
+
+  fn issue(key: &str, summary: &str) -> IssueSummary {
+    IssueSummary {
+      summary: summary.to_string(),
+      status: "To Do".to_string(),
+      status_id: "1".to_string(),
+      issue_type: "Bug".to_string(),
+      ..test_issue(key)
+    }
+  }
+
+  const COLUMNS: &[ListColumn] = &[ListColumn::Key, ListColumn::Summary];
+
+  #[test]
+  fn test_csv_quotes_special_characters() {
+    let issues = [issue("A-1", "plain"), issue("A-2", "say \"hi\", twice")];
+    assert_eq!(
+      render(ExportFormat::Csv, COLUMNS, &issues),
+      "Key,Summary\r\nA-1,plain\r\nA-2,\"say \"\"hi\"\", twice\"\r\n"
+    );
+  }
+
+  #[test]
+  fn test_markdown_escapes_pipes() {
+    let issues = [issue("A-1", "a | b")];
+    assert_eq!(
+      render(ExportFormat::Markdown, COLUMNS, &issues),
+      "| Key | Summary |\n| --- | --- |\n| A-1 | a \\| b |\n"
+    );
+  }
+
+  #[test]
+  fn test_json_uses_column_names() {
+    let issues = [issue("A-1", "x")];
+    let json: serde_json::Value =
+      serde_json::from_str(&render(ExportFormat::Json, COLUMNS, &issues)).unwrap();
+    assert_eq!(json, serde_json::json!([{ "key": "A-1", "summary": "x" }]));
+  }
+}
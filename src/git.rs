@@ -0,0 +1,168 @@
+//! Git integration: branches named after issues, and the issue of the
+//! current branch.
+
+use color_eyre::{eyre::eyre, Result};
+use std::process::Command;
+
+/// Default template for branch names
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "{type}/{key}-{summary-slug}";
+
+/// Maximum length of the summary part of a branch name
+const MAX_SLUG_LEN: usize = 40;
+
+/// Run git in the current directory, returning trimmed stdout
+fn git(args: &[&str]) -> Result<String> {
+  let output = Command::new("git")
+    .args(args)
+    .output()
+    .map_err(|e| eyre!("Failed to run git: {}", e))?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(eyre!("git {}: {}", args.join(" "), stderr.trim()));
+  }
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Name of the checked out branch, if in a git repo and not detached
+pub fn current_branch() -> Option<String> {
+  git(&["symbolic-ref", "--short", "-q", "HEAD"])
+    .ok()
+    .filter(|b| !b.is_empty())
+}
+
+/// Create and check out a branch, or check it out if it already exists.
+/// Returns true if the branch was created.
+pub fn checkout_branch(name: &str) -> Result<bool> {
+  git(&["rev-parse", "--git-dir"]).map_err(|_| eyre!("Not in a git repository"))?;
+  let exists = git(&[
+    "rev-parse",
+    "--verify",
+    "-q",
+    &format!("refs/heads/{}", name),
+  ])
+  .is_ok();
+  if exists {
+    git(&["checkout", "-q", name])?;
+  } else {
+    git(&["checkout", "-q", "-b", name])?;
+  }
+  Ok(!exists)
+}
+
+/// Check out a branch, returning a message for the footer
+pub fn checkout_with_message(name: &str) -> String {
+  match checkout_branch(name) {
+    Ok(true) => format!("Switched to a new branch {}", name),
+    Ok(false) => format!("Switched to branch {}", name),
+    Err(e) => format!("Branch failed: {}", e),
+  }
+}
+
+/// Build a branch name from a template with `{key}`, `{type}`,
+/// `{summary-slug}` and `{project}` placeholders.
+pub fn branch_name(template: &str, key: &str, issue_type: &str, summary: &str) -> String {
+  let project = key.split('-').next().unwrap_or_default();
+  let name = template
+    .replace("{key}", key)
+    .replace("{project}", project)
+    .replace("{type}", &slugify(issue_type, MAX_SLUG_LEN))
+    .replace("{summary-slug}", &slugify(summary, MAX_SLUG_LEN));
+  // Empty placeholders would leave dangling separators
+  name
+    .split('/')
+    .map(|part| part.trim_matches(|c| c == '-' || c == '_'))
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+/// Lowercase ASCII words joined by dashes, cut at a word boundary
+pub fn slugify(s: &str, max_len: usize) -> String {
+  let mut slug = String::new();
+  let words = s
+    .split(|c: char| !c.is_ascii_alphanumeric())
+    .filter(|w| !w.is_empty());
+  for word in words {
+    if !slug.is_empty() && slug.len() + 1 + word.len() > max_len {
+      break;
+    }
+    if !slug.is_empty() {
+      slug.push('-');
+    }
+    slug.push_str(&word.to_ascii_lowercase());
+  }
+  slug.truncate(max_len);
+  slug
+}
+
+/// Find an issue key in a branch name.
+///
+/// Uppercase keys (`feature/ABC-123-x`) are matched for any project.
+/// Lowercase ones (`abc-123-x`) only for `project`, since words like
+/// `fix-2` look like keys too.
+pub fn issue_key_in(branch: &str, project: Option<&str>) -> Option<String> {
+  let candidates = || {
+    branch
+      .split(|c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '_')
+      .flat_map(|part| {
+        let words: Vec<&str> = part.split('-').collect();
+        words
+          .windows(2)
+          .map(|pair| (pair[0], pair[1]))
+          .filter(|(_, number)| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+          .collect::<Vec<_>>()
+      })
+      .filter(|(prefix, _)| {
+        prefix.starts_with(|c: char| c.is_ascii_alphabetic())
+          && prefix
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+      })
+  };
+
+  candidates()
+    .find(|(prefix, _)| !prefix.chars().any(|c| c.is_ascii_lowercase()))
+    .or_else(|| {
+      let project = project?;
+      candidates().find(|(prefix, _)| prefix.eq_ignore_ascii_case(project))
+    })
+    .map(|(prefix, number)| format!("{}-{}", prefix.to_ascii_uppercase(), number))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_branch_name() {
+    assert_eq!(
+      branch_name(
+        DEFAULT_BRANCH_TEMPLATE,
+        "ABC-12",
+        "Bug",
+        "Fix: login (SSO) redirect!"
+      ),
+      "bug/ABC-12-fix-login-sso-redirect"
+    );
+    assert_eq!(
+      branch_name("{type}/{key}-{summary-slug}", "ABC-12", "", "???"),
+      "ABC-12"
+    );
+    assert_eq!(slugify("one two three", 8), "one-two");
+  }
+
+  #[test]
+  fn test_issue_key_in() {
+    assert_eq!(
+      issue_key_in("feature/ABC-123-add-thing", None).as_deref(),
+      Some("ABC-123")
+    );
+    assert_eq!(issue_key_in("PROJ2-7", None).as_deref(), Some("PROJ2-7"));
+    assert_eq!(issue_key_in("bugfix/fix-2-crashes", None), None);
+    assert_eq!(
+      issue_key_in("bugfix/abc-42-crash", Some("ABC")).as_deref(),
+      Some("ABC-42")
+    );
+    assert_eq!(issue_key_in("main", Some("ABC")), None);
+  }
+}
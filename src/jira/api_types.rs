@@ -4,6 +4,7 @@
 //! while keeping domain types focused on application needs.
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Re-serialize a value through JSON to convert between compatible types.
 /// Useful for converting gouqi's BTreeMap fields to our typed structs.
@@ -19,6 +20,20 @@ pub fn reserialize<T: DeserializeOwned>(value: impl Serialize) -> serde_json::Re
 pub struct ApiStatus {
   pub id: String,
   pub name: String,
+  #[serde(rename = "statusCategory")]
+  pub status_category: Option<ApiStatusCategory>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiStatusCategory {
+  pub key: String,
+  #[serde(rename = "colorName")]
+  pub color_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiResolution {
+  pub name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,6 +52,66 @@ pub struct ApiPriority {
   pub name: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ApiComment {
+  pub author: Option<ApiUser>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ApiCommentPage {
+  #[serde(default)]
+  pub comments: Vec<ApiComment>,
+  #[serde(default)]
+  pub total: u32,
+}
+
+// ============================================================================
+// Changelog: expanded on an issue, and its own paginated endpoint
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ApiHistoryItem {
+  pub field: String,
+  pub from: Option<String>,
+  pub to: Option<String>,
+  #[serde(rename = "fromString")]
+  pub from_text: Option<String>,
+  #[serde(rename = "toString")]
+  pub to_text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiHistory {
+  pub id: String,
+  pub author: Option<ApiUser>,
+  #[serde(default)]
+  pub created: String,
+  #[serde(default)]
+  pub items: Vec<ApiHistoryItem>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ApiChangelog {
+  #[serde(default)]
+  pub histories: Vec<ApiHistory>,
+  #[serde(default)]
+  pub total: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiIssueChangelog {
+  #[serde(default)]
+  pub changelog: ApiChangelog,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiChangelogPage {
+  #[serde(default)]
+  pub values: Vec<ApiHistory>,
+  #[serde(rename = "isLast")]
+  pub is_last: Option<bool>,
+}
+
 // ============================================================================
 // Issue fields - used by both search and board issues endpoints
 // ============================================================================
@@ -51,6 +126,7 @@ pub struct ApiIssueFields {
   pub assignee: Option<ApiUser>,
   pub reporter: Option<ApiUser>,
   pub priority: Option<ApiPriority>,
+  pub resolution: Option<ApiResolution>,
   #[serde(default)]
   pub labels: Vec<String>,
   #[serde(default)]
@@ -59,11 +135,43 @@ pub struct ApiIssueFields {
   pub updated: String,
   // Description is complex (can be string or ADF), handled separately
   pub description: Option<serde_json::Value>,
+  // Only requested by the notification watcher
+  pub comment: Option<ApiCommentPage>,
   // Catch-all for custom fields (like epic)
   #[serde(flatten)]
   pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
+impl ApiIssueFields {
+  fn status_category(&self) -> StatusCategory {
+    self
+      .status
+      .as_ref()
+      .and_then(|s| s.status_category.as_ref())
+      .map(|c| StatusCategory::from_key(&c.key))
+      .unwrap_or_default()
+  }
+
+  fn category_color(&self) -> Option<String> {
+    self
+      .status
+      .as_ref()
+      .and_then(|s| s.status_category.as_ref())
+      .and_then(|c| c.color_name.clone())
+  }
+
+  /// Display values of the configured custom fields that are set
+  fn custom_values(&self, custom_fields: &[CustomField]) -> BTreeMap<String, String> {
+    custom_fields
+      .iter()
+      .filter_map(|field| {
+        let value = display_value(self.extra.get(&field.id)?)?;
+        Some((field.name.clone(), value))
+      })
+      .collect()
+  }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ApiIssue {
   pub key: String,
@@ -117,6 +225,32 @@ pub struct ApiBoardConfigResponse {
   pub column_config: Option<ApiColumnConfig>,
 }
 
+// ============================================================================
+// Sprints of a board
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ApiSprint {
+  pub id: u64,
+  #[serde(default)]
+  pub name: String,
+  #[serde(default)]
+  pub state: String,
+  #[serde(rename = "startDate")]
+  pub start_date: Option<String>,
+  #[serde(rename = "endDate")]
+  pub end_date: Option<String>,
+  pub goal: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiSprintsResponse {
+  #[serde(default)]
+  pub values: Vec<ApiSprint>,
+  #[serde(rename = "isLast")]
+  pub is_last: Option<bool>,
+}
+
 // ============================================================================
 // Transitions endpoint response
 // ============================================================================
@@ -124,11 +258,17 @@ pub struct ApiBoardConfigResponse {
 #[derive(Debug, Deserialize)]
 pub struct ApiTransitionTo {
   pub id: String,
+  #[serde(default)]
+  pub name: String,
+  #[serde(rename = "statusCategory")]
+  pub status_category: Option<ApiStatusCategory>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ApiTransition {
   pub id: String,
+  #[serde(default)]
+  pub name: String,
   pub to: ApiTransitionTo,
 }
 
@@ -138,20 +278,93 @@ pub struct ApiTransitionsResponse {
   pub transitions: Vec<ApiTransition>,
 }
 
+// ============================================================================
+// Myself and user search endpoint responses
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ApiMyself {
+  #[serde(rename = "accountId")]
+  pub account_id: Option<String>,
+  pub name: Option<String>,
+  #[serde(rename = "displayName", default)]
+  pub display_name: String,
+}
+
+// ============================================================================
+// Field list and edit metadata endpoint responses
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ApiFieldSchema {
+  #[serde(rename = "type")]
+  pub schema_type: String,
+  pub custom: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiField {
+  pub id: String,
+  pub name: String,
+  #[serde(default)]
+  pub custom: bool,
+  pub schema: Option<ApiFieldSchema>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiAllowedValue {
+  pub id: String,
+  pub value: Option<String>,
+  pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiEditMetaField {
+  #[serde(rename = "allowedValues", default)]
+  pub allowed_values: Vec<ApiAllowedValue>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiEditMeta {
+  #[serde(default)]
+  pub fields: std::collections::HashMap<String, ApiEditMetaField>,
+}
+
 // ============================================================================
 // Conversions to domain types
 // ============================================================================
 
-use super::types::{BoardColumn, BoardConfiguration, Issue, IssueSummary, StatusInfo};
+use super::fields::{display_value, CustomField, FieldInfo, FieldKind, IssueFields};
+use super::types::{
+  BoardColumn, BoardConfiguration, ChangelogEntry, CurrentUser, Issue, IssueSummary, Sprint,
+  StatusCategory, StatusInfo, WatchedIssue,
+};
 
 impl ApiIssue {
   pub fn into_summary(self) -> IssueSummary {
-    self.into_summary_with_epic(None)
+    self.into_summary_with(&IssueFields::default())
   }
 
-  pub fn into_summary_with_epic(self, epic_field: Option<&str>) -> IssueSummary {
+  /// Summary including the epic, story points and custom field values
+  pub fn into_summary_with(self, fields: &IssueFields) -> IssueSummary {
     let f = self.fields;
-    let epic = epic_field.and_then(|field_name| extract_epic_value(f.extra.get(field_name)));
+    let custom = f.custom_values(&fields.custom);
+    let (status_category, category_color) = (f.status_category(), f.category_color());
+    let story_points = fields
+      .story_points
+      .iter()
+      .find_map(|id| f.extra.get(id)?.as_f64());
+    let parent = f
+      .extra
+      .get("parent")
+      .and_then(|p| p.get("key"))
+      .and_then(|k| k.as_str())
+      .map(String::from);
+    let epic = fields
+      .epic
+      .as_deref()
+      .and_then(|field_name| extract_epic_value(f.extra.get(field_name)))
+      .or_else(|| extract_parent_epic(f.extra.get("parent")));
     IssueSummary {
       key: self.key,
       summary: f.summary,
@@ -160,17 +373,26 @@ impl ApiIssue {
         .as_ref()
         .map(|s| s.name.clone())
         .unwrap_or_default(),
+      status_category,
+      category_color,
+      resolution: f.resolution.map(|r| r.name),
       status_id: f.status.map(|s| s.id).unwrap_or_default(),
       issue_type: f.issue_type.map(|t| t.name).unwrap_or_default(),
       assignee: f.assignee.map(|u| u.display_name),
       priority: f.priority.map(|p| p.name),
       epic,
+      parent,
+      story_points,
+      created: f.created,
       updated: f.updated,
+      custom_fields: custom,
     }
   }
 
-  pub fn into_full(self) -> Issue {
+  pub fn into_full(self, custom_fields: &[CustomField]) -> Issue {
     let f = self.fields;
+    let custom = f.custom_values(custom_fields);
+    let (status_category, category_color) = (f.status_category(), f.category_color());
     Issue {
       key: self.key,
       summary: f.summary,
@@ -180,6 +402,9 @@ impl ApiIssue {
         .as_ref()
         .map(|s| s.name.clone())
         .unwrap_or_default(),
+      status_category,
+      category_color,
+      resolution: f.resolution.map(|r| r.name),
       status_id: f.status.map(|s| s.id).unwrap_or_default(),
       issue_type: f.issue_type.map(|t| t.name).unwrap_or_default(),
       assignee: f.assignee.map(|u| u.display_name),
@@ -188,10 +413,117 @@ impl ApiIssue {
       labels: f.labels,
       created: f.created,
       updated: f.updated,
+      custom_fields: custom,
+    }
+  }
+}
+
+impl From<ApiIssue> for WatchedIssue {
+  fn from(issue: ApiIssue) -> Self {
+    let f = issue.fields;
+    let comments = f.comment.unwrap_or_default();
+    WatchedIssue {
+      key: issue.key,
+      summary: f.summary,
+      status: f.status.map(|s| s.name).unwrap_or_default(),
+      assignee: f.assignee.map(|u| u.display_name),
+      comment_count: comments.total.max(comments.comments.len() as u32),
+      last_commenter: comments
+        .comments
+        .last()
+        .and_then(|c| c.author.as_ref())
+        .map(|u| u.display_name.clone()),
+      updated: f.updated,
+    }
+  }
+}
+
+impl ApiHistory {
+  /// One entry per changed field
+  pub fn into_entries(self, issue_key: &str) -> impl Iterator<Item = ChangelogEntry> + '_ {
+    let author = self.author.map(|u| u.display_name);
+    let (id, created) = (self.id, self.created);
+    self
+      .items
+      .into_iter()
+      .enumerate()
+      .map(move |(i, item)| ChangelogEntry {
+        issue_key: issue_key.to_string(),
+        id: format!("{}.{}", id, i),
+        author: author.clone(),
+        created: created.clone(),
+        field: item.field,
+        from: item.from_text,
+        to: item.to_text,
+        from_id: item.from,
+        to_id: item.to,
+      })
+  }
+}
+
+impl From<ApiMyself> for CurrentUser {
+  fn from(user: ApiMyself) -> Self {
+    CurrentUser {
+      account_id: user.account_id,
+      name: user.name,
+      display_name: user.display_name,
+    }
+  }
+}
+
+impl From<ApiField> for FieldInfo {
+  fn from(field: ApiField) -> Self {
+    FieldInfo {
+      id: field.id,
+      name: field.name,
+      custom: field.custom,
+      kind: field
+        .schema
+        .as_ref()
+        .map(|s| FieldKind::from_schema(&s.schema_type))
+        .unwrap_or_default(),
+      custom_type: field.schema.and_then(|s| s.custom),
+    }
+  }
+}
+
+impl From<ApiAllowedValue> for StatusInfo {
+  fn from(value: ApiAllowedValue) -> Self {
+    StatusInfo {
+      name: value
+        .value
+        .or(value.name)
+        .unwrap_or_else(|| value.id.clone()),
+      id: value.id,
+      ..Default::default()
     }
   }
 }
 
+impl From<ApiStatus> for StatusInfo {
+  fn from(status: ApiStatus) -> Self {
+    StatusInfo {
+      id: status.id,
+      name: status.name,
+      category: status
+        .status_category
+        .as_ref()
+        .map(|c| StatusCategory::from_key(&c.key)),
+      category_color: status.status_category.and_then(|c| c.color_name),
+    }
+  }
+}
+
+impl From<ApiTransitionTo> for StatusInfo {
+  fn from(to: ApiTransitionTo) -> Self {
+    StatusInfo::from(ApiStatus {
+      id: to.id,
+      name: to.name,
+      status_category: to.status_category,
+    })
+  }
+}
+
 impl From<ApiColumn> for BoardColumn {
   fn from(col: ApiColumn) -> Self {
     BoardColumn {
@@ -206,6 +538,7 @@ impl From<ApiColumn> for BoardColumn {
             s.name
           },
           id: s.id,
+          ..Default::default()
         })
         .collect(),
     }
@@ -215,6 +548,7 @@ impl From<ApiColumn> for BoardColumn {
 impl From<ApiBoardConfigResponse> for BoardConfiguration {
   fn from(resp: ApiBoardConfigResponse) -> Self {
     BoardConfiguration {
+      board_id: 0,
       columns: resp
         .column_config
         .map(|cc| cc.columns.into_iter().map(BoardColumn::from).collect())
@@ -223,6 +557,19 @@ impl From<ApiBoardConfigResponse> for BoardConfiguration {
   }
 }
 
+impl From<ApiSprint> for Sprint {
+  fn from(sprint: ApiSprint) -> Self {
+    Sprint {
+      id: sprint.id,
+      name: sprint.name,
+      state: sprint.state,
+      start: sprint.start_date,
+      end: sprint.end_date,
+      goal: sprint.goal.filter(|g| !g.is_empty()),
+    }
+  }
+}
+
 // ============================================================================
 // Helpers
 // ============================================================================
@@ -255,6 +602,19 @@ fn extract_epic_value(value: Option<&serde_json::Value>) -> Option<String> {
   None
 }
 
+/// The key of a `parent` that is an epic (team-managed projects), as
+/// opposed to the parent of a subtask
+fn extract_parent_epic(value: Option<&serde_json::Value>) -> Option<String> {
+  let parent = value?;
+  let issue_type = parent.get("fields")?.get("issuetype")?;
+  let is_epic = issue_type.get("hierarchyLevel").and_then(|l| l.as_i64()) == Some(1)
+    || issue_type.get("name").and_then(|n| n.as_str()) == Some("Epic");
+  if !is_epic {
+    return None;
+  }
+  parent.get("key")?.as_str().map(String::from)
+}
+
 /// Extract plain text description from Jira's ADF or plain text format
 fn extract_description(value: &serde_json::Value) -> Option<String> {
   // If it's a string, return it directly (API v2)
@@ -0,0 +1,366 @@
+//! Sprint burndown and burnup from the sprint's issues and their histories.
+
+use super::metrics::{parse_time, IssueFlow};
+use super::types::{BoardColumn, ChangelogEntry, IssueSummary, Sprint};
+use chrono::{DateTime, Utc};
+
+/// What a burndown counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BurnUnit {
+  Issues,
+  Points,
+}
+
+/// An issue that was in the sprint at some point
+#[derive(Debug, Clone)]
+pub struct SprintIssue {
+  pub key: String,
+  /// The current estimate; earlier estimates aren't tracked
+  pub points: f64,
+  pub flow: IssueFlow,
+  /// Whether the issue was in the sprint when it was created and after each
+  /// change of its Sprint field, oldest first
+  pub membership: Vec<(DateTime<Utc>, bool)>,
+}
+
+impl SprintIssue {
+  /// From the issue's history; `in_sprint` is whether it's in the sprint
+  /// now. `None` when the issue has no creation time.
+  pub fn new(
+    issue: &IssueSummary,
+    changelog: &[ChangelogEntry],
+    columns: &[BoardColumn],
+    sprint: &Sprint,
+    in_sprint: bool,
+  ) -> Option<Self> {
+    let flow = IssueFlow::new(issue, changelog, columns)?;
+    let changes: Vec<(DateTime<Utc>, &ChangelogEntry)> = changelog
+      .iter()
+      .filter(|e| e.field == "Sprint")
+      .filter_map(|e| Some((parse_time(&e.created)?, e)))
+      .collect();
+
+    // Without changes it's been in the sprint (or not) since it was created
+    let initial = match changes.first() {
+      Some((_, first)) => lists_sprint(first.from_id.as_deref(), first.from.as_deref(), sprint),
+      None => in_sprint,
+    };
+    let mut membership = vec![(flow.created, initial)];
+    membership.extend(changes.iter().map(|(time, e)| {
+      (
+        *time,
+        lists_sprint(e.to_id.as_deref(), e.to.as_deref(), sprint),
+      )
+    }));
+
+    Some(Self {
+      key: issue.key.clone(),
+      points: issue.story_points.unwrap_or(0.0),
+      flow,
+      membership,
+    })
+  }
+
+  fn in_sprint_at(&self, time: DateTime<Utc>) -> bool {
+    self
+      .membership
+      .iter()
+      .take_while(|(t, _)| *t <= time)
+      .last()
+      .is_some_and(|(_, in_sprint)| *in_sprint)
+  }
+
+  fn weight(&self, unit: BurnUnit) -> f64 {
+    match unit {
+      BurnUnit::Issues => 1.0,
+      BurnUnit::Points => self.points,
+    }
+  }
+}
+
+/// Whether a Sprint field value includes a sprint: by id ("12, 13"), or by
+/// name ("Sprint 1, Sprint 2") for histories cached without ids
+fn lists_sprint(ids: Option<&str>, names: Option<&str>, sprint: &Sprint) -> bool {
+  let list = |value: Option<&str>, item: &str| {
+    value.is_some_and(|v| v.split(',').any(|part| part.trim() == item))
+  };
+  match ids {
+    Some(_) => list(ids, &sprint.id.to_string()),
+    None => list(names, &sprint.name),
+  }
+}
+
+/// An issue added to or removed from the sprint after it started
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeChange {
+  pub key: String,
+  pub added: bool,
+  pub at: DateTime<Utc>,
+  /// Days since the sprint started
+  pub day: f64,
+}
+
+/// Burndown and burnup lines of a sprint, with days since its start as x
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Burndown {
+  /// Length of the sprint in days
+  pub length: f64,
+  /// Scope when the sprint started
+  pub committed: f64,
+  pub remaining: Vec<(f64, f64)>,
+  pub done: Vec<(f64, f64)>,
+  pub scope: Vec<(f64, f64)>,
+  /// Oldest first
+  pub changes: Vec<ScopeChange>,
+}
+
+impl Burndown {
+  /// The sprint from `start` to `end`, as far as `now`. The last board
+  /// column is taken as done.
+  pub fn compute(
+    issues: &[SprintIssue],
+    columns: usize,
+    unit: BurnUnit,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    now: DateTime<Utc>,
+  ) -> Self {
+    let done_column = columns.checked_sub(1);
+    let until = now.min(end).max(start);
+    let day = |time: DateTime<Utc>| (time - start).num_minutes() as f64 / (24.0 * 60.0);
+    // Scope and done at a time
+    let totals = |time: DateTime<Utc>| {
+      issues.iter().filter(|issue| issue.in_sprint_at(time)).fold(
+        (0.0, 0.0),
+        |(scope, done), issue| {
+          let weight = issue.weight(unit);
+          let is_done = done_column.is_some() && issue.flow.column_at(time) == done_column;
+          (scope + weight, if is_done { done + weight } else { done })
+        },
+      )
+    };
+
+    let mut burndown = Self {
+      length: day(end),
+      ..Default::default()
+    };
+    let mut push = |x: f64, (scope, done): (f64, f64)| {
+      burndown.scope.push((x, scope));
+      burndown.done.push((x, done));
+      burndown.remaining.push((x, scope - done));
+    };
+
+    let mut times: Vec<DateTime<Utc>> = issues
+      .iter()
+      .flat_map(|issue| {
+        let moves = issue.flow.moves.iter().map(|(time, _)| *time);
+        moves.chain(issue.membership.iter().map(|(time, _)| *time))
+      })
+      .filter(|time| *time > start && *time <= until)
+      .collect();
+    times.sort();
+    times.dedup();
+
+    let committed = totals(start);
+    push(0.0, committed);
+    let mut last = committed;
+    for time in times {
+      let now = totals(time);
+      if now != last {
+        // A step: the old values up to the change, the new ones after it
+        push(day(time), last);
+        push(day(time), now);
+        last = now;
+      }
+    }
+    push(day(until), last);
+
+    for issue in issues {
+      let mut was_in = false;
+      for (at, in_sprint) in &issue.membership {
+        if *in_sprint != was_in && *at > start && *at <= until {
+          burndown.changes.push(ScopeChange {
+            key: issue.key.clone(),
+            added: *in_sprint,
+            at: *at,
+            day: day(*at),
+          });
+        }
+        was_in = *in_sprint;
+      }
+    }
+    burndown.changes.sort_by_key(|change| change.at);
+    burndown.committed = committed.0;
+    burndown
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::jira::types::{test_issue, StatusInfo};
+
+  /// Ids of statuses and sprints by name
+  const IDS: [(&str, &str); 4] = [
+    ("To Do", "1"),
+    ("Done", "10001"),
+    ("Sprint 1", "7"),
+    ("Sprint 10", "9"),
+  ];
+
+  /// The ids of a comma separated list of names
+  fn ids(names: &str) -> String {
+    names
+      .split(", ")
+      .filter(|name| !name.is_empty())
+      .map(|name| IDS.iter().find(|(n, _)| *n == name).unwrap().1)
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+
+  fn sprint() -> Sprint {
+    Sprint {
+      id: 7,
+      name: "Sprint 1".to_string(),
+      state: "active".to_string(),
+      start: None,
+      end: None,
+      goal: None,
+    }
+  }
+
+  /// Like the board configuration: status ids only
+  fn columns() -> Vec<BoardColumn> {
+    ["To Do", "Done"]
+      .iter()
+      .map(|name| BoardColumn {
+        name: name.to_string(),
+        statuses: vec![StatusInfo {
+          id: ids(name),
+          name: ids(name),
+          ..Default::default()
+        }],
+      })
+      .collect()
+  }
+
+  fn issue(key: &str, status: &str, points: f64) -> IssueSummary {
+    IssueSummary {
+      status: status.to_string(),
+      status_id: ids(status),
+      story_points: Some(points),
+      created: "2024-04-20T00:00:00.000+0000".to_string(),
+      ..test_issue(key)
+    }
+  }
+
+  fn change(day: u32, field: &str, from: &str, to: &str) -> ChangelogEntry {
+    ChangelogEntry {
+      issue_key: String::new(),
+      id: String::new(),
+      author: None,
+      created: format!("2024-05-{:02}T12:00:00.000+0000", day),
+      field: field.to_string(),
+      from: Some(from.to_string()),
+      to: Some(to.to_string()),
+      from_id: Some(ids(from)),
+      to_id: Some(ids(to)),
+    }
+  }
+
+  fn time(s: &str) -> DateTime<Utc> {
+    parse_time(s).unwrap()
+  }
+
+  #[test]
+  fn test_sprint_issue_membership() {
+    let history = [
+      change(1, "Sprint", "", "Sprint 1"),
+      change(3, "Sprint", "Sprint 1", "Sprint 1, Sprint 10"),
+      change(5, "Sprint", "Sprint 1, Sprint 10", "Sprint 10"),
+    ];
+    let issue = SprintIssue::new(
+      &issue("P-1", "To Do", 1.0),
+      &history,
+      &columns(),
+      &sprint(),
+      false,
+    )
+    .unwrap();
+    let membership: Vec<bool> = issue.membership.iter().map(|(_, m)| *m).collect();
+    assert_eq!(membership, [false, true, true, false]);
+    assert!(issue.in_sprint_at(time("2024-05-04T00:00:00Z")));
+    assert!(!issue.in_sprint_at(time("2024-05-06T00:00:00Z")));
+
+    // Histories cached without ids fall back to the sprint names
+    let sprint = sprint();
+    assert!(lists_sprint(Some("9, 7"), Some("Sprint 10"), &sprint));
+    assert!(!lists_sprint(Some("9"), Some("Sprint 1"), &sprint));
+    assert!(lists_sprint(None, Some("Sprint 10, Sprint 1"), &sprint));
+  }
+
+  #[test]
+  fn test_burndown() {
+    let cols = columns();
+    let sprint = sprint();
+    let issues = [
+      // Committed, done on day 2 (by status id, the board has no names)
+      SprintIssue::new(
+        &issue("P-1", "Done", 3.0),
+        &[change(3, "status", "To Do", "Done")],
+        &cols,
+        &sprint,
+        true,
+      ),
+      // Committed, removed on day 3
+      SprintIssue::new(
+        &issue("P-2", "To Do", 2.0),
+        &[change(4, "Sprint", "Sprint 1", "")],
+        &cols,
+        &sprint,
+        false,
+      ),
+      // Added on day 1
+      SprintIssue::new(
+        &issue("P-3", "To Do", 5.0),
+        &[change(2, "Sprint", "", "Sprint 1")],
+        &cols,
+        &sprint,
+        true,
+      ),
+    ]
+    .map(Option::unwrap);
+
+    let start = time("2024-05-01T12:00:00.000+0000");
+    let end = time("2024-05-11T12:00:00.000+0000");
+    let now = time("2024-05-06T12:00:00.000+0000");
+    let points = Burndown::compute(&issues, 2, BurnUnit::Points, start, end, now);
+    assert_eq!(points.length, 10.0);
+    assert_eq!(points.committed, 5.0);
+    assert_eq!(
+      points.remaining,
+      [
+        (0.0, 5.0),
+        (1.0, 5.0),
+        (1.0, 10.0),
+        (2.0, 10.0),
+        (2.0, 7.0),
+        (3.0, 7.0),
+        (3.0, 5.0),
+        (5.0, 5.0)
+      ]
+    );
+    assert_eq!(points.done.last(), Some(&(5.0, 3.0)));
+    assert_eq!(points.scope.last(), Some(&(5.0, 8.0)));
+    let changes: Vec<(&str, bool)> = points
+      .changes
+      .iter()
+      .map(|c| (c.key.as_str(), c.added))
+      .collect();
+    assert_eq!(changes, [("P-3", true), ("P-2", false)]);
+
+    let count = Burndown::compute(&issues, 2, BurnUnit::Issues, start, end, now);
+    assert_eq!(count.committed, 2.0);
+    assert_eq!(count.remaining.last(), Some(&(5.0, 1.0)));
+  }
+}
@@ -1,14 +1,33 @@
-use crate::cache::{CacheLayer, SqliteStorage};
+use crate::cache::{
+  CacheLayer, CacheResult, MutationState, Notification, PendingMutation, SqliteStorage,
+};
 use crate::config::{AuthType, Config};
 use crate::jira::api_types::{
-  reserialize, ApiBoardConfigResponse, ApiBoardIssuesResponse, ApiIssue, ApiIssueFields,
-  ApiTransitionsResponse,
+  reserialize, ApiBoardConfigResponse, ApiBoardIssuesResponse, ApiChangelogPage, ApiEditMeta,
+  ApiField, ApiHistory, ApiIssue, ApiIssueChangelog, ApiIssueFields, ApiMyself, ApiSprintsResponse,
+  ApiStatus, ApiTransitionsResponse,
+};
+use crate::jira::fields::{self, CustomField, FieldInfo, IssueFields};
+use crate::jira::mutations::{Mutation, MutationOutcome, NetworkError, ReplaySummary};
+use crate::jira::notifications::detect_changes;
+use crate::jira::types::{
+  Board, BoardConfiguration, ChangelogEntry, CurrentUser, Issue, IssueSummary, Sprint, StatusInfo,
+  WatchedIssue,
 };
-use crate::jira::types::{Board, BoardConfiguration, Issue, IssueSummary};
-use color_eyre::{eyre::eyre, Result};
+use color_eyre::{eyre::eyre, Report, Result};
 use serde_json::Value;
+use std::collections::HashSet;
+use tracing::warn;
 use url::form_urlencoded;
 
+/// Epics per search when loading the issues of several epics.
+///
+/// The search API takes the JQL in the query string of a GET request, and
+/// `"Epic Link" in (...) OR parent in (...)` lists every key twice, so one
+/// query for a project with hundreds of epics can pass the 8 KB URL limit
+/// of Jira Server and many proxies. 50 epics stay around 1.5 KB.
+const EPIC_BATCH_SIZE: usize = 50;
+
 /// Jira API client with transparent caching support.
 ///
 /// This client provides the Jira API and automatically caches results
@@ -16,24 +35,63 @@ use url::form_urlencoded;
 #[derive(Clone)]
 pub struct JiraClient {
   client: gouqi::r#async::Jira,
+  base_url: String,
+  auth_type: AuthType,
+  /// Epic link field from the config; detected from the field list if unset
   epic_field: Option<String>,
+  /// Story points field from the config; detected from the field list if unset
+  story_points_field: Option<String>,
+  /// Custom fields to load, as named in the config
+  custom_fields: Vec<String>,
   cache: CacheLayer<SqliteStorage>,
 }
 
-fn get_issue_fields(epic_field: Option<&str>) -> Vec<&str> {
+fn get_issue_fields(issue_fields: &IssueFields) -> Vec<&str> {
   let mut fields = vec![
     "summary",
     "status",
     "issuetype",
     "assignee",
     "priority",
+    "resolution",
+    "created",
     "updated",
+    // Epics of team-managed projects
+    "parent",
   ];
-  if let Some(epic_field) = epic_field {
-    fields.push(epic_field);
-  }
+  fields.extend(issue_fields.ids());
   fields
 }
+
+/// Format a cached `updated` timestamp for a JQL `updated > '...'` clause.
+///
+/// JQL only accepts minute precision ("2024-01-15 10:30"), so the filter can
+/// return issues from the same minute again; callers merge by key anyway.
+fn jql_since(updated: &str) -> String {
+  updated.get(..16).unwrap_or(updated).replacen('T', " ", 1)
+}
+
+/// A JQL string literal, with quotes and backslashes escaped
+fn jql_string(value: &str) -> String {
+  format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Wrap a request error, marking transport failures as [`NetworkError`]
+/// so mutations can be queued instead of failing.
+fn request_error(context: &str, e: gouqi::Error) -> Report {
+  match e {
+    gouqi::Error::Http(_) | gouqi::Error::IO(_) => {
+      NetworkError(format!("{}: {}", context, e)).into()
+    }
+    e => eyre!("{}: {}", context, e),
+  }
+}
+
+/// Check if an error means Jira couldn't be reached at all
+fn is_network_error(e: &Report) -> bool {
+  e.downcast_ref::<NetworkError>().is_some()
+}
+
 impl JiraClient {
   /// Resolve auth type based on config and URL
   fn resolve_auth_type(auth_type: AuthType, url: &str) -> AuthType {
@@ -79,7 +137,12 @@ impl JiraClient {
 
   pub fn new(config: &Config, cache: CacheLayer<SqliteStorage>) -> Result<Self> {
     let auth_type = Self::resolve_auth_type(config.jira.auth_type, &config.jira.url);
-    let credentials = Self::get_credentials(auth_type, &config.jira.email)?;
+    let credentials = match Self::get_credentials(auth_type, &config.jira.email) {
+      Ok(credentials) => credentials,
+      // Offline mode never talks to Jira, so missing credentials aren't fatal
+      Err(_) if cache.is_offline() => gouqi::Credentials::Anonymous,
+      Err(e) => return Err(e),
+    };
 
     let http_client = reqwest::Client::builder()
       .tcp_nodelay(true)
@@ -92,31 +155,159 @@ impl JiraClient {
 
     Ok(Self {
       client,
+      base_url: config.jira.url.trim_end_matches('/').to_string(),
+      auth_type,
       epic_field: config.jira.epic_field.clone(),
+      story_points_field: config.jira.story_points_field.clone(),
+      custom_fields: config.jira.custom_fields.clone(),
       cache,
     })
   }
 
+  /// Get the cache layer (for cache management)
+  pub fn cache(&self) -> &CacheLayer<SqliteStorage> {
+    &self.cache
+  }
+
+  /// Web URL of an issue
+  pub fn browse_url(&self, key: &str) -> String {
+    format!("{}/browse/{}", self.base_url, key)
+  }
+
+  /// Web URL of a board.
+  ///
+  /// Cloud boards live under their project; `RapidBoard.jspa` is the
+  /// Server/Data Center page, and Cloud still redirects it for boards
+  /// without a project.
+  pub fn board_url(&self, board: &Board) -> String {
+    match (&self.auth_type, &board.project_key) {
+      (AuthType::Cloud, Some(project)) => format!(
+        "{}/jira/software/projects/{}/boards/{}",
+        self.base_url, project, board.id
+      ),
+      _ => format!(
+        "{}/secure/RapidBoard.jspa?rapidView={}",
+        self.base_url, board.id
+      ),
+    }
+  }
+
+  /// Web URL of a saved filter's results, or of the filter list.
+  ///
+  /// Cloud lists filters under `/jira/filters`; `ManageFilters.jspa` is the
+  /// Server/Data Center page.
+  pub fn filter_url(&self, id: Option<u64>) -> String {
+    match (&self.auth_type, id) {
+      (_, Some(id)) => format!("{}/issues/?filter={}", self.base_url, id),
+      (AuthType::Cloud, None) => format!("{}/jira/filters", self.base_url),
+      (_, None) => format!("{}/secure/ManageFilters.jspa", self.base_url),
+    }
+  }
+
+  /// Check if explicit offline mode is enabled.
+  pub fn is_offline(&self) -> bool {
+    self.cache.is_offline()
+  }
+
+  /// Enable or disable explicit offline mode (cache only, no network access).
+  pub fn set_offline(&self, offline: bool) {
+    self.cache.set_offline(offline);
+  }
+
+  /// Fail early in explicit offline mode, for requests that can't be
+  /// served from the cache; `what` says what needs Jira.
+  fn ensure_online(&self, what: &str) -> Result<()> {
+    if self.is_offline() {
+      return Err(eyre!(
+        "j9s is in offline mode; {} needs Jira (use :offline to reconnect)",
+        what
+      ));
+    }
+    Ok(())
+  }
+
   /// Search for issues using JQL with caching and incremental updates.
-  pub async fn search_issues(&self, jql: &str) -> Result<Vec<IssueSummary>> {
+  pub async fn search_issues(&self, jql: &str) -> Result<CacheResult<Vec<IssueSummary>>> {
     let cache_key = format!("search:{}", jql.trim().to_lowercase());
     let base_jql = jql.to_string();
     let client = self.clone();
 
-    let result = self
+    let keys_jql = jql.to_string();
+    let keys_client = self.clone();
+    let by_keys_client = self.clone();
+
+    self
       .cache
-      .fetch_incremental(&cache_key, move |updated_since| {
-        let effective_jql = if let Some(since) = updated_since {
-          format!("({}) AND updated > '{}'", base_jql, since)
-        } else {
-          base_jql.clone()
-        };
-        let client = client.clone();
-        async move { client.search_issues_raw(&effective_jql).await }
-      })
-      .await?;
+      .fetch_reconciled(
+        &cache_key,
+        move |updated_since| {
+          let effective_jql = if let Some(since) = updated_since {
+            format!("({}) AND updated > '{}'", base_jql, jql_since(since))
+          } else {
+            base_jql.clone()
+          };
+          let client = client.clone();
+          async move { client.search_issues_raw(&effective_jql).await }
+        },
+        move || async move { keys_client.search_issue_keys(&keys_jql).await },
+        move |keys| async move { by_keys_client.get_issues_by_keys(&keys).await },
+      )
+      .await
+  }
 
-    Ok(result.data)
+  /// Get just the keys of issues matching a JQL query (for reconciliation).
+  ///
+  /// Pages explicitly rather than using the search stream, which ends
+  /// silently on errors; a truncated key list would prune valid entries.
+  async fn search_issue_keys(&self, jql: &str) -> Result<Vec<String>> {
+    let search = self.client.search();
+    let mut keys = Vec::new();
+    let mut start_at = 0u64;
+    let mut page_token: Option<String> = None;
+
+    loop {
+      let mut builder = gouqi::SearchOptions::builder();
+      builder
+        .fields(vec!["key"])
+        .max_results(1000)
+        .start_at(start_at);
+      if let Some(token) = &page_token {
+        builder.next_page_token(token);
+      }
+
+      let page = search
+        .list(jql, &builder.build())
+        .await
+        .map_err(|e| eyre!("Failed to search issue keys: {}", e))?;
+
+      let count = page.issues.len() as u64;
+      keys.extend(page.issues.into_iter().map(|issue| issue.key));
+      page_token = page.next_page_token;
+
+      let last_page = match (&page_token, page.is_last_page) {
+        (_, Some(last)) => last,
+        (Some(_), None) => false,
+        (None, None) => start_at + count >= page.total,
+      };
+      if count == 0 || last_page {
+        break;
+      }
+      start_at += count;
+    }
+
+    Ok(keys)
+  }
+
+  /// Fetch issue summaries by key, e.g. ones missing from a cached list.
+  ///
+  /// Moved issues come back under their new key.
+  async fn get_issues_by_keys(&self, keys: &[String]) -> Result<Vec<IssueSummary>> {
+    let mut issues = Vec::with_capacity(keys.len());
+    for chunk in keys.chunks(100) {
+      let jql = format!("key in ({})", chunk.join(","));
+      issues.extend(self.search_issues_raw(&jql).await?);
+    }
+    Ok(issues)
   }
 
   /// Raw search without caching
@@ -124,9 +315,10 @@ impl JiraClient {
     use futures::{StreamExt, TryStreamExt};
 
     let search = self.client.search();
+    let issue_fields = self.issue_fields().await;
 
     let options = gouqi::SearchOptions::builder()
-      .fields(get_issue_fields(self.epic_field.as_deref()))
+      .fields(get_issue_fields(&issue_fields))
       .max_results(100)
       .build();
 
@@ -135,7 +327,6 @@ impl JiraClient {
       .await
       .map_err(|e| eyre!("Failed to search issues: {}", e))?;
 
-    let epic_field = self.epic_field.as_deref();
     let issues: Vec<IssueSummary> = stream
       .map(|issue| {
         let fields: ApiIssueFields = reserialize(&issue.fields)?;
@@ -144,7 +335,7 @@ impl JiraClient {
             key: issue.key,
             fields,
           }
-          .into_summary_with_epic(epic_field),
+          .into_summary_with(&issue_fields),
         )
       })
       .try_collect()
@@ -155,20 +346,18 @@ impl JiraClient {
   }
 
   /// Get a single issue by key with caching.
-  pub async fn get_issue(&self, key: &str) -> Result<Issue> {
+  pub async fn get_issue(&self, key: &str) -> Result<CacheResult<Issue>> {
     let key_owned = key.to_string();
     let client = self.clone();
 
-    let result = self
+    self
       .cache
       .fetch_one(key, move || {
         let key = key_owned.clone();
         let client = client.clone();
         async move { client.get_issue_raw(&key).await }
       })
-      .await?;
-
-    Ok(result.data)
+      .await
   }
 
   /// Raw get issue without caching
@@ -182,32 +371,143 @@ impl JiraClient {
 
     let fields: ApiIssueFields =
       reserialize(&issue.fields).map_err(|e| eyre!("Failed to parse issue {}: {}", key, e))?;
+    let custom_fields = self.custom_fields().await;
 
     Ok(
       ApiIssue {
         key: issue.key,
         fields,
       }
-      .into_full(),
+      .into_full(&custom_fields),
     )
   }
 
+  /// Get an issue's history, oldest first, with caching.
+  ///
+  /// Cached per `updated` timestamp of the issue, so it's only fetched
+  /// again once the issue has changed. The histories cached for earlier
+  /// timestamps are dropped then.
+  pub async fn get_changelog(
+    &self,
+    key: &str,
+    updated: &str,
+  ) -> Result<CacheResult<Vec<ChangelogEntry>>> {
+    let cache_key = format!("changelog:{}:{}", key, updated);
+    let key_owned = key.to_string();
+    let client = self.clone();
+
+    let result = self
+      .cache
+      .fetch_list(&cache_key, move || async move {
+        client.get_changelog_raw(&key_owned).await
+      })
+      .await?;
+    self
+      .cache
+      .storage()
+      .remove_superseded(&format!("changelog:{}:", key), &cache_key)?;
+    Ok(result)
+  }
+
+  /// Raw get changelog without caching
+  async fn get_changelog_raw(&self, key: &str) -> Result<Vec<ChangelogEntry>> {
+    let issue: ApiIssueChangelog = self
+      .client
+      .get(
+        "api",
+        &format!("/issue/{}?fields=created&expand=changelog", key),
+      )
+      .await
+      .map_err(|e| eyre!("Failed to get history of {}: {}", key, e))?;
+
+    // The expanded changelog is cut off on long histories (at 100 on
+    // Cloud); the changelog endpoint has all of it
+    let mut histories = issue.changelog.histories;
+    if (histories.len() as u64) < issue.changelog.total {
+      match self.get_changelog_pages(key).await {
+        Ok(all) => histories = all,
+        Err(e) => warn!("Showing partial history of {}: {}", key, e),
+      }
+    }
+
+    Ok(
+      histories
+        .into_iter()
+        .flat_map(|history| history.into_entries(key))
+        .collect(),
+    )
+  }
+
+  /// Page through the changelog endpoint
+  async fn get_changelog_pages(&self, key: &str) -> Result<Vec<ApiHistory>> {
+    let mut histories = Vec::new();
+    loop {
+      let endpoint = format!(
+        "/issue/{}/changelog?startAt={}&maxResults=100",
+        key,
+        histories.len()
+      );
+      let page: ApiChangelogPage = self
+        .client
+        .get("api", &endpoint)
+        .await
+        .map_err(|e| eyre!("Failed to get history of {}: {}", key, e))?;
+
+      let count = page.values.len();
+      histories.extend(page.values);
+      if count == 0 || page.is_last.unwrap_or(true) {
+        break;
+      }
+    }
+    Ok(histories)
+  }
+
+  /// Get the histories of many issues, a few requests at a time. Issues
+  /// whose history can't be loaded are left out.
+  pub async fn get_changelogs(
+    &self,
+    issues: &[IssueSummary],
+  ) -> Vec<(String, Vec<ChangelogEntry>)> {
+    use futures::StreamExt;
+
+    // Owned keys, so the requests don't borrow from the caller
+    let issues: Vec<(String, String)> = issues
+      .iter()
+      .map(|issue| (issue.key.clone(), issue.updated.clone()))
+      .collect();
+    futures::stream::iter(issues)
+      .map(|(key, updated)| {
+        let client = self.clone();
+        async move {
+          match client.get_changelog(&key, &updated).await {
+            Ok(result) => Some((key, result.data)),
+            Err(e) => {
+              warn!("Skipping history of {}: {}", key, e);
+              None
+            }
+          }
+        }
+      })
+      .buffer_unordered(8)
+      .filter_map(|entries| async move { entries })
+      .collect()
+      .await
+  }
+
   /// Get all boards with caching, optionally filtered by project.
-  pub async fn get_boards(&self, project: Option<&str>) -> Result<Vec<Board>> {
+  pub async fn get_boards(&self, project: Option<&str>) -> Result<CacheResult<Vec<Board>>> {
     let cache_key = format!("boards:{}", project.unwrap_or(""));
     let project_owned = project.map(String::from);
     let client = self.clone();
 
-    let result = self
+    self
       .cache
       .fetch_list(&cache_key, move || {
         let project = project_owned.clone();
         let client = client.clone();
         async move { client.get_boards_raw(project.as_deref()).await }
       })
-      .await?;
-
-    Ok(result.data)
+      .await
   }
 
   /// Raw get boards without caching
@@ -231,6 +531,7 @@ impl JiraClient {
         id: board.id,
         name: board.name,
         board_type: board.type_name,
+        project_key: board.location.and_then(|l| l.project_key),
       })
       .collect()
       .await;
@@ -243,7 +544,7 @@ impl JiraClient {
     &self,
     board_id: u64,
     jql: Option<&str>,
-  ) -> Result<Vec<IssueSummary>> {
+  ) -> Result<CacheResult<Vec<IssueSummary>>> {
     let cache_key = format!(
       "board_issues:{}:{}",
       board_id,
@@ -252,25 +553,38 @@ impl JiraClient {
     let base_jql = jql.map(String::from);
     let client = self.clone();
 
-    let result = self
+    let keys_jql = base_jql.clone();
+    let keys_client = self.clone();
+    let by_keys_client = self.clone();
+
+    self
       .cache
-      .fetch_incremental(&cache_key, move |updated_since| {
-        let effective_jql = match (&base_jql, updated_since) {
-          (Some(base), Some(since)) => Some(format!("({}) AND updated > '{}'", base, since)),
-          (Some(base), None) => Some(base.clone()),
-          (None, Some(since)) => Some(format!("updated > '{}'", since)),
-          (None, None) => None,
-        };
-        let client = client.clone();
-        async move {
-          client
-            .get_board_issues_raw(board_id, effective_jql.as_deref())
+      .fetch_reconciled(
+        &cache_key,
+        move |updated_since| {
+          let effective_jql = match (&base_jql, updated_since) {
+            (Some(base), Some(since)) => {
+              Some(format!("({}) AND updated > '{}'", base, jql_since(since)))
+            }
+            (Some(base), None) => Some(base.clone()),
+            (None, Some(since)) => Some(format!("updated > '{}'", jql_since(since))),
+            (None, None) => None,
+          };
+          let client = client.clone();
+          async move {
+            client
+              .get_board_issues_raw(board_id, effective_jql.as_deref())
+              .await
+          }
+        },
+        move || async move {
+          keys_client
+            .get_board_issue_keys(board_id, keys_jql.as_deref())
             .await
-        }
-      })
-      .await?;
-
-    Ok(result.data)
+        },
+        move |keys| async move { by_keys_client.get_issues_by_keys(&keys).await },
+      )
+      .await
   }
 
   /// Raw get board issues without caching
@@ -279,12 +593,35 @@ impl JiraClient {
     board_id: u64,
     jql: Option<&str>,
   ) -> Result<Vec<IssueSummary>> {
+    let issue_fields = self.issue_fields().await;
+    let fields = get_issue_fields(&issue_fields).join(",");
+
+    let issues = self.get_board_api_issues(board_id, jql, &fields).await?;
+    Ok(
+      issues
+        .into_iter()
+        .map(|issue| issue.into_summary_with(&issue_fields))
+        .collect(),
+    )
+  }
+
+  /// Get just the keys of a board's issues (for reconciliation)
+  async fn get_board_issue_keys(&self, board_id: u64, jql: Option<&str>) -> Result<Vec<String>> {
+    let issues = self.get_board_api_issues(board_id, jql, "key").await?;
+    Ok(issues.into_iter().map(|issue| issue.key).collect())
+  }
+
+  /// Page through a board's issues, requesting the given fields
+  async fn get_board_api_issues(
+    &self,
+    board_id: u64,
+    jql: Option<&str>,
+    fields: &str,
+  ) -> Result<Vec<ApiIssue>> {
     let mut all_issues = Vec::new();
     let mut start_at = 0u64;
     let max_results = 100u64;
 
-    let fields = get_issue_fields(self.epic_field.as_deref()).join(",");
-
     loop {
       let mut endpoint = format!(
         "/board/{}/issue?startAt={}&maxResults={}&fields={}",
@@ -302,15 +639,8 @@ impl JiraClient {
         .await
         .map_err(|e| eyre!("Failed to get board issues: {}", e))?;
 
-      let epic_field = self.epic_field.as_deref();
       let issues_count = response.issues.len() as u64;
-      let issues: Vec<IssueSummary> = response
-        .issues
-        .into_iter()
-        .map(|issue| issue.into_summary_with_epic(epic_field))
-        .collect();
-
-      all_issues.extend(issues);
+      all_issues.extend(response.issues);
 
       // Check if we've fetched all issues
       if start_at + issues_count >= response.total {
@@ -322,21 +652,87 @@ impl JiraClient {
     Ok(all_issues)
   }
 
-  /// Get board configuration (columns)
+  /// Get board configuration (columns) with caching
   pub async fn get_board_configuration(&self, board_id: u64) -> Result<BoardConfiguration> {
-    let endpoint = format!("/board/{}/configuration", board_id);
+    let client = self.clone();
+    let result = self
+      .cache
+      .fetch_one(&board_id.to_string(), move || async move {
+        let endpoint = format!("/board/{}/configuration", board_id);
+        let response: ApiBoardConfigResponse = client
+          .client
+          .get("agile", &endpoint)
+          .await
+          .map_err(|e| eyre!("Failed to get board configuration: {}", e))?;
+        Ok(BoardConfiguration {
+          board_id,
+          ..response.into()
+        })
+      })
+      .await?;
+    Ok(result.data)
+  }
 
-    let response: ApiBoardConfigResponse = self
-      .client
-      .get("agile", &endpoint)
-      .await
-      .map_err(|e| eyre!("Failed to get board configuration: {}", e))?;
+  /// Get the active sprints of a scrum board (there can be parallel ones)
+  /// with caching
+  pub async fn get_active_sprints(&self, board_id: u64) -> Result<Vec<Sprint>> {
+    let cache_key = format!("active_sprints:{}", board_id);
+    let client = self.clone();
+    let result = self
+      .cache
+      .fetch_list(&cache_key, move || async move {
+        client.get_active_sprints_raw(board_id).await
+      })
+      .await?;
+    Ok(result.data)
+  }
+
+  /// Raw get active sprints without caching
+  async fn get_active_sprints_raw(&self, board_id: u64) -> Result<Vec<Sprint>> {
+    let mut sprints = Vec::new();
+    loop {
+      let endpoint = format!(
+        "/board/{}/sprint?state=active&startAt={}",
+        board_id,
+        sprints.len()
+      );
+      let page: ApiSprintsResponse = self
+        .client
+        .get("agile", &endpoint)
+        .await
+        .map_err(|e| eyre!("Failed to get sprints: {}", e))?;
 
-    Ok(response.into())
+      let count = page.values.len();
+      sprints.extend(page.values.into_iter().map(Sprint::from));
+      if count == 0 || page.is_last.unwrap_or(true) {
+        break;
+      }
+    }
+    Ok(sprints)
+  }
+
+  /// Get the issues in a sprint
+  pub async fn get_sprint_issues(&self, sprint_id: u64) -> Result<CacheResult<Vec<IssueSummary>>> {
+    self.search_issues(&format!("sprint = {}", sprint_id)).await
+  }
+
+  /// Get the issues removed from a sprint after it started. JQL only finds
+  /// them by board and sprint name.
+  pub async fn get_removed_sprint_issues(
+    &self,
+    board_name: &str,
+    sprint_name: &str,
+  ) -> Result<CacheResult<Vec<IssueSummary>>> {
+    let jql = format!(
+      "issue in removedAfterSprintStart({}, {})",
+      jql_string(board_name),
+      jql_string(sprint_name)
+    );
+    self.search_issues(&jql).await
   }
 
   /// Get epics for a project
-  pub async fn get_epics(&self, project: &str) -> Result<Vec<IssueSummary>> {
+  pub async fn get_epics(&self, project: &str) -> Result<CacheResult<Vec<IssueSummary>>> {
     let jql = format!(
       "project = {} AND issuetype = Epic ORDER BY updated DESC",
       project
@@ -345,15 +741,534 @@ impl JiraClient {
   }
 
   /// Get issues that belong to an epic
-  pub async fn get_epic_issues(&self, epic_key: &str) -> Result<Vec<IssueSummary>> {
-    // Use the Epic Link field if configured, otherwise try "Epic Link"
-    let epic_field = self.epic_field.as_deref().unwrap_or("Epic Link");
-    let jql = format!("\"{}\" = {} ORDER BY updated DESC", epic_field, epic_key);
+  pub async fn get_epic_issues(&self, epic_key: &str) -> Result<CacheResult<Vec<IssueSummary>>> {
+    self.get_epics_issues(&[epic_key]).await
+  }
+
+  /// Get the issues of several epics, e.g. to roll up their progress.
+  ///
+  /// This is one `"Epic Link" in (...)` search for up to [`EPIC_BATCH_SIZE`]
+  /// epics, and one per batch beyond that; the issues are ordered by update
+  /// within each batch only.
+  pub async fn get_epics_issues(
+    &self,
+    epic_keys: &[&str],
+  ) -> Result<CacheResult<Vec<IssueSummary>>> {
+    // Team-managed projects, which link epics through `parent`, are Cloud only
+    let epic_field = self.issue_fields().await.epic;
+    let parent = matches!(self.auth_type, AuthType::Cloud);
+
+    // Sorted, so reordering the epics doesn't change the searches
+    let mut keys = epic_keys.to_vec();
+    keys.sort_unstable();
+    keys.dedup();
+    let mut result = CacheResult::from_network(Vec::new());
+    for batch in keys.chunks(EPIC_BATCH_SIZE) {
+      let jql = fields::epic_issues_jql(epic_field.as_deref(), parent, batch);
+      let mut issues = self.search_issues(&jql).await?;
+      // Report the oldest batch, so cached data isn't shown as fresh
+      if let Some(cached_at) = issues.cached_at {
+        if result.cached_at.is_none_or(|t| cached_at < t) {
+          result.source = issues.source;
+          result.cached_at = Some(cached_at);
+        }
+      }
+      result.data.append(&mut issues.data);
+    }
+    Ok(result)
+  }
+
+  /// Get the issues directly under an issue: the issues of an epic, or
+  /// those with it as `parent` (the subtasks of an issue, the epics of an
+  /// initiative)
+  pub async fn get_child_issues(
+    &self,
+    issue: &IssueSummary,
+  ) -> Result<CacheResult<Vec<IssueSummary>>> {
+    if issue.issue_type.eq_ignore_ascii_case("Epic") {
+      return self.get_epic_issues(&issue.key).await;
+    }
+    let jql = format!("parent = {} ORDER BY updated DESC", issue.key);
+    self.search_issues(&jql).await
+  }
+
+  /// Get issues by key with caching
+  pub async fn get_issues(&self, keys: &[&str]) -> Result<CacheResult<Vec<IssueSummary>>> {
+    let jql = format!("key in ({}) ORDER BY updated DESC", keys.join(", "));
     self.search_issues(&jql).await
   }
 
-  /// Update issue status by finding and executing the appropriate transition
-  pub async fn update_issue_status(&self, issue_key: &str, status_id: &str) -> Result<()> {
+  /// Get Jira's field list with caching.
+  pub async fn get_fields(&self) -> Result<CacheResult<Vec<FieldInfo>>> {
+    let client = self.clone();
+
+    self
+      .cache
+      .fetch_list("fields", move || async move {
+        let fields: Vec<ApiField> = client
+          .client
+          .get("api", "/field")
+          .await
+          .map_err(|e| eyre!("Failed to get fields: {}", e))?;
+        Ok(fields.into_iter().map(FieldInfo::from).collect())
+      })
+      .await
+  }
+
+  /// Get Jira's status list with caching.
+  pub async fn get_statuses(&self) -> Result<CacheResult<Vec<StatusInfo>>> {
+    let client = self.clone();
+
+    self
+      .cache
+      .fetch_list("statuses", move || async move {
+        let statuses: Vec<ApiStatus> = client
+          .client
+          .get("api", "/status")
+          .await
+          .map_err(|e| eyre!("Failed to get statuses: {}", e))?;
+        Ok(statuses.into_iter().map(StatusInfo::from).collect())
+      })
+      .await
+  }
+
+  /// Look up a status in the (cached) status list, for its category
+  async fn find_status(&self, status_id: &str) -> Option<StatusInfo> {
+    match self.get_statuses().await {
+      Ok(statuses) => statuses.data.into_iter().find(|s| s.id == status_id),
+      Err(e) => {
+        warn!("Status list unavailable: {}", e);
+        None
+      }
+    }
+  }
+
+  /// The fields loaded with issue summaries: those from the config, with
+  /// the epic link and story points fields found in the (cached) field list
+  /// when not configured.
+  ///
+  /// Issues still load without them when the field list is unavailable.
+  pub async fn issue_fields(&self) -> IssueFields {
+    let mut issue_fields = IssueFields {
+      epic: self.epic_field.clone(),
+      story_points: self.story_points_field.iter().cloned().collect(),
+      custom: Vec::new(),
+    };
+    let detect = issue_fields.epic.is_none() || issue_fields.story_points.is_empty();
+    if !detect && self.custom_fields.is_empty() {
+      return issue_fields;
+    }
+    let list = match self.get_fields().await {
+      Ok(result) => result.data,
+      Err(e) => {
+        warn!("Field list unavailable: {}", e);
+        return issue_fields;
+      }
+    };
+    if issue_fields.epic.is_none() {
+      issue_fields.epic = fields::epic_link_field(&list).map(|f| f.id.clone());
+    }
+    if issue_fields.story_points.is_empty() {
+      issue_fields.story_points = fields::story_points_fields(&list)
+        .into_iter()
+        .map(|f| f.id.clone())
+        .collect();
+    }
+    issue_fields.custom = fields::resolve(&self.custom_fields, &list);
+    issue_fields
+  }
+
+  /// The custom fields from the config, matched to field ids
+  pub async fn custom_fields(&self) -> Vec<CustomField> {
+    self.issue_fields().await.custom
+  }
+
+  /// The values a select field accepts on an issue, from its edit metadata
+  pub async fn field_options(&self, issue_key: &str, field_id: &str) -> Result<Vec<StatusInfo>> {
+    self.ensure_online("looking up the values of a field")?;
+    let meta: ApiEditMeta = self
+      .client
+      .get("api", &format!("/issue/{}/editmeta", issue_key))
+      .await
+      .map_err(|e| eyre!("Failed to get edit metadata for {}: {}", issue_key, e))?;
+    let options = meta
+      .fields
+      .into_iter()
+      .find(|(id, _)| id == field_id)
+      .map(|(_, field)| field.allowed_values)
+      .unwrap_or_default();
+    if options.is_empty() {
+      return Err(eyre!("{} can't be edited on {}", field_id, issue_key));
+    }
+    Ok(options.into_iter().map(StatusInfo::from).collect())
+  }
+
+  /// Get the authenticated user with caching (so it's available offline).
+  pub async fn get_myself(&self) -> Result<CurrentUser> {
+    let client = self.clone();
+
+    let result = self
+      .cache
+      .fetch_one("myself", move || {
+        let client = client.clone();
+        async move {
+          let user: ApiMyself = client
+            .client
+            .get("api", "/myself")
+            .await
+            .map_err(|e| eyre!("Failed to get current user: {}", e))?;
+          Ok(CurrentUser::from(user))
+        }
+      })
+      .await?;
+
+    Ok(result.data)
+  }
+
+  /// Move an issue to a status by finding and executing the appropriate transition
+  pub async fn update_issue_status(
+    &self,
+    issue_key: &str,
+    status: &StatusInfo,
+  ) -> Result<MutationOutcome> {
+    let known = match status.category {
+      Some(_) => Some(status.clone()),
+      // Board configurations only list status ids
+      None => self.find_status(&status.id).await,
+    };
+    let (category, category_color) = known
+      .map(|s| (s.category, s.category_color))
+      .unwrap_or_default();
+    let mutation = Mutation::Transition {
+      status_id: status.id.clone(),
+      status_name: status.name.clone(),
+      category,
+      category_color,
+    };
+    self.mutate(issue_key, mutation).await
+  }
+
+  /// Assign an issue to the authenticated user
+  pub async fn assign_to_me(&self, issue_key: &str) -> Result<MutationOutcome> {
+    let me = self.get_myself().await?;
+    let mutation = Mutation::Assign {
+      account_id: me.account_id,
+      name: me.name,
+      display_name: Some(me.display_name),
+    };
+    self.mutate(issue_key, mutation).await
+  }
+
+  /// Move an issue to a status given by name, e.g. "In Review".
+  ///
+  /// Matches the target status or the transition name, ignoring case.
+  /// Needs the network to look up the issue's transitions.
+  pub async fn transition_to(&self, issue_key: &str, name: &str) -> Result<MutationOutcome> {
+    if self.is_offline() {
+      return Err(eyre!("Transitions can't be looked up offline"));
+    }
+
+    let mut response: ApiTransitionsResponse = self
+      .client
+      .get("api", &format!("/issue/{}/transitions", issue_key))
+      .await
+      .map_err(|e| request_error("Failed to get transitions", e))?;
+
+    let index = response
+      .transitions
+      .iter()
+      .position(|t| t.to.name.eq_ignore_ascii_case(name) || t.name.eq_ignore_ascii_case(name))
+      .ok_or_else(|| {
+        let available: Vec<&str> = response
+          .transitions
+          .iter()
+          .map(|t| t.to.name.as_str())
+          .collect();
+        eyre!(
+          "No transition to '{}' for {} (available: {})",
+          name,
+          issue_key,
+          available.join(", ")
+        )
+      })?;
+
+    let status = StatusInfo::from(response.transitions.swap_remove(index).to);
+    self.update_issue_status(issue_key, &status).await
+  }
+
+  /// Assign an issue to a user by name or email; "me" assigns to the
+  /// authenticated user and "none" unassigns
+  pub async fn assign(&self, issue_key: &str, user: &str) -> Result<MutationOutcome> {
+    let mutation = match user {
+      "me" => return self.assign_to_me(issue_key).await,
+      "none" => Mutation::Assign {
+        account_id: None,
+        name: None,
+        display_name: None,
+      },
+      query => {
+        let user = self.find_user(query).await?;
+        Mutation::Assign {
+          account_id: user.account_id,
+          name: user.name,
+          display_name: Some(user.display_name),
+        }
+      }
+    };
+    self.mutate(issue_key, mutation).await
+  }
+
+  /// Look up a single user by name or email
+  async fn find_user(&self, query: &str) -> Result<ApiMyself> {
+    let param = if self.auth_type == AuthType::Cloud {
+      "query"
+    } else {
+      "username"
+    };
+    let encoded: String = form_urlencoded::byte_serialize(query.as_bytes()).collect();
+    let mut users: Vec<ApiMyself> = self
+      .client
+      .get("api", &format!("/user/search?{}={}", param, encoded))
+      .await
+      .map_err(|e| request_error("Failed to search users", e))?;
+
+    let exact = users.iter().position(|u| {
+      u.display_name.eq_ignore_ascii_case(query)
+        || u
+          .name
+          .as_deref()
+          .is_some_and(|n| n.eq_ignore_ascii_case(query))
+    });
+    if let Some(i) = exact {
+      return Ok(users.swap_remove(i));
+    }
+    match users.len() {
+      0 => Err(eyre!("No user matching '{}'", query)),
+      1 => Ok(users.remove(0)),
+      _ => {
+        let names: Vec<&str> = users.iter().map(|u| u.display_name.as_str()).collect();
+        Err(eyre!(
+          "'{}' matches several users: {}",
+          query,
+          names.join(", ")
+        ))
+      }
+    }
+  }
+
+  /// Add a plain-text comment to an issue
+  pub async fn add_comment(&self, issue_key: &str, body: &str) -> Result<MutationOutcome> {
+    let mutation = Mutation::Comment {
+      body: body.to_string(),
+    };
+    self.mutate(issue_key, mutation).await
+  }
+
+  /// Set a user field to a user found by name or email; "me" is the
+  /// authenticated user and "none" clears the field
+  pub async fn set_user_field(
+    &self,
+    issue_key: &str,
+    field_id: &str,
+    user: &str,
+  ) -> Result<MutationOutcome> {
+    let (account_id, name) = match user {
+      "none" => return self.edit_field(issue_key, field_id, Value::Null).await,
+      "me" => {
+        let me = self.get_myself().await?;
+        (me.account_id, me.name)
+      }
+      query => {
+        let user = self.find_user(query).await?;
+        (user.account_id, user.name)
+      }
+    };
+    let value = match (account_id, name) {
+      (Some(id), _) => serde_json::json!({ "accountId": id }),
+      (None, name) => serde_json::json!({ "name": name }),
+    };
+    self.edit_field(issue_key, field_id, value).await
+  }
+
+  /// Set a single issue field
+  pub async fn edit_field(
+    &self,
+    issue_key: &str,
+    field: &str,
+    value: Value,
+  ) -> Result<MutationOutcome> {
+    let mutation = Mutation::EditField {
+      field: field.to_string(),
+      value,
+    };
+    self.mutate(issue_key, mutation).await
+  }
+
+  /// Rank an issue right above (`before`) or below another one, changing
+  /// its place on boards and in the backlog
+  pub async fn rank_issue(
+    &self,
+    issue_key: &str,
+    other: &str,
+    before: bool,
+  ) -> Result<MutationOutcome> {
+    let mutation = Mutation::Rank {
+      other: other.to_string(),
+      before,
+    };
+    self.mutate(issue_key, mutation).await
+  }
+
+  /// Apply a mutation, queueing it when offline or when Jira is unreachable.
+  ///
+  /// Either way the cached issue is updated so views reflect the change.
+  async fn mutate(&self, issue_key: &str, mutation: Mutation) -> Result<MutationOutcome> {
+    if !self.is_offline() {
+      match self.apply_mutation(issue_key, &mutation).await {
+        Ok(()) => {
+          self.apply_optimistic(issue_key, &mutation)?;
+          if !mutation.updates_cache() {
+            // Reload the issue to show the new value
+            self.cache.storage().invalidate_entity(issue_key)?;
+          }
+          return Ok(MutationOutcome::Applied);
+        }
+        Err(e) if is_network_error(&e) => {
+          warn!(
+            "Jira unreachable, queueing {} for {}: {}",
+            mutation.kind(),
+            issue_key,
+            e
+          );
+        }
+        Err(e) => return Err(e),
+      }
+    }
+
+    let base_updated = self.cached_updated(issue_key)?;
+    self.cache.storage().enqueue_mutation(
+      issue_key,
+      mutation.kind(),
+      &mutation,
+      base_updated.as_deref(),
+    )?;
+    self.apply_optimistic(issue_key, &mutation)?;
+    Ok(MutationOutcome::Queued)
+  }
+
+  /// Update cached copies of an issue to reflect a mutation
+  fn apply_optimistic(&self, issue_key: &str, mutation: &Mutation) -> Result<()> {
+    self
+      .cache
+      .update_entity::<IssueSummary, _>(issue_key, |issue| mutation.apply_to_summary(issue))?;
+    self
+      .cache
+      .update_entity::<Issue, _>(issue_key, |issue| mutation.apply_to_issue(issue))?;
+    if let Mutation::Rank { other, before } = mutation {
+      // Keep cached board and backlog lists in rank order
+      self
+        .cache
+        .storage()
+        .move_entity(issue_key, other, *before)?;
+    }
+    Ok(())
+  }
+
+  /// The issue's `updated` timestamp as last seen in the cache
+  fn cached_updated(&self, issue_key: &str) -> Result<Option<String>> {
+    if let Some(issue) = self.cache.get_entity::<IssueSummary>(issue_key)? {
+      return Ok(Some(issue.updated));
+    }
+    Ok(
+      self
+        .cache
+        .get_entity::<Issue>(issue_key)?
+        .map(|i| i.updated),
+    )
+  }
+
+  /// Send a mutation to Jira
+  async fn apply_mutation(&self, issue_key: &str, mutation: &Mutation) -> Result<()> {
+    match mutation {
+      Mutation::Transition { status_id, .. } => self.transition_issue(issue_key, status_id).await,
+      Mutation::Assign {
+        account_id, name, ..
+      } => {
+        let body = match (account_id, name) {
+          (Some(id), _) => serde_json::json!({ "accountId": id }),
+          (None, Some(name)) => serde_json::json!({ "name": name }),
+          (None, None) if self.auth_type == AuthType::Cloud => {
+            serde_json::json!({ "accountId": null })
+          }
+          (None, None) => serde_json::json!({ "name": null }),
+        };
+        self
+          .client
+          .put::<Value, _>("api", &format!("/issue/{}/assignee", issue_key), body)
+          .await
+          .map_err(|e| request_error("Failed to assign issue", e))?;
+        Ok(())
+      }
+      Mutation::Comment { body } => {
+        self
+          .client
+          .post::<Value, _>(
+            "api",
+            &format!("/issue/{}/comment", issue_key),
+            serde_json::json!({ "body": body }),
+          )
+          .await
+          .map_err(|e| request_error("Failed to add comment", e))?;
+        Ok(())
+      }
+      Mutation::EditField { field, value } => {
+        let mut fields = serde_json::Map::new();
+        fields.insert(field.clone(), value.clone());
+        self
+          .client
+          .put::<Value, _>(
+            "api",
+            &format!("/issue/{}", issue_key),
+            serde_json::json!({ "fields": fields }),
+          )
+          .await
+          .map_err(|e| request_error("Failed to update issue", e))?;
+        Ok(())
+      }
+      Mutation::Rank { other, before } => {
+        let mut body = serde_json::Map::new();
+        body.insert("issues".to_string(), serde_json::json!([issue_key]));
+        let anchor = if *before {
+          "rankBeforeIssue"
+        } else {
+          "rankAfterIssue"
+        };
+        body.insert(anchor.to_string(), Value::String(other.clone()));
+        let response = self
+          .client
+          .put::<Value, _>("agile", "/issue/rank", Value::Object(body))
+          .await
+          .map_err(|e| request_error("Failed to rank issue", e))?;
+
+        // A partial success (207) lists the errors per issue
+        let errors: Vec<&str> = response["entries"]
+          .as_array()
+          .into_iter()
+          .flatten()
+          .filter_map(|entry| entry["errors"].as_array())
+          .flatten()
+          .filter_map(Value::as_str)
+          .collect();
+        if !errors.is_empty() {
+          return Err(eyre!("Failed to rank issue: {}", errors.join(", ")));
+        }
+        Ok(())
+      }
+    }
+  }
+
+  /// Execute the transition leading to the target status
+  async fn transition_issue(&self, issue_key: &str, status_id: &str) -> Result<()> {
     // Get available transitions
     let endpoint = format!("/issue/{}/transitions", issue_key);
 
@@ -361,7 +1276,7 @@ impl JiraClient {
       .client
       .get("api", &endpoint)
       .await
-      .map_err(|e| eyre!("Failed to get transitions: {}", e))?;
+      .map_err(|e| request_error("Failed to get transitions", e))?;
 
     // Find transition that leads to target status
     let transition_id = response
@@ -382,8 +1297,212 @@ impl JiraClient {
       .client
       .post::<Value, _>("api", &endpoint, body)
       .await
-      .map_err(|e| eyre!("Failed to execute transition: {}", e))?;
+      .map_err(|e| request_error("Failed to execute transition", e))?;
 
     Ok(())
   }
+
+  /// Fetch only the issue's current `updated` timestamp
+  async fn get_issue_updated(&self, issue_key: &str) -> Result<String> {
+    let issue: ApiIssue = self
+      .client
+      .get("api", &format!("/issue/{}?fields=updated", issue_key))
+      .await
+      .map_err(|e| request_error("Failed to get issue", e))?;
+    Ok(issue.fields.updated)
+  }
+
+  /// Check the watch query for changes and record them as notifications.
+  ///
+  /// Only issues updated since the last check are fetched; each is compared
+  /// with its cached copy. Returns the notifications that were added.
+  pub async fn check_watched(&self, jql: &str) -> Result<Vec<(String, String)>> {
+    let cache_key = format!("watch:{}", jql);
+    let base_jql = jql.to_string();
+    let client = self.clone();
+
+    let changes = self
+      .cache
+      .fetch_changes(&cache_key, move |updated_since| {
+        let jql = match updated_since {
+          Some(since) => format!("({}) AND updated > '{}'", base_jql, jql_since(since)),
+          None => base_jql,
+        };
+        async move { client.search_watched_raw(&jql).await }
+      })
+      .await?;
+    if changes.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let me = self.get_myself().await?.display_name;
+    let storage = self.cache.storage();
+    let mut added = Vec::new();
+    for (previous, current) in &changes {
+      for (kind, change) in detect_changes(previous.as_ref(), current, &me) {
+        let message = format!("{} · {}", change, current.summary);
+        storage.add_notification(&current.key, kind.as_str(), &message)?;
+        added.push((current.key.clone(), message));
+      }
+    }
+    Ok(added)
+  }
+
+  /// Raw search for the notification watcher
+  async fn search_watched_raw(&self, jql: &str) -> Result<Vec<WatchedIssue>> {
+    use futures::{StreamExt, TryStreamExt};
+
+    let options = gouqi::SearchOptions::builder()
+      .fields(vec!["summary", "status", "assignee", "comment", "updated"])
+      .max_results(100)
+      .build();
+
+    let search = self.client.search();
+    let stream = search
+      .stream(jql, &options)
+      .await
+      .map_err(|e| request_error("Failed to search watched issues", e))?;
+
+    stream
+      .map(|issue| {
+        let fields: ApiIssueFields = reserialize(&issue.fields)?;
+        Ok(WatchedIssue::from(ApiIssue {
+          key: issue.key,
+          fields,
+        }))
+      })
+      .try_collect()
+      .await
+      .map_err(|e: serde_json::Error| eyre!("Failed to parse issue: {}", e))
+  }
+
+  /// Get notifications, newest first
+  pub fn notifications(&self) -> Result<Vec<Notification>> {
+    self.cache.storage().notifications()
+  }
+
+  /// Count notifications not yet seen in the notification center
+  pub fn unread_notification_count(&self) -> Result<usize> {
+    self.cache.storage().unread_notification_count()
+  }
+
+  /// Mark all notifications as seen
+  pub fn mark_notifications_read(&self) -> Result<()> {
+    self.cache.storage().mark_notifications_read()
+  }
+
+  /// Delete all notifications
+  pub fn clear_notifications(&self) -> Result<()> {
+    self.cache.storage().clear_notifications()
+  }
+
+  /// Get previous palette commands, oldest first
+  pub fn command_history(&self) -> Result<Vec<String>> {
+    self.cache.storage().command_history()
+  }
+
+  /// Remember a palette command
+  pub fn add_command_history(&self, command: &str) -> Result<()> {
+    self.cache.storage().add_command_history(command)
+  }
+
+  /// Get every cached board, for command completion
+  pub fn cached_boards(&self) -> Result<Vec<Board>> {
+    self.cache.storage().all_entities()
+  }
+
+  /// Get every cached issue summary, for command completion
+  pub fn cached_issues(&self) -> Result<Vec<IssueSummary>> {
+    self.cache.storage().all_entities()
+  }
+
+  /// Get queued offline mutations in replay order
+  pub fn pending_mutations(&self) -> Result<Vec<PendingMutation<Mutation>>> {
+    self.cache.storage().pending_mutations()
+  }
+
+  /// Count mutations waiting to be replayed
+  pub fn pending_mutation_count(&self) -> Result<usize> {
+    self.cache.storage().pending_mutation_count()
+  }
+
+  /// Mark a conflicted or failed mutation for another replay attempt
+  pub fn retry_mutation(&self, id: i64) -> Result<()> {
+    self.cache.storage().retry_mutation(id)
+  }
+
+  /// Drop a queued mutation and its optimistic changes
+  pub fn discard_mutation(&self, pending: &PendingMutation<Mutation>) -> Result<()> {
+    let storage = self.cache.storage();
+    storage.remove_mutation(pending.id)?;
+    // Cached copies still carry the optimistic change; refetch them
+    storage.invalidate_entity(&pending.entity_key)
+  }
+
+  /// Replay queued mutations in order.
+  ///
+  /// A mutation conflicts when the issue was updated on the server since it
+  /// was queued. Once a mutation for an issue conflicts or fails, later ones
+  /// for the same issue are held back to preserve ordering. Replay stops at
+  /// the first network error.
+  pub async fn replay_mutations(&self) -> Result<ReplaySummary> {
+    let storage = self.cache.storage();
+    let mut summary = ReplaySummary::default();
+    // Issues with an earlier unresolved mutation
+    let mut blocked: HashSet<String> = HashSet::new();
+
+    for pending in self.pending_mutations()? {
+      let key = pending.entity_key.as_str();
+      if pending.state != MutationState::Pending {
+        blocked.insert(key.to_string());
+        continue;
+      }
+      if blocked.contains(key) {
+        continue;
+      }
+
+      if let Some(base_updated) = &pending.base_updated {
+        match self.get_issue_updated(key).await {
+          Ok(current) if current != *base_updated => {
+            storage.set_mutation_state(
+              pending.id,
+              MutationState::Conflict,
+              Some(&format!("Issue changed on the server at {}", current)),
+            )?;
+            summary.conflicts += 1;
+            blocked.insert(key.to_string());
+            continue;
+          }
+          Ok(_) => {}
+          Err(e) if is_network_error(&e) => break,
+          Err(e) => {
+            storage.set_mutation_state(pending.id, MutationState::Failed, Some(&e.to_string()))?;
+            summary.failed += 1;
+            blocked.insert(key.to_string());
+            continue;
+          }
+        }
+      }
+
+      match self.apply_mutation(key, &pending.mutation).await {
+        Ok(()) => {
+          storage.remove_mutation(pending.id)?;
+          storage.invalidate_entity(key)?;
+          summary.applied += 1;
+          if let Ok(updated) = self.get_issue_updated(key).await {
+            storage.rebase_mutations(key, &updated)?;
+          }
+        }
+        Err(e) if is_network_error(&e) => break,
+        Err(e) => {
+          storage.set_mutation_state(pending.id, MutationState::Failed, Some(&e.to_string()))?;
+          summary.failed += 1;
+          blocked.insert(key.to_string());
+        }
+      }
+    }
+
+    summary.remaining = storage.pending_mutation_count()?;
+    Ok(summary)
+  }
 }
@@ -0,0 +1,346 @@
+//! Field metadata and the custom fields configured for display and editing.
+
+use crate::cache::Cacheable;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+/// What kind of value a field holds, from its schema type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FieldKind {
+  Text,
+  Number,
+  Date,
+  User,
+  /// A single choice from the field's allowed values
+  Select,
+  /// Anything j9s can show but not edit (lists, date-times, ...)
+  #[default]
+  Other,
+}
+
+impl FieldKind {
+  /// From the `schema.type` in Jira's field list
+  pub fn from_schema(schema_type: &str) -> Self {
+    match schema_type {
+      "string" => FieldKind::Text,
+      "number" => FieldKind::Number,
+      "date" => FieldKind::Date,
+      "user" => FieldKind::User,
+      "option" => FieldKind::Select,
+      _ => FieldKind::Other,
+    }
+  }
+}
+
+/// Schema type of Jira Software's Epic Link field
+const EPIC_LINK_TYPE: &str = "com.pyxis.greenhopper.jira:gh-epic-link";
+/// Schema type of the story points field of team-managed projects
+const STORY_POINTS_TYPE: &str = "com.pyxis.greenhopper.jira:jsw-story-points";
+/// Names of the story points fields of company- and team-managed projects
+const STORY_POINTS_NAMES: [&str; 2] = ["Story Points", "Story point estimate"];
+
+/// A field from Jira's field list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldInfo {
+  pub id: String,
+  pub name: String,
+  pub custom: bool,
+  pub kind: FieldKind,
+  /// The plugin type of a custom field (`schema.custom`)
+  #[serde(default)]
+  pub custom_type: Option<String>,
+}
+
+impl Cacheable for FieldInfo {
+  fn cache_key(&self) -> String {
+    self.id.clone()
+  }
+
+  fn updated_at(&self) -> Option<&str> {
+    None
+  }
+
+  fn entity_type() -> &'static str {
+    "field"
+  }
+}
+
+/// A custom field listed in the config, matched against Jira's field list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomField {
+  /// The name as written in the config; issue values are stored under it
+  pub name: String,
+  pub id: String,
+  pub kind: FieldKind,
+}
+
+impl CustomField {
+  /// The value to send for what was typed into the field's editor.
+  /// Empty input clears the field.
+  pub fn input_value(&self, input: &str) -> Result<Value, String> {
+    let input = input.trim();
+    if input.is_empty() {
+      return Ok(Value::Null);
+    }
+    match self.kind {
+      FieldKind::Text => Ok(Value::String(input.to_string())),
+      FieldKind::Number => match input.parse::<i64>() {
+        Ok(n) => Ok(n.into()),
+        Err(_) => input
+          .parse::<f64>()
+          .ok()
+          .and_then(serde_json::Number::from_f64)
+          .map(Value::Number)
+          .ok_or_else(|| format!("{} must be a number", self.name)),
+      },
+      FieldKind::Date => NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .map(|_| Value::String(input.to_string()))
+        .map_err(|_| format!("{} must be a date like 2024-05-31", self.name)),
+      FieldKind::User | FieldKind::Select | FieldKind::Other => {
+        Err(format!("{} can't be typed in", self.name))
+      }
+    }
+  }
+}
+
+/// Match configured field names (or ids like `customfield_10016`) against
+/// the field list, in config order. Unknown names are skipped.
+pub fn resolve(names: &[String], fields: &[FieldInfo]) -> Vec<CustomField> {
+  names
+    .iter()
+    .filter_map(|name| {
+      let field = fields
+        .iter()
+        .find(|f| f.id == *name || f.name.eq_ignore_ascii_case(name));
+      if field.is_none() {
+        warn!("Unknown custom field '{}' in config", name);
+      }
+      field.map(|f| CustomField {
+        name: name.clone(),
+        id: f.id.clone(),
+        kind: f.kind,
+      })
+    })
+    .collect()
+}
+
+/// The Epic Link field, by its schema type or else by name.
+///
+/// Team-managed projects don't have one; their issues point at the epic
+/// through `parent`.
+pub fn epic_link_field(fields: &[FieldInfo]) -> Option<&FieldInfo> {
+  fields
+    .iter()
+    .find(|f| f.custom_type.as_deref() == Some(EPIC_LINK_TYPE))
+    .or_else(|| {
+      fields
+        .iter()
+        .find(|f| f.name.eq_ignore_ascii_case("Epic Link"))
+    })
+}
+
+/// The story points fields, by schema type or name. Company- and
+/// team-managed projects use different ones, so there can be two.
+pub fn story_points_fields(fields: &[FieldInfo]) -> Vec<&FieldInfo> {
+  fields
+    .iter()
+    .filter(|f| {
+      f.custom_type.as_deref() == Some(STORY_POINTS_TYPE)
+        || (f.kind == FieldKind::Number
+          && STORY_POINTS_NAMES
+            .iter()
+            .any(|name| f.name.eq_ignore_ascii_case(name)))
+    })
+    .collect()
+}
+
+/// Fields loaded with issue summaries besides the standard ones
+#[derive(Debug, Clone, Default)]
+pub struct IssueFields {
+  /// The epic link field; `parent` is loaded too
+  pub epic: Option<String>,
+  /// The story points fields; the first one set is used
+  pub story_points: Vec<String>,
+  pub custom: Vec<CustomField>,
+}
+
+impl IssueFields {
+  /// Ids of all the fields, to request them
+  pub fn ids(&self) -> impl Iterator<Item = &str> {
+    self
+      .epic
+      .iter()
+      .chain(&self.story_points)
+      .map(String::as_str)
+      .chain(self.custom.iter().map(|f| f.id.as_str()))
+  }
+}
+
+/// JQL for the issues of one or more epics. With `parent`, issues of
+/// team-managed projects are matched too; without an epic link field only
+/// they are.
+pub fn epic_issues_jql(epic_field: Option<&str>, parent: bool, epic_keys: &[&str]) -> String {
+  let keys = match epic_keys {
+    [key] => format!("= {}", key),
+    keys => format!("in ({})", keys.join(", ")),
+  };
+  let clause = match (epic_field, parent) {
+    (Some(field), true) => format!("({} {} OR parent {})", jql_field(field), keys, keys),
+    (Some(field), false) => format!("{} {}", jql_field(field), keys),
+    (None, _) => format!("parent {}", keys),
+  };
+  format!("{} ORDER BY updated DESC", clause)
+}
+
+/// A field as JQL refers to it: `cf[10014]` for custom field ids, the
+/// quoted name otherwise
+fn jql_field(field: &str) -> String {
+  match field.strip_prefix("customfield_") {
+    Some(n) => format!("cf[{}]", n),
+    None => format!("\"{}\"", field),
+  }
+}
+
+/// Text for a field value: options by their value, users by display name,
+/// lists joined with commas. Empty values give `None`.
+pub fn display_value(value: &Value) -> Option<String> {
+  match value {
+    Value::Null => None,
+    Value::String(s) if s.is_empty() => None,
+    Value::String(s) => Some(s.clone()),
+    // 5.0 shows as "5"
+    Value::Number(n) => n.as_f64().map(|n| n.to_string()),
+    Value::Bool(b) => Some(b.to_string()),
+    Value::Object(obj) => ["value", "displayName", "name", "key"]
+      .iter()
+      .find_map(|k| obj.get(*k).and_then(Value::as_str))
+      .map(String::from),
+    Value::Array(items) => {
+      let parts: Vec<String> = items.iter().filter_map(display_value).collect();
+      (!parts.is_empty()).then(|| parts.join(", "))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn field(id: &str, name: &str, kind: FieldKind) -> FieldInfo {
+    FieldInfo {
+      id: id.to_string(),
+      name: name.to_string(),
+      custom: true,
+      kind,
+      custom_type: None,
+    }
+  }
+
+  #[test]
+  fn test_resolve_by_name_or_id() {
+    let fields = [
+      field("customfield_10016", "Story Points", FieldKind::Number),
+      field("customfield_10020", "Team", FieldKind::Select),
+    ];
+    let names = ["story points", "customfield_10020", "Nope"].map(String::from);
+    let resolved = resolve(&names, &fields);
+    assert_eq!(resolved.len(), 2);
+    assert_eq!(resolved[0].name, "story points");
+    assert_eq!(resolved[0].id, "customfield_10016");
+    assert_eq!(resolved[1].kind, FieldKind::Select);
+  }
+
+  #[test]
+  fn test_epic_link_field() {
+    let mut fields = vec![
+      field("customfield_10011", "Epic Name", FieldKind::Text),
+      field("customfield_10014", "epic link", FieldKind::Other),
+    ];
+    assert_eq!(epic_link_field(&fields).unwrap().id, "customfield_10014");
+
+    // A renamed field is still found by its type
+    fields.push(FieldInfo {
+      custom_type: Some(EPIC_LINK_TYPE.to_string()),
+      ..field("customfield_10100", "Feature", FieldKind::Other)
+    });
+    assert_eq!(epic_link_field(&fields).unwrap().id, "customfield_10100");
+
+    assert!(epic_link_field(&fields[..1]).is_none());
+  }
+
+  #[test]
+  fn test_epic_issues_jql() {
+    assert_eq!(
+      epic_issues_jql(Some("customfield_10014"), true, &["P-1"]),
+      "(cf[10014] = P-1 OR parent = P-1) ORDER BY updated DESC"
+    );
+    assert_eq!(
+      epic_issues_jql(Some("Epic Link"), false, &["P-1"]),
+      "\"Epic Link\" = P-1 ORDER BY updated DESC"
+    );
+    assert_eq!(
+      epic_issues_jql(None, false, &["P-1", "P-2"]),
+      "parent in (P-1, P-2) ORDER BY updated DESC"
+    );
+  }
+
+  #[test]
+  fn test_story_points_fields() {
+    let fields = [
+      field("customfield_10016", "Story Points", FieldKind::Number),
+      field("customfield_10020", "Story Points", FieldKind::Text),
+      FieldInfo {
+        custom_type: Some(STORY_POINTS_TYPE.to_string()),
+        ..field("customfield_10030", "Estimate", FieldKind::Number)
+      },
+    ];
+    let ids: Vec<&str> = story_points_fields(&fields)
+      .iter()
+      .map(|f| f.id.as_str())
+      .collect();
+    assert_eq!(ids, ["customfield_10016", "customfield_10030"]);
+  }
+
+  #[test]
+  fn test_display_value() {
+    assert_eq!(display_value(&json!(5.0)), Some("5".to_string()));
+    assert_eq!(display_value(&json!(2.5)), Some("2.5".to_string()));
+    assert_eq!(
+      display_value(&json!({"id": "1", "value": "High"})),
+      Some("High".to_string())
+    );
+    assert_eq!(
+      display_value(&json!({"displayName": "Ann"})),
+      Some("Ann".to_string())
+    );
+    assert_eq!(
+      display_value(&json!(["a", {"value": "b"}])),
+      Some("a, b".to_string())
+    );
+    assert_eq!(display_value(&json!([])), None);
+    assert_eq!(display_value(&json!(null)), None);
+  }
+
+  #[test]
+  fn test_input_value() {
+    let points = CustomField {
+      name: "Points".to_string(),
+      id: "customfield_1".to_string(),
+      kind: FieldKind::Number,
+    };
+    assert_eq!(points.input_value("3"), Ok(json!(3)));
+    assert_eq!(points.input_value("0.5"), Ok(json!(0.5)));
+    assert_eq!(points.input_value(" "), Ok(Value::Null));
+    assert!(points.input_value("lots").is_err());
+
+    let due = CustomField {
+      kind: FieldKind::Date,
+      ..points
+    };
+    assert_eq!(due.input_value("2024-05-31"), Ok(json!("2024-05-31")));
+    assert!(due.input_value("31/05/2024").is_err());
+  }
+}
@@ -0,0 +1,356 @@
+//! Flow metrics of a board from its issues' status changes: lead and cycle
+//! times, throughput, WIP per column and cumulative flow.
+
+use super::types::{BoardColumn, ChangelogEntry, IssueSummary};
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc};
+
+/// Parse a Jira timestamp like "2024-05-01T12:34:56.000+0000" (issues) or
+/// "2024-05-01T12:34:56.000Z" (sprints)
+pub fn parse_time(timestamp: &str) -> Option<DateTime<Utc>> {
+  DateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S%.f%z")
+    .or_else(|_| DateTime::parse_from_rfc3339(timestamp))
+    .ok()
+    .map(|t| t.with_timezone(&Utc))
+}
+
+/// The board column a status (by id) is in. Board configurations only
+/// list status ids, so names can't be matched.
+fn column_of(columns: &[BoardColumn], status_id: Option<&str>) -> Option<usize> {
+  let status_id = status_id?;
+  columns
+    .iter()
+    .position(|column| column.statuses.iter().any(|s| s.id == status_id))
+}
+
+/// The columns an issue went through
+#[derive(Debug, Clone)]
+pub struct IssueFlow {
+  pub created: DateTime<Utc>,
+  /// The column entered at each status change, oldest first, starting with
+  /// the one the issue was created in; `None` for statuses not on the board
+  pub moves: Vec<(DateTime<Utc>, Option<usize>)>,
+}
+
+impl IssueFlow {
+  /// From the status changes in an issue's history. `None` when the issue
+  /// has no creation time (cached by an older version).
+  pub fn new(
+    issue: &IssueSummary,
+    changelog: &[ChangelogEntry],
+    columns: &[BoardColumn],
+  ) -> Option<Self> {
+    let created = parse_time(&issue.created)?;
+    let changes: Vec<(DateTime<Utc>, &ChangelogEntry)> = changelog
+      .iter()
+      .filter(|e| e.field == "status")
+      .filter_map(|e| Some((parse_time(&e.created)?, e)))
+      .collect();
+
+    // Without status changes it's still in the status it was created in
+    let initial = match changes.first() {
+      Some((_, first)) => first.from_id.as_deref(),
+      None => Some(issue.status_id.as_str()),
+    };
+    let mut moves = vec![(created, column_of(columns, initial))];
+    moves.extend(
+      changes
+        .iter()
+        .map(|(time, e)| (*time, column_of(columns, e.to_id.as_deref()))),
+    );
+    Some(Self { created, moves })
+  }
+
+  /// The column the issue was in just before a time
+  pub fn column_before(&self, time: DateTime<Utc>) -> Option<usize> {
+    self
+      .moves
+      .iter()
+      .take_while(|(t, _)| *t < time)
+      .last()
+      .and_then(|(_, column)| *column)
+  }
+
+  /// The column the issue was in at a time, after any move at that moment
+  pub fn column_at(&self, time: DateTime<Utc>) -> Option<usize> {
+    self
+      .moves
+      .iter()
+      .take_while(|(t, _)| *t <= time)
+      .last()
+      .and_then(|(_, column)| *column)
+  }
+
+  /// When work started: the first move past the first column
+  pub fn started_at(&self) -> Option<DateTime<Utc>> {
+    self
+      .moves
+      .iter()
+      .find(|(_, column)| column.is_some_and(|c| c > 0))
+      .map(|(time, _)| *time)
+  }
+
+  /// When the issue last reached the done column, if it's still there
+  pub fn done_at(&self, done: usize) -> Option<DateTime<Utc>> {
+    self
+      .moves
+      .iter()
+      .rev()
+      .take_while(|(_, column)| *column == Some(done))
+      .last()
+      .map(|(time, _)| *time)
+  }
+}
+
+/// Lead and cycle times, throughput and daily column counts over a window
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlowMetrics {
+  /// Days from creation to done of the issues finished in the window,
+  /// shortest first
+  pub lead_times: Vec<f64>,
+  /// Days from leaving the first column to done, shortest first
+  pub cycle_times: Vec<f64>,
+  /// Issues finished each week (starting on Monday) of the window
+  pub throughput: Vec<(NaiveDate, usize)>,
+  /// Issues in each column at the end of each day of the window
+  pub daily: Vec<(NaiveDate, Vec<usize>)>,
+}
+
+impl FlowMetrics {
+  /// From the issues' flows over the days `from..=to`. The last column is
+  /// taken as done.
+  pub fn compute(flows: &[IssueFlow], columns: usize, from: NaiveDate, to: NaiveDate) -> Self {
+    let mut metrics = Self::default();
+    let Some(done) = columns.checked_sub(1) else {
+      return metrics;
+    };
+
+    let start = from.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+    let finished: Vec<(&IssueFlow, DateTime<Utc>)> = flows
+      .iter()
+      .filter_map(|flow| Some((flow, flow.done_at(done)?)))
+      .filter(|(_, done_at)| *done_at >= start)
+      .collect();
+
+    for (flow, done_at) in &finished {
+      metrics
+        .lead_times
+        .push(days_between(flow.created, *done_at));
+      let started = flow.started_at().unwrap_or(*done_at);
+      metrics.cycle_times.push(days_between(started, *done_at));
+    }
+    metrics.lead_times.sort_by(f64::total_cmp);
+    metrics.cycle_times.sort_by(f64::total_cmp);
+
+    let mut week = from - Days::new(from.weekday().num_days_from_monday().into());
+    while week <= to {
+      let next = week + Days::new(7);
+      let count = finished
+        .iter()
+        .filter(|(_, done_at)| (week..next).contains(&done_at.date_naive()))
+        .count();
+      metrics.throughput.push((week, count));
+      week = next;
+    }
+
+    for day in from.iter_days().take_while(|day| *day <= to) {
+      let end = (day + Days::new(1))
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc();
+      let mut counts = vec![0; columns];
+      for column in flows.iter().filter_map(|flow| flow.column_before(end)) {
+        counts[column] += 1;
+      }
+      metrics.daily.push((day, counts));
+    }
+
+    metrics
+  }
+
+  /// Average of issues finished per week
+  pub fn weekly_throughput(&self) -> f64 {
+    match self.throughput.len() {
+      0 => 0.0,
+      weeks => self.lead_times.len() as f64 / weeks as f64,
+    }
+  }
+}
+
+fn days_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+  (to - from).num_minutes().max(0) as f64 / (24.0 * 60.0)
+}
+
+/// The value below which `p` percent of the values fall (nearest rank), from
+/// values sorted in ascending order
+pub fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+  if sorted.is_empty() {
+    return None;
+  }
+  let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+  sorted.get(rank.clamp(1, sorted.len()) - 1).copied()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::jira::types::{test_issue, StatusInfo};
+
+  /// Status ids and names
+  const STATUSES: [(&str, &str); 3] = [("1", "To Do"), ("3", "In Progress"), ("10001", "Done")];
+
+  fn status_id(name: &str) -> String {
+    let (id, _) = STATUSES.iter().find(|(_, n)| *n == name).unwrap();
+    id.to_string()
+  }
+
+  /// Like the board configuration: status ids only, the name falls back to
+  /// the id
+  fn columns() -> Vec<BoardColumn> {
+    STATUSES
+      .iter()
+      .map(|(id, name)| BoardColumn {
+        name: name.to_string(),
+        statuses: vec![StatusInfo {
+          id: id.to_string(),
+          name: id.to_string(),
+          ..Default::default()
+        }],
+      })
+      .collect()
+  }
+
+  fn issue(created: &str, status: &str) -> IssueSummary {
+    IssueSummary {
+      status: status.to_string(),
+      status_id: status_id(status),
+      created: created.to_string(),
+      ..test_issue("P-1")
+    }
+  }
+
+  fn change(created: &str, from: &str, to: &str) -> ChangelogEntry {
+    ChangelogEntry {
+      issue_key: "P-1".to_string(),
+      id: created.to_string(),
+      author: None,
+      created: created.to_string(),
+      field: "status".to_string(),
+      from: Some(from.to_string()),
+      to: Some(to.to_string()),
+      from_id: Some(status_id(from)),
+      to_id: Some(status_id(to)),
+    }
+  }
+
+  fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+  }
+
+  #[test]
+  fn test_issue_flow() {
+    let history = [
+      change("2024-05-02T09:00:00.000+0000", "To Do", "In Progress"),
+      change("2024-05-03T09:00:00.000+0000", "In Progress", "Done"),
+      change("2024-05-04T09:00:00.000+0000", "Done", "In Progress"),
+      change("2024-05-06T09:00:00.000+0000", "In Progress", "Done"),
+    ];
+    let flow = IssueFlow::new(
+      &issue("2024-05-01T09:00:00.000+0000", "Done"),
+      &history,
+      &columns(),
+    )
+    .unwrap();
+    assert_eq!(flow.moves.len(), 5);
+    // Reopened, so done when it was finished again
+    assert_eq!(flow.done_at(2), parse_time("2024-05-06T09:00:00.000+0000"));
+    assert_eq!(
+      flow.started_at(),
+      parse_time("2024-05-02T09:00:00.000+0000")
+    );
+    assert_eq!(
+      flow.column_before(parse_time("2024-05-05T00:00:00.000+0000").unwrap()),
+      Some(1)
+    );
+    let finished = parse_time("2024-05-03T09:00:00.000Z").unwrap();
+    assert_eq!(flow.column_before(finished), Some(1));
+    assert_eq!(flow.column_at(finished), Some(2));
+
+    // Never moved: still where it was created
+    let new = IssueFlow::new(
+      &issue("2024-05-01T09:00:00.000+0000", "To Do"),
+      &[],
+      &columns(),
+    );
+    assert_eq!(new.unwrap().moves, [(flow.created, Some(0))]);
+  }
+
+  #[test]
+  fn test_columns_match_status_ids() {
+    let cols = columns();
+    // Names alone (histories cached without ids) don't map to columns
+    let mut unnamed = change("2024-05-02T09:00:00.000+0000", "To Do", "Done");
+    unnamed.from_id = None;
+    unnamed.to_id = None;
+    let flow = IssueFlow::new(
+      &issue("2024-05-01T09:00:00.000+0000", "Done"),
+      &[unnamed],
+      &cols,
+    )
+    .unwrap();
+    assert_eq!(
+      flow.moves.iter().map(|(_, c)| *c).collect::<Vec<_>>(),
+      [None, None]
+    );
+
+    let flow = IssueFlow::new(
+      &issue("2024-05-01T09:00:00.000+0000", "Done"),
+      &[change("2024-05-02T09:00:00.000+0000", "To Do", "Done")],
+      &cols,
+    )
+    .unwrap();
+    assert_eq!(
+      flow.moves.iter().map(|(_, c)| *c).collect::<Vec<_>>(),
+      [Some(0), Some(2)]
+    );
+  }
+
+  #[test]
+  fn test_compute() {
+    let cols = columns();
+    let done = IssueFlow::new(
+      &issue("2024-05-01T00:00:00.000+0000", "Done"),
+      &[
+        change("2024-05-03T00:00:00.000+0000", "To Do", "In Progress"),
+        change("2024-05-07T00:00:00.000+0000", "In Progress", "Done"),
+      ],
+      &cols,
+    )
+    .unwrap();
+    let open = IssueFlow::new(&issue("2024-05-06T12:00:00.000+0000", "To Do"), &[], &cols).unwrap();
+
+    // Wednesday to Tuesday: two weeks starting on Mondays
+    let metrics = FlowMetrics::compute(&[done, open], 3, date("2024-05-01"), date("2024-05-07"));
+    assert_eq!(metrics.lead_times, [6.0]);
+    assert_eq!(metrics.cycle_times, [4.0]);
+    assert_eq!(
+      metrics.throughput,
+      [(date("2024-04-29"), 0), (date("2024-05-06"), 1)]
+    );
+    assert_eq!(metrics.weekly_throughput(), 0.5);
+    assert_eq!(metrics.daily.len(), 7);
+    assert_eq!(metrics.daily[0].1, [1, 0, 0]);
+    assert_eq!(metrics.daily[2].1, [0, 1, 0]);
+    assert_eq!(metrics.daily[5].1, [1, 1, 0]);
+    assert_eq!(metrics.daily[6].1, [1, 0, 1]);
+  }
+
+  #[test]
+  fn test_percentile() {
+    let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+    assert_eq!(percentile(&values, 50.0), Some(5.0));
+    assert_eq!(percentile(&values, 85.0), Some(9.0));
+    assert_eq!(percentile(&values, 100.0), Some(10.0));
+    assert_eq!(percentile(&values[..1], 50.0), Some(1.0));
+    assert_eq!(percentile(&[], 50.0), None);
+  }
+}
@@ -1,5 +1,11 @@
 mod api_types;
+pub mod burndown;
 pub mod client;
+pub mod fields;
+pub mod metrics;
+pub mod mutations;
+pub mod notifications;
+pub mod progress;
 pub mod types;
 
 pub use client::JiraClient;
@@ -0,0 +1,278 @@
+//! Issue mutations that can be applied immediately or queued while offline.
+
+use super::types::{Issue, IssueSummary, StatusCategory};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A change to a single issue.
+///
+/// Mutations are serialized into the offline queue, so variants must stay
+/// backwards compatible with what older versions wrote.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Mutation {
+  /// Transition to a status (the transition is looked up at apply time)
+  Transition {
+    status_id: String,
+    status_name: String,
+    /// Category of the target status, if known
+    #[serde(default)]
+    category: Option<StatusCategory>,
+    #[serde(default)]
+    category_color: Option<String>,
+  },
+  /// Assign to a user, or unassign when both ids are None
+  Assign {
+    account_id: Option<String>,
+    name: Option<String>,
+    display_name: Option<String>,
+  },
+  /// Add a plain-text comment
+  Comment { body: String },
+  /// Set a single field to a JSON value
+  EditField {
+    field: String,
+    value: serde_json::Value,
+  },
+  /// Rank right above (`before`) or below another issue on boards and
+  /// backlogs
+  Rank { other: String, before: bool },
+}
+
+impl Mutation {
+  /// Short identifier stored alongside the queued payload
+  pub fn kind(&self) -> &'static str {
+    match self {
+      Mutation::Transition { .. } => "transition",
+      Mutation::Assign { .. } => "assign",
+      Mutation::Comment { .. } => "comment",
+      Mutation::EditField { .. } => "edit_field",
+      Mutation::Rank { .. } => "rank",
+    }
+  }
+
+  /// Human-readable description for the pending mutations view
+  pub fn describe(&self) -> String {
+    match self {
+      Mutation::Transition { status_name, .. } => format!("Move to {}", status_name),
+      Mutation::Assign {
+        display_name: Some(name),
+        ..
+      } => format!("Assign to {}", name),
+      Mutation::Assign { .. } => "Unassign".to_string(),
+      Mutation::Comment { body } => format!("Comment: {}", body.lines().next().unwrap_or("")),
+      Mutation::EditField { field, value } => match value.as_str() {
+        Some(v) => format!("Set {} = {}", field, v),
+        None => format!("Set {} = {}", field, value),
+      },
+      Mutation::Rank {
+        other,
+        before: true,
+      } => format!("Rank above {}", other),
+      Mutation::Rank { other, .. } => format!("Rank below {}", other),
+    }
+  }
+
+  /// Whether the optimistic update shows the whole change; other fields
+  /// are only seen after reloading the issue
+  pub fn updates_cache(&self) -> bool {
+    match self {
+      Mutation::EditField { field, .. } => field == "summary",
+      _ => true,
+    }
+  }
+
+  /// Apply the mutation to a cached issue summary (optimistic update)
+  pub fn apply_to_summary(&self, issue: &mut IssueSummary) {
+    self.apply(CachedFields {
+      summary: &mut issue.summary,
+      status: &mut issue.status,
+      status_id: &mut issue.status_id,
+      status_category: &mut issue.status_category,
+      category_color: &mut issue.category_color,
+      assignee: &mut issue.assignee,
+    });
+  }
+
+  /// Apply the mutation to a cached full issue (optimistic update)
+  pub fn apply_to_issue(&self, issue: &mut Issue) {
+    self.apply(CachedFields {
+      summary: &mut issue.summary,
+      status: &mut issue.status,
+      status_id: &mut issue.status_id,
+      status_category: &mut issue.status_category,
+      category_color: &mut issue.category_color,
+      assignee: &mut issue.assignee,
+    });
+  }
+
+  fn apply(&self, issue: CachedFields<'_>) {
+    match self {
+      Mutation::Transition {
+        status_id,
+        status_name,
+        category,
+        category_color,
+      } => {
+        *issue.status = status_name.clone();
+        *issue.status_id = status_id.clone();
+        // Keep the old category when the target's isn't known
+        if let Some(category) = category {
+          *issue.status_category = *category;
+          *issue.category_color = category_color.clone();
+        }
+      }
+      Mutation::Assign { display_name, .. } => *issue.assignee = display_name.clone(),
+      Mutation::EditField { field, value } if field == "summary" => {
+        if let Some(summary) = value.as_str() {
+          *issue.summary = summary.to_string();
+        }
+      }
+      Mutation::Comment { .. } | Mutation::EditField { .. } | Mutation::Rank { .. } => {}
+    }
+  }
+}
+
+/// The fields of `IssueSummary` and `Issue` that optimistic updates change
+struct CachedFields<'a> {
+  summary: &'a mut String,
+  status: &'a mut String,
+  status_id: &'a mut String,
+  status_category: &'a mut StatusCategory,
+  category_color: &'a mut Option<String>,
+  assignee: &'a mut Option<String>,
+}
+
+/// What happened to a mutation request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutationOutcome {
+  /// Sent to Jira successfully
+  Applied,
+  /// Recorded in the offline queue for later replay
+  Queued,
+}
+
+/// Result of replaying the offline queue
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplaySummary {
+  pub applied: usize,
+  pub conflicts: usize,
+  pub failed: usize,
+  /// Mutations left pending because the network is still unavailable
+  pub remaining: usize,
+}
+
+impl fmt::Display for ReplaySummary {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Replayed {} queued change(s)", self.applied)?;
+    if self.conflicts > 0 {
+      write!(f, ", {} conflict(s)", self.conflicts)?;
+    }
+    if self.failed > 0 {
+      write!(f, ", {} failed", self.failed)?;
+    }
+    if self.conflicts > 0 || self.failed > 0 {
+      write!(f, " - see :pending")?;
+    }
+    Ok(())
+  }
+}
+
+/// Error for requests that never reached Jira (connection refused, timeout, ...)
+///
+/// Mutations failing with this error are queued instead of reported.
+#[derive(Debug)]
+pub struct NetworkError(pub String);
+
+impl fmt::Display for NetworkError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.0)
+  }
+}
+
+impl std::error::Error for NetworkError {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::jira::types::test_issue;
+
+  fn summary() -> IssueSummary {
+    IssueSummary {
+      summary: "Old summary".to_string(),
+      status: "To Do".to_string(),
+      status_id: "1".to_string(),
+      issue_type: "Task".to_string(),
+      updated: "2024-01-01".to_string(),
+      ..test_issue("TEST-1")
+    }
+  }
+
+  #[test]
+  fn test_serialization_roundtrip() {
+    let mutation = Mutation::Transition {
+      status_id: "3".to_string(),
+      status_name: "Done".to_string(),
+      category: Some(StatusCategory::Done),
+      category_color: Some("green".to_string()),
+    };
+    let json = serde_json::to_string(&mutation).unwrap();
+    assert!(json.contains("\"kind\":\"transition\""));
+    assert_eq!(serde_json::from_str::<Mutation>(&json).unwrap(), mutation);
+
+    // Transitions queued before categories were recorded
+    let old = r#"{"kind":"transition","status_id":"3","status_name":"Done"}"#;
+    assert!(matches!(
+      serde_json::from_str::<Mutation>(old).unwrap(),
+      Mutation::Transition { category: None, .. }
+    ));
+  }
+
+  #[test]
+  fn test_apply_transition_and_assign() {
+    let mut issue = summary();
+    Mutation::Transition {
+      status_id: "3".to_string(),
+      status_name: "Done".to_string(),
+      category: Some(StatusCategory::Done),
+      category_color: Some("green".to_string()),
+    }
+    .apply_to_summary(&mut issue);
+    Mutation::Assign {
+      account_id: Some("abc".to_string()),
+      name: None,
+      display_name: Some("Alice".to_string()),
+    }
+    .apply_to_summary(&mut issue);
+
+    assert_eq!(issue.status, "Done");
+    assert_eq!(issue.status_id, "3");
+    assert!(issue.is_done());
+    assert_eq!(issue.category_color.as_deref(), Some("green"));
+    assert_eq!(issue.assignee.as_deref(), Some("Alice"));
+  }
+
+  #[test]
+  fn test_apply_summary_edit() {
+    let mut issue = summary();
+    Mutation::EditField {
+      field: "summary".to_string(),
+      value: serde_json::json!("New summary"),
+    }
+    .apply_to_summary(&mut issue);
+    assert_eq!(issue.summary, "New summary");
+  }
+
+  #[test]
+  fn test_replay_summary_display() {
+    let summary = ReplaySummary {
+      applied: 2,
+      conflicts: 1,
+      ..Default::default()
+    };
+    assert_eq!(
+      summary.to_string(),
+      "Replayed 2 queued change(s), 1 conflict(s) - see :pending"
+    );
+  }
+}
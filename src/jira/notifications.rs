@@ -0,0 +1,138 @@
+//! Turning changes to watched issues into notifications.
+
+use super::types::WatchedIssue;
+
+/// What happened to a watched issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+  /// The issue was assigned to the current user
+  Assigned,
+  /// The issue moved to another status
+  Status,
+  /// Someone else commented
+  Comment,
+  /// The issue started matching the watch query
+  Watching,
+}
+
+impl NotificationKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      NotificationKind::Assigned => "assigned",
+      NotificationKind::Status => "status",
+      NotificationKind::Comment => "comment",
+      NotificationKind::Watching => "watching",
+    }
+  }
+
+  pub fn parse(s: &str) -> Option<Self> {
+    match s {
+      "assigned" => Some(NotificationKind::Assigned),
+      "status" => Some(NotificationKind::Status),
+      "comment" => Some(NotificationKind::Comment),
+      "watching" => Some(NotificationKind::Watching),
+      _ => None,
+    }
+  }
+}
+
+/// Describe what changed between the cached and current version of an issue.
+///
+/// `me` is the current user's display name, used to recognize assignments
+/// and skip the user's own comments. Changes to other fields are ignored.
+pub fn detect_changes(
+  previous: Option<&WatchedIssue>,
+  current: &WatchedIssue,
+  me: &str,
+) -> Vec<(NotificationKind, String)> {
+  let assigned_to_me = current.assignee.as_deref() == Some(me);
+
+  let Some(previous) = previous else {
+    return vec![if assigned_to_me {
+      (NotificationKind::Assigned, "Assigned to you".to_string())
+    } else {
+      (
+        NotificationKind::Watching,
+        "New on your watch list".to_string(),
+      )
+    }];
+  };
+
+  let mut changes = Vec::new();
+  if assigned_to_me && previous.assignee != current.assignee {
+    changes.push((NotificationKind::Assigned, "Assigned to you".to_string()));
+  }
+  if previous.status != current.status {
+    changes.push((
+      NotificationKind::Status,
+      format!("{} → {}", previous.status, current.status),
+    ));
+  }
+  let commenter = current.last_commenter.as_deref();
+  if current.comment_count > previous.comment_count && commenter != Some(me) {
+    let who = commenter.unwrap_or("Someone");
+    changes.push((NotificationKind::Comment, format!("{} commented", who)));
+  }
+  changes
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn issue(
+    status: &str,
+    assignee: Option<&str>,
+    comments: u32,
+    commenter: Option<&str>,
+  ) -> WatchedIssue {
+    WatchedIssue {
+      key: "A-1".to_string(),
+      summary: "Fix it".to_string(),
+      status: status.to_string(),
+      assignee: assignee.map(String::from),
+      comment_count: comments,
+      last_commenter: commenter.map(String::from),
+      updated: String::new(),
+    }
+  }
+
+  #[test]
+  fn test_new_issues() {
+    let mine = issue("To Do", Some("Me"), 0, None);
+    let theirs = issue("To Do", Some("Bob"), 0, None);
+    assert_eq!(
+      detect_changes(None, &mine, "Me")[0].0,
+      NotificationKind::Assigned
+    );
+    assert_eq!(
+      detect_changes(None, &theirs, "Me")[0].0,
+      NotificationKind::Watching
+    );
+  }
+
+  #[test]
+  fn test_assignment_status_and_comment() {
+    let before = issue("To Do", Some("Bob"), 1, Some("Bob"));
+    let after = issue("In Progress", Some("Me"), 2, Some("Alice"));
+    let kinds: Vec<_> = detect_changes(Some(&before), &after, "Me")
+      .into_iter()
+      .map(|(kind, _)| kind)
+      .collect();
+    assert_eq!(
+      kinds,
+      [
+        NotificationKind::Assigned,
+        NotificationKind::Status,
+        NotificationKind::Comment
+      ]
+    );
+  }
+
+  #[test]
+  fn test_own_comments_and_other_edits_are_ignored() {
+    let before = issue("To Do", Some("Me"), 1, Some("Bob"));
+    let after = issue("To Do", Some("Me"), 2, Some("Me"));
+    assert!(detect_changes(Some(&before), &after, "Me").is_empty());
+  }
+}
@@ -0,0 +1,204 @@
+//! Epic progress rolled up from the epics' issues.
+
+use super::types::{IssueSummary, StatusCategory};
+use std::collections::{HashMap, HashSet};
+
+/// Issue counts by status category and story points of an epic's issues
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EpicProgress {
+  pub todo: usize,
+  pub in_progress: usize,
+  pub done: usize,
+  pub points_done: f64,
+  pub points_total: f64,
+}
+
+impl EpicProgress {
+  /// Progress of a single issue; resolved issues are done whatever their
+  /// status
+  fn of(issue: &IssueSummary) -> Self {
+    let points = issue.story_points.unwrap_or(0.0);
+    let mut progress = Self {
+      points_total: points,
+      ..Self::default()
+    };
+    if issue.is_done() {
+      progress.done = 1;
+      progress.points_done = points;
+    } else if issue.status_category == StatusCategory::InProgress {
+      progress.in_progress = 1;
+    } else {
+      progress.todo = 1;
+    }
+    progress
+  }
+
+  fn merge(&mut self, other: &Self) {
+    self.todo += other.todo;
+    self.in_progress += other.in_progress;
+    self.done += other.done;
+    self.points_done += other.points_done;
+    self.points_total += other.points_total;
+  }
+
+  pub fn total(&self) -> usize {
+    self.todo + self.in_progress + self.done
+  }
+
+  /// Share of issues done, from 0 to 1 (0 without issues)
+  pub fn done_ratio(&self) -> f64 {
+    match self.total() {
+      0 => 0.0,
+      total => self.done as f64 / total as f64,
+    }
+  }
+}
+
+/// An issue as last counted
+#[derive(Debug, Clone)]
+struct Counted {
+  epic: String,
+  updated: String,
+  progress: EpicProgress,
+}
+
+/// Progress of each epic, kept up to date from successive loads of the
+/// epics' issues.
+///
+/// Only issues that were added, changed (by their `updated` time), moved to
+/// another epic or dropped from the results are recounted, and only the
+/// epics they touch are summed again.
+#[derive(Debug, Clone, Default)]
+pub struct Rollup {
+  issues: HashMap<String, Counted>,
+  progress: HashMap<String, EpicProgress>,
+}
+
+impl Rollup {
+  /// Apply the current issues of the epics; returns the epics whose
+  /// progress was recounted
+  pub fn update(&mut self, issues: &[IssueSummary]) -> HashSet<String> {
+    let mut touched = HashSet::new();
+    let mut seen = HashSet::new();
+    for issue in issues {
+      let Some(epic) = &issue.epic else {
+        continue;
+      };
+      seen.insert(issue.key.as_str());
+      let counted = Counted {
+        epic: epic.clone(),
+        updated: issue.updated.clone(),
+        progress: EpicProgress::of(issue),
+      };
+      match self.issues.insert(issue.key.clone(), counted) {
+        Some(old) if old.epic == *epic && old.updated == issue.updated => continue,
+        Some(old) => {
+          touched.insert(old.epic);
+        }
+        None => {}
+      }
+      touched.insert(epic.clone());
+    }
+
+    self.issues.retain(|key, counted| {
+      let keep = seen.contains(key.as_str());
+      if !keep {
+        touched.insert(counted.epic.clone());
+      }
+      keep
+    });
+
+    for epic in &touched {
+      self.progress.remove(epic);
+    }
+    for counted in self.issues.values() {
+      if touched.contains(&counted.epic) {
+        let progress = self.progress.entry(counted.epic.clone()).or_default();
+        progress.merge(&counted.progress);
+      }
+    }
+    touched
+  }
+
+  /// Progress of an epic (empty if it has no issues)
+  pub fn get(&self, epic: &str) -> EpicProgress {
+    self.progress.get(epic).copied().unwrap_or_default()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::jira::types::test_issue;
+
+  fn issue(key: &str, epic: &str, category: StatusCategory, points: Option<f64>) -> IssueSummary {
+    IssueSummary {
+      status_category: category,
+      issue_type: "Story".to_string(),
+      epic: Some(epic.to_string()),
+      story_points: points,
+      updated: "2024-01-01T00:00:00.000+0000".to_string(),
+      ..test_issue(key)
+    }
+  }
+
+  #[test]
+  fn test_rollup() {
+    let mut resolved = issue("P-4", "E-1", StatusCategory::InProgress, Some(1.0));
+    resolved.resolution = Some("Won't Do".to_string());
+    let issues = [
+      issue("P-1", "E-1", StatusCategory::ToDo, Some(3.0)),
+      issue("P-2", "E-1", StatusCategory::InProgress, None),
+      issue("P-3", "E-1", StatusCategory::Done, Some(5.0)),
+      resolved,
+      issue("P-5", "E-2", StatusCategory::ToDo, Some(2.0)),
+    ];
+    let mut rollup = Rollup::default();
+    rollup.update(&issues);
+
+    assert_eq!(
+      rollup.get("E-1"),
+      EpicProgress {
+        todo: 1,
+        in_progress: 1,
+        done: 2,
+        points_done: 6.0,
+        points_total: 9.0,
+      }
+    );
+    assert_eq!(rollup.get("E-1").done_ratio(), 0.5);
+    assert_eq!(rollup.get("E-2").total(), 1);
+    assert_eq!(rollup.get("E-3"), EpicProgress::default());
+    assert_eq!(EpicProgress::default().done_ratio(), 0.0);
+  }
+
+  #[test]
+  fn test_rollup_recounts_only_changes() {
+    let mut issues = vec![
+      issue("P-1", "E-1", StatusCategory::ToDo, Some(3.0)),
+      issue("P-2", "E-2", StatusCategory::ToDo, None),
+      issue("P-3", "E-3", StatusCategory::ToDo, None),
+    ];
+    let mut rollup = Rollup::default();
+    assert_eq!(rollup.update(&issues).len(), 3);
+    assert!(rollup.update(&issues).is_empty());
+
+    // Done, moved to another epic, and dropped from the results
+    issues[0].status_category = StatusCategory::Done;
+    issues[0].updated = "2024-01-02T00:00:00.000+0000".to_string();
+    issues[1].epic = Some("E-3".to_string());
+    issues[1].updated = "2024-01-02T00:00:00.000+0000".to_string();
+    issues.truncate(2);
+
+    let touched = rollup.update(&issues);
+    assert_eq!(touched.len(), 3);
+    assert_eq!(rollup.get("E-1").done, 1);
+    assert_eq!(rollup.get("E-1").points_done, 3.0);
+    assert_eq!(rollup.get("E-2").total(), 0);
+    assert_eq!(rollup.get("E-3").total(), 1);
+
+    // Unchanged issues are not recounted
+    issues[0].summary = "Stale copy".to_string();
+    assert!(rollup.update(&issues).is_empty());
+  }
+}
@@ -1,17 +1,92 @@
 use crate::cache::Cacheable;
+use std::collections::BTreeMap;
+
+/// Jira's status category: which group of the workflow a status is in.
+///
+/// Unlike status names, these are the same on every Jira instance and in
+/// every language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum StatusCategory {
+  #[default]
+  ToDo,
+  InProgress,
+  Done,
+}
+
+impl StatusCategory {
+  /// From the category key in the API (`new`, `indeterminate`, `done`)
+  pub fn from_key(key: &str) -> Self {
+    match key {
+      "indeterminate" => Self::InProgress,
+      "done" => Self::Done,
+      _ => Self::ToDo,
+    }
+  }
+}
 
 /// Summary of an issue for list views
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct IssueSummary {
   pub key: String,
   pub summary: String,
   pub status: String,
   pub status_id: String,
+  /// Missing from issues cached by older versions
+  #[serde(default)]
+  pub status_category: StatusCategory,
+  /// Jira's color for the status category (e.g. `blue-gray`)
+  #[serde(default)]
+  pub category_color: Option<String>,
+  #[serde(default)]
+  pub resolution: Option<String>,
   pub issue_type: String,
   pub assignee: Option<String>,
   pub priority: Option<String>,
   pub epic: Option<String>,
+  /// Key of the parent: the issue of a subtask, the epic of an issue in a
+  /// team-managed project, the initiative of an epic
+  #[serde(default)]
+  pub parent: Option<String>,
+  #[serde(default)]
+  pub story_points: Option<f64>,
+  /// Missing from issues cached by older versions
+  #[serde(default)]
+  pub created: String,
   pub updated: String,
+  /// Values of the configured custom fields, by configured name
+  #[serde(default)]
+  pub custom_fields: BTreeMap<String, String>,
+}
+
+/// An issue summary with just a key, for test fixtures to fill in
+#[cfg(test)]
+pub fn test_issue(key: &str) -> IssueSummary {
+  IssueSummary {
+    key: key.to_string(),
+    ..Default::default()
+  }
+}
+
+impl IssueSummary {
+  /// Whether the issue is finished: in a done status or resolved
+  pub fn is_done(&self) -> bool {
+    self.status_category == StatusCategory::Done || self.resolution.is_some()
+  }
+
+  /// The issue this one is under in the hierarchy: its parent, or else its
+  /// epic
+  pub fn tree_parent(&self) -> Option<&str> {
+    self.parent.as_deref().or(self.epic.as_deref())
+  }
+
+  /// Value of a configured custom field (name matched ignoring case)
+  pub fn custom_field(&self, name: &str) -> Option<&str> {
+    self
+      .custom_fields
+      .iter()
+      .find(|(field, _)| field.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.as_str())
+  }
 }
 
 impl Cacheable for IssueSummary {
@@ -36,6 +111,12 @@ pub struct Issue {
   pub description: Option<String>,
   pub status: String,
   pub status_id: String,
+  #[serde(default)]
+  pub status_category: StatusCategory,
+  #[serde(default)]
+  pub category_color: Option<String>,
+  #[serde(default)]
+  pub resolution: Option<String>,
   pub issue_type: String,
   pub assignee: Option<String>,
   pub reporter: Option<String>,
@@ -43,6 +124,9 @@ pub struct Issue {
   pub labels: Vec<String>,
   pub created: String,
   pub updated: String,
+  /// Values of the configured custom fields, by configured name
+  #[serde(default)]
+  pub custom_fields: BTreeMap<String, String>,
 }
 
 impl Cacheable for Issue {
@@ -59,12 +143,99 @@ impl Cacheable for Issue {
   }
 }
 
+/// An issue matched by the notification watch query, with just the fields
+/// needed to tell what changed
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WatchedIssue {
+  pub key: String,
+  pub summary: String,
+  pub status: String,
+  pub assignee: Option<String>,
+  pub comment_count: u32,
+  pub last_commenter: Option<String>,
+  pub updated: String,
+}
+
+impl Cacheable for WatchedIssue {
+  fn cache_key(&self) -> String {
+    self.key.clone()
+  }
+
+  fn updated_at(&self) -> Option<&str> {
+    Some(&self.updated)
+  }
+
+  fn entity_type() -> &'static str {
+    "watched_issue"
+  }
+}
+
+/// One field change from an issue's history
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChangelogEntry {
+  pub issue_key: String,
+  /// The history record's id and the change's position in it; one record
+  /// can change several fields
+  pub id: String,
+  pub author: Option<String>,
+  pub created: String,
+  pub field: String,
+  pub from: Option<String>,
+  pub to: Option<String>,
+  /// Ids behind `from` and `to` where the field has them, e.g. status ids
+  #[serde(default)]
+  pub from_id: Option<String>,
+  #[serde(default)]
+  pub to_id: Option<String>,
+}
+
+impl Cacheable for ChangelogEntry {
+  fn cache_key(&self) -> String {
+    format!("{}/{}", self.issue_key, self.id)
+  }
+
+  fn updated_at(&self) -> Option<&str> {
+    None
+  }
+
+  fn entity_type() -> &'static str {
+    "changelog"
+  }
+}
+
+/// The authenticated user, cached so "assign to me" works offline
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CurrentUser {
+  /// Jira Cloud account id
+  pub account_id: Option<String>,
+  /// Jira Server/DC username
+  pub name: Option<String>,
+  pub display_name: String,
+}
+
+impl Cacheable for CurrentUser {
+  fn cache_key(&self) -> String {
+    "myself".to_string()
+  }
+
+  fn updated_at(&self) -> Option<&str> {
+    None
+  }
+
+  fn entity_type() -> &'static str {
+    "current_user"
+  }
+}
+
 /// Board summary
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Board {
   pub id: u64,
   pub name: String,
   pub board_type: String, // "scrum" or "kanban"
+  /// Key of the project the board belongs to (not set for cross-project boards)
+  #[serde(default)]
+  pub project_key: Option<String>,
 }
 
 impl Cacheable for Board {
@@ -92,21 +263,82 @@ pub struct Epic {
 }
 
 /// Status info with id and human-readable name
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct StatusInfo {
   pub id: String,
   pub name: String,
+  /// Not known for statuses from a board configuration, which only lists ids
+  #[serde(default)]
+  pub category: Option<StatusCategory>,
+  #[serde(default)]
+  pub category_color: Option<String>,
+}
+
+impl Cacheable for StatusInfo {
+  fn cache_key(&self) -> String {
+    self.id.clone()
+  }
+
+  fn updated_at(&self) -> Option<&str> {
+    None
+  }
+
+  fn entity_type() -> &'static str {
+    "status"
+  }
 }
 
 /// Board column configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct BoardColumn {
   pub name: String,
   pub statuses: Vec<StatusInfo>, // Status info that maps to this column
 }
 
+/// A sprint of a scrum board
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Sprint {
+  pub id: u64,
+  pub name: String,
+  /// "future", "active" or "closed"
+  pub state: String,
+  /// Not set for sprints that haven't been started
+  pub start: Option<String>,
+  pub end: Option<String>,
+  pub goal: Option<String>,
+}
+
+impl Cacheable for Sprint {
+  fn cache_key(&self) -> String {
+    self.id.to_string()
+  }
+
+  fn updated_at(&self) -> Option<&str> {
+    None
+  }
+
+  fn entity_type() -> &'static str {
+    "sprint"
+  }
+}
+
 /// Board configuration with columns
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct BoardConfiguration {
+  pub board_id: u64,
   pub columns: Vec<BoardColumn>,
 }
+
+impl Cacheable for BoardConfiguration {
+  fn cache_key(&self) -> String {
+    self.board_id.to_string()
+  }
+
+  fn updated_at(&self) -> Option<&str> {
+    None
+  }
+
+  fn entity_type() -> &'static str {
+    "board_config"
+  }
+}
@@ -1,9 +1,14 @@
 mod app;
+mod browser;
 mod cache;
+mod cli;
+mod clipboard;
 mod commands;
 mod config;
 mod db;
 mod event;
+mod export;
+mod git;
 mod jira;
 mod query;
 mod ui;
@@ -49,6 +54,27 @@ struct Args {
   /// Jira project key to use
   #[arg(short, long)]
   project: Option<String>,
+
+  /// Run in offline mode: only show cached data, never touch the network
+  #[arg(long)]
+  offline: bool,
+
+  /// Open the issue whose key is in the current git branch name
+  #[arg(long)]
+  current: bool,
+
+  #[command(subcommand)]
+  command: Option<cli::Command>,
+}
+
+/// Load the config file and apply command line overrides
+fn load_config(args: &Args) -> Result<config::Config> {
+  let config = config::Config::load(args.config.as_deref())?;
+  Ok(config::Config {
+    default_project: args.project.clone().or(config.default_project),
+    offline: config.offline || args.offline,
+    ..config
+  })
 }
 
 #[tokio::main]
@@ -58,21 +84,22 @@ async fn main() -> Result<()> {
 
   let args = Args::parse();
 
-  // Load configuration
-  let config = config::Config::load(args.config.as_deref())?;
+  // Subcommands run without the TUI
+  if let Some(command) = args.command {
+    let args = Args {
+      command: None,
+      ..args
+    };
+    return cli::run(command, || load_config(&args)).await;
+  }
 
-  // Override project if specified on command line
-  let config = if let Some(project) = args.project {
-    config::Config {
-      default_project: Some(project),
-      ..config
-    }
-  } else {
-    config
-  };
+  let config = load_config(&args)?;
+  if let Some(name) = &config.skin {
+    ui::set_skin(ui::Skin::load(name)?);
+  }
 
   // Initialize and run the app
-  let mut app = app::App::new(config).await?;
+  let mut app = app::App::new(config, args.current).await?;
   app.run().await?;
 
   Ok(())
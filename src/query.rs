@@ -29,6 +29,7 @@
 //! }
 //! ```
 
+use crate::cache::revalidate;
 use std::future::Future;
 use std::pin::Pin;
 use std::time::{Duration, Instant};
@@ -81,14 +82,13 @@ type FetcherFn<T> = Box<dyn Fn() -> BoxFuture<T> + Send + Sync>;
 /// - Loading/success/error states
 /// - Cached data that persists during refetch
 /// - Async result handling via channels
-/// - Optional stale time tracking for cache invalidation
 pub struct Query<T> {
   state: QueryState,
   cached_data: Option<T>,
   fetcher: FetcherFn<T>,
   receiver: Option<mpsc::UnboundedReceiver<Result<T, String>>>,
   fetched_at: Option<Instant>,
-  stale_time: Duration,
+  started_at: Option<Instant>,
 }
 
 impl<T: Send + 'static> Query<T> {
@@ -121,18 +121,10 @@ impl<T: Send + 'static> Query<T> {
       fetcher: Box::new(move || Box::pin(fetcher())),
       receiver: None,
       fetched_at: None,
-      stale_time: Duration::from_secs(60), // Default 1 minute
+      started_at: None,
     }
   }
 
-  /// Set the stale time for this query.
-  ///
-  /// After this duration, the data is considered stale and `is_stale()` returns true.
-  pub fn with_stale_time(mut self, duration: Duration) -> Self {
-    self.stale_time = duration;
-    self
-  }
-
   /// Get the current state of the query.
   pub fn state(&self) -> &QueryState {
     &self.state
@@ -144,6 +136,12 @@ impl<T: Send + 'static> Query<T> {
     self.cached_data.as_ref()
   }
 
+  /// Get mutable access to the cached data, for optimistic updates.
+  /// The next fetch replaces whatever was changed.
+  pub fn data_mut(&mut self) -> Option<&mut T> {
+    self.cached_data.as_mut()
+  }
+
   /// Check if the query is currently loading (initial fetch or refetch).
   pub fn is_loading(&self) -> bool {
     self.state.is_loading()
@@ -169,18 +167,39 @@ impl<T: Send + 'static> Query<T> {
     self.state.error()
   }
 
-  /// Check if the data is stale (older than stale_time).
-  pub fn is_stale(&self) -> bool {
+  /// Get when the current data was fetched.
+  pub fn fetched_at(&self) -> Option<Instant> {
+    self.fetched_at
+  }
+
+  /// Check if the data is older than the given age.
+  fn is_older_than(&self, max_age: Duration) -> bool {
     if self.cached_data.is_some() {
       self
         .fetched_at
-        .map(|t| t.elapsed() > self.stale_time)
+        .map(|t| t.elapsed() > max_age)
         .unwrap_or(true)
     } else {
       false
     }
   }
 
+  /// Refetch in the background if the data is older than `max_age`.
+  ///
+  /// Used for auto-refresh: cached data older than `max_age` is fetched
+  /// again even if the cache's stale time hasn't passed. Failed attempts
+  /// also wait `max_age` before the next try, so an unreachable server
+  /// isn't hit on every tick. Returns `true` if a refetch was started.
+  pub fn refetch_if_older(&mut self, max_age: Duration) -> bool {
+    let attempted_recently = self.started_at.is_some_and(|t| t.elapsed() <= max_age);
+    if self.is_loading() || attempted_recently || !self.is_older_than(max_age) {
+      return false;
+    }
+    self.receiver = None;
+    self.start_fetch(Some(max_age));
+    true
+  }
+
   /// Start fetching data if not already loading.
   ///
   /// This is a no-op if the query is already loading.
@@ -188,7 +207,7 @@ impl<T: Send + 'static> Query<T> {
     if self.state.is_loading() {
       return;
     }
-    self.start_fetch();
+    self.start_fetch(None);
   }
 
   /// Force a refetch, even if already loading or data exists.
@@ -196,7 +215,7 @@ impl<T: Send + 'static> Query<T> {
   pub fn refetch(&mut self) {
     // Cancel any pending fetch by dropping the receiver
     self.receiver = None;
-    self.start_fetch();
+    self.start_fetch(None);
   }
 
   /// Poll for results from a pending fetch.
@@ -234,16 +253,21 @@ impl<T: Send + 'static> Query<T> {
     }
   }
 
-  /// Internal: start the fetch operation
-  fn start_fetch(&mut self) {
+  /// Internal: start the fetch operation, revalidating cached data older
+  /// than `max_age` if given
+  fn start_fetch(&mut self, max_age: Option<Duration>) {
     let (tx, rx) = mpsc::unbounded_channel();
     self.receiver = Some(rx);
     self.state = QueryState::Loading;
+    self.started_at = Some(Instant::now());
     // Note: cached_data is NOT cleared - preserves stale-while-revalidate
 
     let future = (self.fetcher)();
     tokio::spawn(async move {
-      let result = future.await;
+      let result = match max_age {
+        Some(max_age) => revalidate(max_age, future).await,
+        None => future.await,
+      };
       // Ignore send errors - receiver may have been dropped
       let _ = tx.send(result);
     });
@@ -259,7 +283,7 @@ impl<T: std::fmt::Debug> std::fmt::Debug for Query<T> {
       .field("state", &self.state)
       .field("has_data", &self.cached_data.is_some())
       .field("fetched_at", &self.fetched_at)
-      .field("stale_time", &self.stale_time)
+      .field("started_at", &self.started_at)
       .finish_non_exhaustive()
   }
 }
@@ -297,18 +321,6 @@ mod tests {
     assert_eq!(query.error(), Some("Something went wrong"));
   }
 
-  #[tokio::test]
-  async fn test_query_stale() {
-    let mut query = Query::new(|| async { Ok::<_, String>(42) }).with_stale_time(Duration::ZERO);
-
-    query.fetch();
-    tokio::time::sleep(Duration::from_millis(10)).await;
-    query.poll();
-
-    // With zero stale time, should immediately be stale
-    assert!(query.is_stale());
-  }
-
   #[tokio::test]
   async fn test_fetch_while_loading_is_noop() {
     let mut query = Query::new(|| async {
@@ -365,4 +377,23 @@ mod tests {
     // Only the second fetch should have completed and been received
     assert_eq!(query.data(), Some(&1));
   }
+
+  #[tokio::test]
+  async fn test_refetch_if_older() {
+    let mut query = Query::new(|| async { Ok::<_, String>(42) });
+
+    // Nothing to refresh before the first load
+    assert!(!query.refetch_if_older(Duration::ZERO));
+
+    query.fetch();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    query.poll();
+
+    assert!(!query.refetch_if_older(Duration::from_secs(60)));
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(query.refetch_if_older(Duration::from_millis(5)));
+    assert!(query.is_refetching());
+    // Already loading
+    assert!(!query.refetch_if_older(Duration::ZERO));
+  }
 }
@@ -0,0 +1,80 @@
+use crate::jira::types::IssueSummary;
+use crate::ui::renderfns::format_timestamp;
+use serde::{Deserialize, Deserializer};
+
+/// A field shown as a column in issue lists (and written by `:export`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListColumn {
+  Key,
+  Status,
+  Type,
+  Assignee,
+  Priority,
+  Epic,
+  Updated,
+  Summary,
+  /// A custom field from `jira.custom_fields`, by its configured name
+  Custom(String),
+}
+
+impl<'de> Deserialize<'de> for ListColumn {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    Ok(match name.to_lowercase().as_str() {
+      "key" => ListColumn::Key,
+      "status" => ListColumn::Status,
+      "type" => ListColumn::Type,
+      "assignee" => ListColumn::Assignee,
+      "priority" => ListColumn::Priority,
+      "epic" => ListColumn::Epic,
+      "updated" => ListColumn::Updated,
+      "summary" => ListColumn::Summary,
+      _ => ListColumn::Custom(name),
+    })
+  }
+}
+
+impl ListColumn {
+  /// Columns shown when none are configured
+  pub const DEFAULT: &'static [ListColumn] =
+    &[ListColumn::Key, ListColumn::Status, ListColumn::Summary];
+
+  pub fn header(&self) -> &str {
+    match self {
+      ListColumn::Key => "Key",
+      ListColumn::Status => "Status",
+      ListColumn::Type => "Type",
+      ListColumn::Assignee => "Assignee",
+      ListColumn::Priority => "Priority",
+      ListColumn::Epic => "Epic",
+      ListColumn::Updated => "Updated",
+      ListColumn::Summary => "Summary",
+      ListColumn::Custom(name) => name,
+    }
+  }
+
+  /// Display width in the list; `None` takes the remaining space
+  pub fn width(&self) -> Option<usize> {
+    match self {
+      ListColumn::Key | ListColumn::Status | ListColumn::Assignee => Some(15),
+      ListColumn::Type | ListColumn::Priority | ListColumn::Epic => Some(10),
+      ListColumn::Updated => Some(16),
+      ListColumn::Custom(_) => Some(12),
+      ListColumn::Summary => None,
+    }
+  }
+
+  pub fn value(&self, issue: &IssueSummary) -> String {
+    match self {
+      ListColumn::Key => issue.key.clone(),
+      ListColumn::Status => issue.status.clone(),
+      ListColumn::Type => issue.issue_type.clone(),
+      ListColumn::Assignee => issue.assignee.clone().unwrap_or_default(),
+      ListColumn::Priority => issue.priority.clone().unwrap_or_default(),
+      ListColumn::Epic => issue.epic.clone().unwrap_or_default(),
+      ListColumn::Updated => format_timestamp(&issue.updated),
+      ListColumn::Summary => issue.summary.clone(),
+      ListColumn::Custom(name) => issue.custom_field(name).unwrap_or_default().to_string(),
+    }
+  }
+}
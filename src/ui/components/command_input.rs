@@ -1,25 +1,63 @@
 use super::input::{InputResult, TextInput};
 use super::KeyResult;
-use crate::commands::{self, Command};
+use crate::commands::{self, Alias, ArgKind};
+use crate::ui::skin;
+use crate::ui::{Action, Keymap};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use std::collections::HashMap;
 
 /// Events emitted by command input that parent needs to handle
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CommandEvent {
-  /// Command submitted
+  /// Command mode opened; a good time to refresh history and completions
+  Activated,
+  /// Command submitted (name resolved, argument as typed)
   Submitted(String),
   /// Command cancelled
   Cancelled,
 }
 
-/// Command input component with autocomplete
+/// An autocomplete entry: the text to insert and what it stands for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+  pub value: String,
+  pub description: String,
+}
+
+impl Suggestion {
+  pub fn new(value: impl Into<String>, description: impl Into<String>) -> Self {
+    Self {
+      value: value.into(),
+      description: description.into(),
+    }
+  }
+}
+
+/// Command input component with autocomplete and history
 #[derive(Debug, Clone, Default)]
 pub struct CommandInput {
   input: TextInput,
   active: bool,
   selected_suggestion: usize,
+  /// Tab opened the suggestion list before anything was typed
+  listing: bool,
+
+  /// User-defined commands from the config
+  aliases: Vec<Alias>,
+  /// Argument candidates by kind (cached boards, projects, epics)
+  completions: HashMap<ArgKind, Vec<Suggestion>>,
+  /// Argument typed before Tab started cycling through completions
+  completing: Option<String>,
+
+  /// Previous commands, oldest first
+  history: Vec<String>,
+  /// Position while browsing history, and the input from before browsing
+  history_index: Option<usize>,
+  draft: String,
+
+  keys: Keymap,
 }
 
 impl CommandInput {
@@ -41,12 +79,80 @@ impl CommandInput {
   pub fn activate(&mut self) {
     self.active = true;
     self.input.clear();
+    self.reset_navigation();
+  }
+
+  /// Set previous commands, oldest first
+  pub fn set_history(&mut self, history: Vec<String>) {
+    self.history = history;
+    self.history_index = None;
+  }
+
+  /// Set the key bindings (for the activation key)
+  pub fn set_keymap(&mut self, keys: &Keymap) {
+    self.keys = keys.clone();
+  }
+
+  /// Set the user-defined commands offered next to the built-in ones
+  pub fn set_aliases(&mut self, aliases: Vec<Alias>) {
+    self.aliases = aliases;
+  }
+
+  /// Set the argument candidates for a kind of argument
+  pub fn set_completions(&mut self, kind: ArgKind, candidates: Vec<Suggestion>) {
+    self.completions.insert(kind, candidates);
+  }
+
+  fn reset_navigation(&mut self) {
     self.selected_suggestion = 0;
+    self.listing = false;
+    self.completing = None;
+    self.history_index = None;
+  }
+
+  /// Whether the suggestion list is shown: once something is typed or Tab
+  /// is pressed, and not while browsing history
+  fn suggestions_open(&self) -> bool {
+    self.history_index.is_none() && (self.listing || !self.input.value().trim().is_empty())
   }
 
-  /// Get autocomplete suggestions for current input
-  pub fn suggestions(&self) -> Vec<&'static Command> {
-    commands::get_suggestions(self.input.value())
+  /// Get autocomplete suggestions: command names while typing the first
+  /// word, argument candidates after it
+  pub fn suggestions(&self) -> Vec<Suggestion> {
+    let input = self.input.value().trim_start();
+    let Some((name, arg)) = input.split_once(' ') else {
+      return commands::get_suggestions(input, &self.aliases)
+        .into_iter()
+        .map(|cmd| match &cmd.arg {
+          Some(arg) => Suggestion::new(cmd.name, format!("{} {}", cmd.description, arg.usage)),
+          None => Suggestion::new(cmd.name, cmd.description),
+        })
+        .collect();
+    };
+
+    let Some(kind) = commands::get_suggestions(name, &self.aliases)
+      .first()
+      .and_then(|cmd| cmd.arg.as_ref())
+      .map(|arg| arg.kind)
+    else {
+      return Vec::new();
+    };
+    let Some(candidates) = self.completions.get(&kind) else {
+      return Vec::new();
+    };
+
+    let arg = self.completing.as_deref().unwrap_or(arg).trim();
+    if arg.is_empty() {
+      return candidates.clone();
+    }
+    let mut matches: Vec<(&Suggestion, u32)> = candidates
+      .iter()
+      .filter_map(|c| {
+        commands::match_rank(arg, &c.value, &[c.description.as_str()]).map(|rank| (c, rank))
+      })
+      .collect();
+    matches.sort_by_key(|(_, rank)| *rank);
+    matches.into_iter().map(|(c, _)| c.clone()).collect()
   }
 
   /// Get the selected suggestion index
@@ -59,9 +165,9 @@ impl CommandInput {
   pub fn handle_key(&mut self, key: KeyEvent) -> KeyResult<CommandEvent> {
     // If not active, check for activation key
     if !self.active {
-      if key.code == KeyCode::Char(':') {
+      if self.keys.is(Action::Command, &key) {
         self.activate();
-        return KeyResult::Handled;
+        return KeyResult::Event(CommandEvent::Activated);
       }
       return KeyResult::NotHandled;
     }
@@ -71,32 +177,44 @@ impl CommandInput {
       KeyCode::Esc => {
         self.active = false;
         self.input.clear();
-        self.selected_suggestion = 0;
+        self.reset_navigation();
         return KeyResult::Event(CommandEvent::Cancelled);
       }
       KeyCode::Enter => {
-        self.active = false;
-        let cmd = self.resolve_command();
-        self.input.clear();
-        self.selected_suggestion = 0;
-        return KeyResult::Event(CommandEvent::Submitted(cmd));
+        return match self.resolve_command() {
+          Some(cmd) => {
+            self.active = false;
+            self.input.clear();
+            self.reset_navigation();
+            KeyResult::Event(CommandEvent::Submitted(cmd))
+          }
+          None => KeyResult::Handled,
+        };
+      }
+      KeyCode::Tab => {
+        self.cycle_suggestion(1);
+        return KeyResult::Handled;
+      }
+      KeyCode::BackTab => {
+        self.cycle_suggestion(-1);
+        return KeyResult::Handled;
+      }
+      // Up/Down move through the suggestions while they are shown,
+      // through the history otherwise
+      KeyCode::Up if self.suggestions_open() => {
+        self.step_selection(-1);
+        return KeyResult::Handled;
+      }
+      KeyCode::Down if self.suggestions_open() => {
+        self.step_selection(1);
+        return KeyResult::Handled;
       }
-      KeyCode::Tab | KeyCode::Down => {
-        let suggestions = self.suggestions();
-        if !suggestions.is_empty() {
-          self.selected_suggestion = (self.selected_suggestion + 1) % suggestions.len();
-        }
+      KeyCode::Up => {
+        self.browse_history(-1);
         return KeyResult::Handled;
       }
-      KeyCode::BackTab | KeyCode::Up => {
-        let suggestions = self.suggestions();
-        if !suggestions.is_empty() {
-          self.selected_suggestion = if self.selected_suggestion == 0 {
-            suggestions.len() - 1
-          } else {
-            self.selected_suggestion - 1
-          };
-        }
+      KeyCode::Down => {
+        self.browse_history(1);
         return KeyResult::Handled;
       }
       _ => {}
@@ -105,7 +223,7 @@ impl CommandInput {
     // Delegate to TextInput for text editing
     match self.input.handle_key(key) {
       InputResult::Consumed => {
-        self.selected_suggestion = 0; // Reset on input change
+        self.reset_navigation(); // Reset on input change
         KeyResult::Handled
       }
       InputResult::Submitted(_) | InputResult::Cancelled => {
@@ -116,13 +234,106 @@ impl CommandInput {
     }
   }
 
-  /// Resolve the final command (from suggestion or direct input)
-  fn resolve_command(&self) -> String {
-    let suggestions = self.suggestions();
-    if !suggestions.is_empty() && self.selected_suggestion < suggestions.len() {
-      suggestions[self.selected_suggestion].name.to_string()
+  /// Move the suggestion selection. While typing an argument this also
+  /// fills the selected candidate into the input, like shell completion.
+  fn cycle_suggestion(&mut self, direction: i32) {
+    if !self.suggestions_open() {
+      // The first Tab only opens the list
+      self.listing = true;
+      self.history_index = None;
+      self.selected_suggestion = 0;
+      return;
+    }
+    let input = self.input.value().trim_start().to_string();
+    let completing_arg = input.split_once(' ');
+    if let Some((_, arg)) = completing_arg {
+      if self.completing.is_none() {
+        self.completing = Some(arg.to_string());
+        // The first Tab picks the best match
+        self.selected_suggestion = 0;
+      } else {
+        self.step_selection(direction);
+      }
     } else {
-      self.input.value().trim().to_lowercase()
+      self.step_selection(direction);
+    }
+
+    if let Some((name, _)) = completing_arg {
+      if let Some(suggestion) = self.suggestions().get(self.selected_suggestion) {
+        let value = format!("{} {}", name, suggestion.value);
+        self.input.set_value(&value);
+      }
+    }
+  }
+
+  fn step_selection(&mut self, direction: i32) {
+    let len = self.suggestions().len();
+    if len == 0 {
+      return;
+    }
+    self.selected_suggestion = if direction > 0 {
+      (self.selected_suggestion + 1) % len
+    } else if self.selected_suggestion == 0 {
+      len - 1
+    } else {
+      self.selected_suggestion - 1
+    };
+  }
+
+  /// Step through previous commands (-1 = older, 1 = newer)
+  fn browse_history(&mut self, direction: i32) {
+    if self.history.is_empty() {
+      return;
+    }
+    let last = self.history.len() - 1;
+    let index = match (self.history_index, direction < 0) {
+      (None, true) => {
+        self.draft = self.input.value().to_string();
+        Some(last)
+      }
+      (None, false) => return,
+      (Some(i), true) => Some(i.saturating_sub(1)),
+      (Some(i), false) if i < last => Some(i + 1),
+      (Some(_), false) => None,
+    };
+
+    let value = match index {
+      Some(i) => self.history[i].clone(),
+      None => std::mem::take(&mut self.draft),
+    };
+    self.input.set_value(&value);
+    self.selected_suggestion = 0;
+    self.listing = false;
+    self.completing = None;
+    self.history_index = index;
+  }
+
+  /// Resolve the final command (from suggestion or direct input),
+  /// keeping any argument as typed.
+  ///
+  /// Returns None, with the input completed, when the chosen command still
+  /// needs an argument.
+  fn resolve_command(&mut self) -> Option<String> {
+    let input = self.input.value().trim().to_string();
+    let (name, arg) = input.split_once(' ').unwrap_or((&input, ""));
+    let arg = arg.trim();
+
+    if !arg.is_empty() {
+      return Some(format!("{} {}", name.to_lowercase(), arg));
+    }
+
+    let command = commands::get_suggestions(name, &self.aliases)
+      .into_iter()
+      .nth(self.selected_suggestion)
+      .filter(|_| !name.is_empty() || self.listing);
+    match command {
+      Some(cmd) if cmd.arg.as_ref().is_some_and(|a| a.required) => {
+        self.input.set_value(&format!("{} ", cmd.name));
+        self.reset_navigation();
+        None
+      }
+      Some(cmd) => Some(cmd.name.to_string()),
+      None => Some(name.to_lowercase()),
     }
   }
 
@@ -132,7 +343,11 @@ impl CommandInput {
       return;
     }
 
-    let suggestions = self.suggestions();
+    let suggestions = if self.suggestions_open() {
+      self.suggestions()
+    } else {
+      Vec::new()
+    };
 
     // Calculate overlay dimensions
     let width = (area.width * 60 / 100).min(60).max(30);
@@ -155,7 +370,7 @@ impl CommandInput {
     // Draw the border/block
     let block = Block::default()
       .borders(Borders::ALL)
-      .border_style(Style::default().fg(Color::Yellow))
+      .border_style(skin().border_style(true))
       .title(" Command ");
 
     let inner = block.inner(overlay_area);
@@ -176,9 +391,9 @@ impl CommandInput {
 
     // Draw input line
     let input_line = Line::from(vec![
-      Span::styled(":", Style::default().fg(Color::Yellow)),
+      Span::styled(":", Style::default().fg(skin().text.highlight)),
       Span::raw(self.input.value()),
-      Span::styled("_", Style::default().fg(Color::Yellow)), // Cursor
+      Span::styled("_", Style::default().fg(skin().text.highlight)), // Cursor
     ]);
     let input_para = Paragraph::new(input_line);
     frame.render_widget(input_para, chunks[0]);
@@ -187,21 +402,22 @@ impl CommandInput {
     if !suggestions.is_empty() && chunks[1].height > 0 {
       let items: Vec<ListItem> = suggestions
         .iter()
-        .take(8)
-        .map(|cmd| {
+        .map(|suggestion| {
           let line = Line::from(vec![
             Span::styled(
-              format!("{:<12}", cmd.name),
-              Style::default().fg(Color::Cyan),
+              format!("{:<12} ", suggestion.value),
+              Style::default().fg(skin().text.accent),
+            ),
+            Span::styled(
+              suggestion.description.as_str(),
+              Style::default().fg(skin().text.dim),
             ),
-            Span::styled(cmd.description, Style::default().fg(Color::DarkGray)),
           ]);
           ListItem::new(line)
         })
         .collect();
 
-      let list =
-        List::new(items).highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White));
+      let list = List::new(items).highlight_style(skin().selection_style());
 
       let mut state = ListState::default();
       state.select(Some(self.selected_suggestion));
@@ -210,3 +426,94 @@ impl CommandInput {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crossterm::event::KeyModifiers;
+
+  fn press(input: &mut CommandInput, code: KeyCode) -> KeyResult<CommandEvent> {
+    input.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+  }
+
+  fn type_str(input: &mut CommandInput, s: &str) {
+    for c in s.chars() {
+      press(input, KeyCode::Char(c));
+    }
+  }
+
+  #[test]
+  fn test_argument_completion_and_submit() {
+    let mut input = CommandInput::new();
+    input.set_completions(
+      ArgKind::Board,
+      vec![
+        Suggestion::new("12", "Team A"),
+        Suggestion::new("34", "Team B"),
+      ],
+    );
+    press(&mut input, KeyCode::Char(':'));
+    type_str(&mut input, "board team b");
+    assert_eq!(input.suggestions()[0].value, "34");
+
+    press(&mut input, KeyCode::Tab);
+    assert_eq!(input.selected_suggestion(), 0);
+    assert_eq!(input.value(), "board 34");
+    assert_eq!(
+      press(&mut input, KeyCode::Enter),
+      KeyResult::Event(CommandEvent::Submitted("board 34".to_string()))
+    );
+  }
+
+  #[test]
+  fn test_required_argument_is_prompted() {
+    let mut input = CommandInput::new();
+    press(&mut input, KeyCode::Char(':'));
+    type_str(&mut input, "board");
+    // "board" needs an argument, so Enter completes the name instead
+    assert_eq!(press(&mut input, KeyCode::Enter), KeyResult::Handled);
+    assert_eq!(input.value(), "board ");
+    assert!(input.is_active());
+  }
+
+  #[test]
+  fn test_history_navigation() {
+    let mut input = CommandInput::new();
+    input.set_history(vec!["issues".to_string(), "board 12".to_string()]);
+    press(&mut input, KeyCode::Char(':'));
+
+    press(&mut input, KeyCode::Up);
+    assert_eq!(input.value(), "board 12");
+    press(&mut input, KeyCode::Up);
+    assert_eq!(input.value(), "issues");
+    press(&mut input, KeyCode::Up);
+    assert_eq!(input.value(), "issues");
+    press(&mut input, KeyCode::Down);
+    assert_eq!(input.value(), "board 12");
+    press(&mut input, KeyCode::Down);
+    assert_eq!(input.value(), "");
+  }
+
+  #[test]
+  fn test_up_down_move_through_open_suggestions() {
+    let mut input = CommandInput::new();
+    input.set_history(vec!["issues".to_string()]);
+    press(&mut input, KeyCode::Char(':'));
+    type_str(&mut input, "ep");
+
+    // Typing opened the list, so the history is left alone
+    press(&mut input, KeyCode::Down);
+    assert_eq!(input.selected_suggestion(), 1);
+    assert_eq!(input.value(), "ep");
+    press(&mut input, KeyCode::Up);
+    assert_eq!(input.selected_suggestion(), 0);
+
+    // Tab opens the list on an empty palette too
+    press(&mut input, KeyCode::Esc);
+    press(&mut input, KeyCode::Char(':'));
+    press(&mut input, KeyCode::Tab);
+    press(&mut input, KeyCode::Down);
+    assert_eq!(input.selected_suggestion(), 1);
+    assert_eq!(input.value(), "");
+  }
+}
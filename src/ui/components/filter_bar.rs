@@ -1,7 +1,9 @@
 use super::filter_source::FilterSource;
 use super::KeyResult;
 use crate::ui::renderfns::truncate;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::ui::skin;
+use crate::ui::{Action, Keymap};
+use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 use std::marker::PhantomData;
@@ -26,6 +28,7 @@ where
   field: F,
   values: Vec<Option<String>>, // None = unassigned
   selected: usize,             // 0 = All, 1+ = index into values
+  keys: Keymap,
   _phantom: PhantomData<T>,
 }
 
@@ -48,10 +51,16 @@ where
       field: F::default(),
       values: Vec::new(),
       selected: 0,
+      keys: Keymap::default(),
       _phantom: PhantomData,
     }
   }
 
+  /// Set the key bindings
+  pub fn set_keymap(&mut self, keys: &Keymap) {
+    self.keys = keys.clone();
+  }
+
   /// Check if filter bar is currently active
   pub fn is_active(&self) -> bool {
     self.active
@@ -109,12 +118,12 @@ where
       return KeyResult::NotHandled;
     }
 
-    match key.code {
-      KeyCode::PageUp => {
+    match self.keys.action(&key) {
+      Some(Action::PrevFilterTab) => {
         self.navigate(-1);
         KeyResult::Event(FilterBarEvent::SelectionChanged)
       }
-      KeyCode::PageDown => {
+      Some(Action::NextFilterTab) => {
         self.navigate(1);
         KeyResult::Event(FilterBarEvent::SelectionChanged)
       }
@@ -151,25 +160,29 @@ where
     // Show current filter field name
     spans.push(Span::styled(
       format!("[{}] ", self.field.label()),
-      Style::default().fg(Color::Yellow),
+      Style::default().fg(skin().text.highlight),
     ));
 
     // "All" tab (index 0)
     let all_style = if self.selected == 0 {
-      Style::default().fg(Color::Black).bg(Color::Cyan)
+      Style::default()
+        .fg(skin().selection.tab_fg)
+        .bg(skin().selection.tab_bg)
     } else {
-      Style::default().fg(Color::Gray)
+      Style::default().fg(skin().selection.inactive_tab)
     };
     spans.push(Span::styled(" All ", all_style));
 
     // Individual filter tabs
     for (idx, value) in self.values.iter().enumerate() {
-      spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
+      spans.push(Span::styled("│", Style::default().fg(skin().text.dim)));
       let is_selected = self.selected == idx + 1;
       let style = if is_selected {
-        Style::default().fg(Color::Black).bg(Color::Cyan)
+        Style::default()
+          .fg(skin().selection.tab_fg)
+          .bg(skin().selection.tab_bg)
       } else {
-        Style::default().fg(Color::Gray)
+        Style::default().fg(skin().selection.inactive_tab)
       };
       let label = match value {
         Some(v) => format!(" {} ", truncate(v, 15)),
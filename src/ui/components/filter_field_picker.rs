@@ -1,6 +1,8 @@
 use super::filter_source::FilterSource;
 use super::KeyResult;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::ui::skin;
+use crate::ui::{Action, Keymap};
+use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
 use std::marker::PhantomData;
@@ -25,7 +27,9 @@ where
 {
   active: bool,
   selected: usize,
-  _phantom: PhantomData<(F, T)>,
+  fields: Vec<F>,
+  keys: Keymap,
+  _phantom: PhantomData<T>,
 }
 
 impl<F, T> Default for FilterFieldPicker<F, T>
@@ -45,10 +49,22 @@ where
     Self {
       active: false,
       selected: 0,
+      fields: F::all_variants().to_vec(),
+      keys: Keymap::default(),
       _phantom: PhantomData,
     }
   }
 
+  /// Set the fields to choose from (all variants by default)
+  pub fn set_fields(&mut self, fields: Vec<F>) {
+    self.fields = fields;
+  }
+
+  /// Set the key bindings
+  pub fn set_keymap(&mut self, keys: &Keymap) {
+    self.keys = keys.clone();
+  }
+
   /// Check if picker is currently active
   pub fn is_active(&self) -> bool {
     self.active
@@ -72,32 +88,32 @@ where
       return KeyResult::NotHandled;
     }
 
-    let fields = F::all_variants();
+    let len = self.fields.len();
 
-    match key.code {
-      KeyCode::Esc | KeyCode::Char('q') => {
+    match self.keys.action(&key) {
+      Some(Action::Back) => {
         self.hide();
         KeyResult::Event(FilterFieldPickerEvent::Cancelled)
       }
-      KeyCode::Enter => {
-        if let Some(field) = fields.get(self.selected) {
+      Some(Action::OpenDetail) => {
+        if let Some(field) = self.fields.get(self.selected).cloned() {
           self.hide();
-          KeyResult::Event(FilterFieldPickerEvent::Selected(field.clone()))
+          KeyResult::Event(FilterFieldPickerEvent::Selected(field))
         } else {
           self.hide();
           KeyResult::Event(FilterFieldPickerEvent::Cancelled)
         }
       }
-      KeyCode::Char('j') | KeyCode::Down => {
-        if !fields.is_empty() {
-          self.selected = (self.selected + 1) % fields.len();
+      Some(Action::MoveDown) => {
+        if len > 0 {
+          self.selected = (self.selected + 1) % len;
         }
         KeyResult::Handled
       }
-      KeyCode::Char('k') | KeyCode::Up => {
-        if !fields.is_empty() {
+      Some(Action::MoveUp) => {
+        if len > 0 {
           self.selected = if self.selected == 0 {
-            fields.len() - 1
+            len - 1
           } else {
             self.selected - 1
           };
@@ -114,7 +130,7 @@ where
       return;
     }
 
-    let fields = F::all_variants();
+    let fields = &self.fields;
 
     // Calculate overlay dimensions
     let max_name_len = fields.iter().map(|f| f.label().len()).max().unwrap_or(10);
@@ -133,7 +149,7 @@ where
     // Draw the border/block
     let block = Block::default()
       .borders(Borders::ALL)
-      .border_style(Style::default().fg(Color::Yellow))
+      .border_style(skin().border_style(true))
       .title(" Filter By ");
 
     let inner = block.inner(overlay_area);
@@ -149,14 +165,13 @@ where
       .map(|field| {
         let line = Line::from(vec![Span::styled(
           field.label(),
-          Style::default().fg(Color::Cyan),
+          Style::default().fg(skin().text.accent),
         )]);
         ListItem::new(line)
       })
       .collect();
 
-    let list =
-      List::new(items).highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White));
+    let list = List::new(items).highlight_style(skin().selection_style());
 
     let mut state = ListState::default();
     state.select(Some(self.selected));
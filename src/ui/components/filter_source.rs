@@ -5,7 +5,7 @@
 /// issues by assignee vs by epic.
 pub trait FilterSource<T>: Clone + Default + PartialEq + 'static {
   /// Human-readable label for this filter source
-  fn label(&self) -> &'static str;
+  fn label(&self) -> &str;
 
   /// Get unique values from the list for populating filter tabs.
   /// Returns `None` values for items with missing field values.
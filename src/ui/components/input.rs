@@ -21,26 +21,23 @@ pub struct TextInput {
 }
 
 impl TextInput {
-  pub fn new() -> Self {
-    Self::default()
-  }
-
   /// Get the current input value
   pub fn value(&self) -> &str {
     &self.buffer
   }
 
-  /// Check if the input is empty
-  pub fn is_empty(&self) -> bool {
-    self.buffer.is_empty()
-  }
-
   /// Clear the input
   pub fn clear(&mut self) {
     self.buffer.clear();
     self.cursor = 0;
   }
 
+  /// Replace the input with a value, cursor at the end
+  pub fn set_value(&mut self, value: &str) {
+    self.buffer = value.to_string();
+    self.cursor = self.buffer.len();
+  }
+
   /// Handle a key event, returning the result
   pub fn handle_key(&mut self, key: KeyEvent) -> InputResult {
     match key.code {
@@ -128,8 +125,8 @@ mod tests {
 
   #[test]
   fn test_basic_input() {
-    let mut input = TextInput::new();
-    assert!(input.is_empty());
+    let mut input = TextInput::default();
+    assert!(input.value().is_empty());
 
     input.handle_key(key(KeyCode::Char('h')));
     input.handle_key(key(KeyCode::Char('i')));
@@ -138,7 +135,7 @@ mod tests {
 
   #[test]
   fn test_submit() {
-    let mut input = TextInput::new();
+    let mut input = TextInput::default();
     input.handle_key(key(KeyCode::Char('t')));
     input.handle_key(key(KeyCode::Char('e')));
     input.handle_key(key(KeyCode::Char('s')));
@@ -150,7 +147,7 @@ mod tests {
 
   #[test]
   fn test_cancel() {
-    let mut input = TextInput::new();
+    let mut input = TextInput::default();
     input.handle_key(key(KeyCode::Char('x')));
 
     let result = input.handle_key(key(KeyCode::Esc));
@@ -159,7 +156,7 @@ mod tests {
 
   #[test]
   fn test_backspace() {
-    let mut input = TextInput::new();
+    let mut input = TextInput::default();
     input.handle_key(key(KeyCode::Char('a')));
     input.handle_key(key(KeyCode::Char('b')));
     input.handle_key(key(KeyCode::Char('c')));
@@ -169,7 +166,7 @@ mod tests {
 
   #[test]
   fn test_cursor_movement() {
-    let mut input = TextInput::new();
+    let mut input = TextInput::default();
     input.handle_key(key(KeyCode::Char('a')));
     input.handle_key(key(KeyCode::Char('c')));
     input.handle_key(key(KeyCode::Left));
@@ -179,7 +176,7 @@ mod tests {
 
   #[test]
   fn test_ctrl_u_clear_before_cursor() {
-    let mut input = TextInput::new();
+    let mut input = TextInput::default();
     for c in "hello world".chars() {
       input.handle_key(key(KeyCode::Char(c)));
     }
@@ -0,0 +1,92 @@
+use super::yank_menu::{YankEvent, YankMenu, YankTarget};
+use super::KeyResult;
+use crate::browser;
+use crate::config::Config;
+use crate::git::{self, DEFAULT_BRANCH_TEMPLATE};
+use crate::jira::types::IssueSummary;
+use crate::jira::JiraClient;
+use crate::ui::{Action, Keymap};
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+
+/// What views can do with any issue: copy from it (`y`), open it in the
+/// browser (`o`) and check out a git branch for it (`B`).
+///
+/// Views pass keys to `handle_key` before their own handling, so the copy
+/// menu gets them while it is open, and show `take_message` in the footer.
+pub struct IssueActions {
+  jira: JiraClient,
+  yank: YankMenu,
+  branch_template: String,
+  message: Option<String>,
+}
+
+impl IssueActions {
+  pub fn new(jira: JiraClient) -> Self {
+    Self {
+      jira,
+      yank: YankMenu::new(),
+      branch_template: DEFAULT_BRANCH_TEMPLATE.to_string(),
+      message: None,
+    }
+  }
+
+  /// Apply the branch template and the view's key bindings
+  pub fn configure(&mut self, config: &Config, keys: &Keymap) {
+    self.branch_template = config.git.branch_template.clone();
+    self.yank.set_keymap(keys);
+  }
+
+  /// Handle a key event; returns `true` if the copy menu took it
+  pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+    match self.yank.handle_key(key) {
+      KeyResult::NotHandled => false,
+      KeyResult::Event(YankEvent::Copied(message)) => {
+        self.message = Some(message);
+        true
+      }
+      KeyResult::Handled | KeyResult::Event(YankEvent::Cancelled) => true,
+    }
+  }
+
+  /// Run an action on an issue; returns `false` for other actions
+  pub fn run(&mut self, action: Action, issue: &IssueSummary) -> bool {
+    match action {
+      Action::Copy => self.copy(&issue.key, &issue.issue_type, &issue.summary),
+      Action::OpenInBrowser => self.open(&issue.key),
+      Action::GitBranch => self.branch(&issue.key, &issue.issue_type, &issue.summary),
+      _ => return false,
+    }
+    true
+  }
+
+  /// Show the copy menu for an issue
+  pub fn copy(&mut self, key: &str, issue_type: &str, summary: &str) {
+    let branch = git::branch_name(&self.branch_template, key, issue_type, summary);
+    self
+      .yank
+      .show(YankTarget::new(key, summary, branch, &self.jira));
+  }
+
+  /// Open an issue in the browser
+  pub fn open(&mut self, key: &str) {
+    let url = self.jira.browse_url(key);
+    self.message = Some(browser::open_with_message(&url));
+  }
+
+  /// Check out the git branch for an issue
+  pub fn branch(&mut self, key: &str, issue_type: &str, summary: &str) {
+    let name = git::branch_name(&self.branch_template, key, issue_type, summary);
+    self.message = Some(git::checkout_with_message(&name));
+  }
+
+  /// Render the copy menu if open
+  pub fn render_overlay(&self, frame: &mut Frame, area: Rect) {
+    self.yank.render_overlay(frame, area);
+  }
+
+  /// Result of the last action, for the footer
+  pub fn take_message(&mut self) -> Option<String> {
+    self.message.take()
+  }
+}
@@ -3,7 +3,7 @@ use crate::jira::types::IssueSummary;
 use std::collections::BTreeSet;
 
 /// Field to filter issues by
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum IssueFilterField {
   #[default]
   None,
@@ -11,9 +11,20 @@ pub enum IssueFilterField {
   Epic,
   Status,
   Priority,
+  /// A custom field from `jira.custom_fields`, by its configured name
+  Custom(String),
 }
 
 impl IssueFilterField {
+  /// The built-in fields followed by the configured custom fields
+  pub fn with_custom(custom_fields: &[String]) -> Vec<Self> {
+    Self::all_variants()
+      .iter()
+      .cloned()
+      .chain(custom_fields.iter().cloned().map(IssueFilterField::Custom))
+      .collect()
+  }
+
   /// Extract the value of this filter field from an issue
   fn extract(&self, issue: &IssueSummary) -> Option<String> {
     match self {
@@ -22,18 +33,20 @@ impl IssueFilterField {
       IssueFilterField::Epic => issue.epic.clone(),
       IssueFilterField::Status => Some(issue.status.clone()),
       IssueFilterField::Priority => issue.priority.clone(),
+      IssueFilterField::Custom(name) => issue.custom_field(name).map(String::from),
     }
   }
 }
 
 impl FilterSource<IssueSummary> for IssueFilterField {
-  fn label(&self) -> &'static str {
+  fn label(&self) -> &str {
     match self {
       IssueFilterField::None => "None",
       IssueFilterField::Assignee => "Assignee",
       IssueFilterField::Epic => "Epic",
       IssueFilterField::Status => "Status",
       IssueFilterField::Priority => "Priority",
+      IssueFilterField::Custom(name) => name,
     }
   }
 
@@ -93,11 +106,11 @@ impl FilterSource<IssueSummary> for IssueFilterField {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::jira::types::test_issue;
 
   fn test_issues() -> Vec<IssueSummary> {
     vec![
       IssueSummary {
-        key: "TEST-1".to_string(),
         summary: "First issue".to_string(),
         status: "To Do".to_string(),
         status_id: "1".to_string(),
@@ -106,28 +119,26 @@ mod tests {
         priority: Some("High".to_string()),
         epic: Some("Epic-1".to_string()),
         updated: "2024-01-01".to_string(),
+        ..test_issue("TEST-1")
       },
       IssueSummary {
-        key: "TEST-2".to_string(),
         summary: "Second issue".to_string(),
         status: "In Progress".to_string(),
         status_id: "2".to_string(),
         issue_type: "Task".to_string(),
         assignee: Some("Bob".to_string()),
         priority: Some("Low".to_string()),
-        epic: None,
         updated: "2024-01-02".to_string(),
+        ..test_issue("TEST-2")
       },
       IssueSummary {
-        key: "TEST-3".to_string(),
         summary: "Third issue".to_string(),
         status: "To Do".to_string(),
         status_id: "1".to_string(),
         issue_type: "Task".to_string(),
-        assignee: None,
-        priority: None,
         epic: Some("Epic-1".to_string()),
         updated: "2024-01-03".to_string(),
+        ..test_issue("TEST-3")
       },
     ]
   }
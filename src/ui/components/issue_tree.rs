@@ -0,0 +1,182 @@
+use crate::jira::types::IssueSummary;
+use std::collections::{HashMap, HashSet};
+
+/// A row of the tree: an issue at its depth
+#[derive(Debug, Clone, Copy)]
+pub struct TreeRow<'a> {
+  pub issue: &'a IssueSummary,
+  pub depth: usize,
+  /// Whether any loaded issue is under this one
+  pub has_children: bool,
+}
+
+/// Expansion state of a tree of issues.
+///
+/// The tree itself comes from the issues: each one is shown under its
+/// parent (or epic) when that is loaded too, and at the top otherwise.
+/// Children are loaded lazily, the first time an issue is expanded.
+#[derive(Debug, Clone, Default)]
+pub struct IssueTree {
+  expanded: HashSet<String>,
+  loaded: HashSet<String>,
+}
+
+impl IssueTree {
+  pub fn is_expanded(&self, key: &str) -> bool {
+    self.expanded.contains(key)
+  }
+
+  /// Expand an issue. Returns true when its children still need loading.
+  pub fn expand(&mut self, key: &str) -> bool {
+    self.expanded.insert(key.to_string());
+    !self.loaded.contains(key)
+  }
+
+  pub fn collapse(&mut self, key: &str) {
+    self.expanded.remove(key);
+  }
+
+  /// Record that the children of an issue have been loaded
+  pub fn set_loaded(&mut self, key: &str) {
+    self.loaded.insert(key.to_string());
+  }
+
+  /// Whether the children of an issue have been loaded (there may be none)
+  pub fn is_loaded(&self, key: &str) -> bool {
+    self.loaded.contains(key)
+  }
+
+  /// The visible rows, depth first in the order of `items`.
+  ///
+  /// An issue is shown when it matches, or when something under it does so
+  /// the match keeps its context. With `searching`, collapsed issues are
+  /// searched too; otherwise only expanded issues show their children.
+  pub fn rows<'a>(
+    &self,
+    items: &'a [IssueSummary],
+    matches: impl Fn(&IssueSummary) -> bool,
+    searching: bool,
+  ) -> Vec<TreeRow<'a>> {
+    let keys: HashSet<&str> = items.iter().map(|i| i.key.as_str()).collect();
+    let mut children: HashMap<&str, Vec<&'a IssueSummary>> = HashMap::new();
+    let mut roots = Vec::new();
+    for item in items {
+      match item.tree_parent().filter(|p| keys.contains(p)) {
+        Some(parent) => children.entry(parent).or_default().push(item),
+        None => roots.push(item),
+      }
+    }
+
+    let mut rows = Vec::new();
+    for root in roots {
+      self.visit(root, 0, &children, &matches, searching, &mut rows);
+    }
+    rows
+  }
+
+  /// Add the rows of an issue and what's under it; returns whether any
+  /// were added
+  fn visit<'a>(
+    &self,
+    issue: &'a IssueSummary,
+    depth: usize,
+    children: &HashMap<&str, Vec<&'a IssueSummary>>,
+    matches: &impl Fn(&IssueSummary) -> bool,
+    searching: bool,
+    rows: &mut Vec<TreeRow<'a>>,
+  ) -> bool {
+    let under = children.get(issue.key.as_str());
+    let at = rows.len();
+    rows.push(TreeRow {
+      issue,
+      depth,
+      has_children: under.is_some(),
+    });
+
+    let mut shown_under = false;
+    if searching || self.is_expanded(&issue.key) {
+      for child in under.into_iter().flatten() {
+        shown_under |= self.visit(child, depth + 1, children, matches, searching, rows);
+      }
+    }
+
+    if matches(issue) || shown_under {
+      true
+    } else {
+      rows.truncate(at);
+      false
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::jira::types::test_issue;
+
+  fn issue(key: &str, parent: Option<&str>, epic: Option<&str>) -> IssueSummary {
+    IssueSummary {
+      summary: format!("{} summary", key),
+      epic: epic.map(String::from),
+      parent: parent.map(String::from),
+      ..test_issue(key)
+    }
+  }
+
+  fn keys(rows: &[TreeRow]) -> Vec<(String, usize)> {
+    rows
+      .iter()
+      .map(|r| (r.issue.key.clone(), r.depth))
+      .collect()
+  }
+
+  fn items() -> Vec<IssueSummary> {
+    vec![
+      issue("I-1", None, None),
+      issue("E-1", Some("I-1"), None),
+      issue("S-1", None, Some("E-1")),
+      issue("T-1", Some("S-1"), None),
+      issue("E-2", None, None),
+      // Parent not loaded: shown at the top
+      issue("S-2", Some("X-9"), None),
+    ]
+  }
+
+  #[test]
+  fn test_rows_follow_expansion() {
+    let items = items();
+    let mut tree = IssueTree::default();
+    let all = |_: &IssueSummary| true;
+    assert_eq!(
+      keys(&tree.rows(&items, all, false)),
+      [("I-1", 0), ("E-2", 0), ("S-2", 0)].map(|(k, d)| (k.to_string(), d))
+    );
+
+    assert!(tree.expand("I-1"));
+    tree.set_loaded("I-1");
+    assert!(!tree.expand("I-1"));
+    tree.expand("E-1");
+    let rows = tree.rows(&items, all, false);
+    assert_eq!(
+      keys(&rows),
+      [("I-1", 0), ("E-1", 1), ("S-1", 2), ("E-2", 0), ("S-2", 0)].map(|(k, d)| (k.to_string(), d))
+    );
+    assert!(rows[2].has_children);
+    assert!(!rows[3].has_children);
+
+    tree.collapse("I-1");
+    assert_eq!(tree.rows(&items, all, false).len(), 3);
+  }
+
+  #[test]
+  fn test_search_keeps_ancestors() {
+    let items = items();
+    let tree = IssueTree::default();
+    let rows = tree.rows(&items, |i| i.key == "T-1", true);
+    assert_eq!(
+      keys(&rows),
+      [("I-1", 0), ("E-1", 1), ("S-1", 2), ("T-1", 3)].map(|(k, d)| (k.to_string(), d))
+    );
+    assert!(tree.rows(&items, |_| false, true).is_empty());
+  }
+}
@@ -3,18 +3,24 @@ mod filter_bar;
 mod filter_field_picker;
 mod filter_source;
 mod input;
+mod issue_actions;
 mod issue_filters;
+mod issue_tree;
 mod key_result;
+mod prompt_input;
 mod search_input;
 mod status_picker;
 mod ticket_panel;
+mod yank_menu;
 
-pub use command_input::{CommandEvent, CommandInput};
+pub use command_input::{CommandEvent, CommandInput, Suggestion};
 pub use filter_bar::{FilterBar, FilterBarEvent};
 pub use filter_field_picker::{FilterFieldPicker, FilterFieldPickerEvent};
 pub use filter_source::FilterSource;
+pub use issue_actions::IssueActions;
 pub use issue_filters::IssueFilterField;
 pub use key_result::KeyResult;
+pub use prompt_input::{PromptEvent, PromptInput};
 pub use search_input::{SearchEvent, SearchInput};
 pub use status_picker::{StatusPicker, StatusPickerEvent};
 pub use ticket_panel::{TicketPanel, TicketPanelEvent};
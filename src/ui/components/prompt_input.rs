@@ -0,0 +1,100 @@
+use super::input::{InputResult, TextInput};
+use super::KeyResult;
+use crate::ui::skin;
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+/// Events emitted by the prompt that parent needs to handle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptEvent {
+  /// Value submitted with Enter
+  Submitted(String),
+  /// Prompt dismissed with Escape
+  Cancelled,
+}
+
+/// Single-line text prompt overlay (e.g. for comments or field edits)
+///
+/// Unlike SearchInput it has no activation key; the parent opens it with
+/// `show()`.
+#[derive(Debug, Clone, Default)]
+pub struct PromptInput {
+  input: TextInput,
+  title: String,
+  active: bool,
+}
+
+impl PromptInput {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Show the prompt with a title and initial value
+  pub fn show(&mut self, title: impl Into<String>, initial: &str) {
+    self.title = title.into();
+    self.input.set_value(initial);
+    self.active = true;
+  }
+
+  /// Handle a key event while shown
+  pub fn handle_key(&mut self, key: KeyEvent) -> KeyResult<PromptEvent> {
+    if !self.active {
+      return KeyResult::NotHandled;
+    }
+
+    match self.input.handle_key(key) {
+      InputResult::Submitted(value) => {
+        self.active = false;
+        self.input.clear();
+        KeyResult::Event(PromptEvent::Submitted(value))
+      }
+      InputResult::Cancelled => {
+        self.active = false;
+        self.input.clear();
+        KeyResult::Event(PromptEvent::Cancelled)
+      }
+      // Swallow everything else so keys don't leak to the view
+      InputResult::Consumed | InputResult::NotHandled => KeyResult::Handled,
+    }
+  }
+
+  /// Render the prompt overlay if shown
+  pub fn render_overlay(&self, frame: &mut Frame, area: Rect) {
+    if !self.active {
+      return;
+    }
+
+    let width = (area.width * 80 / 100).clamp(30, 80);
+    let height = 3;
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let overlay_area = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(true))
+      .title(format!(" {} ", self.title));
+
+    let inner = block.inner(overlay_area);
+    frame.render_widget(block, overlay_area);
+
+    if inner.height == 0 {
+      return;
+    }
+
+    // Keep the end of long values visible
+    let value = self.input.value();
+    let max = (inner.width as usize).saturating_sub(1);
+    let skip = value.chars().count().saturating_sub(max);
+    let visible: String = value.chars().skip(skip).collect();
+
+    let input_line = Line::from(vec![
+      Span::raw(visible),
+      Span::styled("_", Style::default().fg(skin().text.highlight)), // Cursor
+    ]);
+    frame.render_widget(Paragraph::new(input_line), inner);
+  }
+}
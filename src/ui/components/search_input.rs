@@ -1,6 +1,8 @@
 use super::input::{InputResult, TextInput};
 use super::KeyResult;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::ui::skin;
+use crate::ui::{Action, Keymap};
+use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
@@ -18,6 +20,7 @@ pub enum SearchEvent {
 pub struct SearchInput {
   input: TextInput,
   active: bool,
+  keys: Keymap,
 }
 
 impl SearchInput {
@@ -25,6 +28,11 @@ impl SearchInput {
     Self::default()
   }
 
+  /// Set the key bindings
+  pub fn set_keymap(&mut self, keys: &Keymap) {
+    self.keys = keys.clone();
+  }
+
   /// Check if search is currently active
   pub fn is_active(&self) -> bool {
     self.active
@@ -45,7 +53,7 @@ impl SearchInput {
   pub fn handle_key(&mut self, key: KeyEvent) -> KeyResult<SearchEvent> {
     // If not active, check for activation key
     if !self.active {
-      if key.code == KeyCode::Char('/') {
+      if self.keys.is(Action::Search, &key) {
         self.activate();
         return KeyResult::Handled;
       }
@@ -92,7 +100,7 @@ impl SearchInput {
     // Draw the border/block
     let block = Block::default()
       .borders(Borders::ALL)
-      .border_style(Style::default().fg(Color::Yellow))
+      .border_style(skin().border_style(true))
       .title(" Search ");
 
     let inner = block.inner(overlay_area);
@@ -104,9 +112,9 @@ impl SearchInput {
 
     // Draw input line
     let input_line = Line::from(vec![
-      Span::styled("/", Style::default().fg(Color::Yellow)),
+      Span::styled("/", Style::default().fg(skin().text.highlight)),
       Span::raw(self.input.value()),
-      Span::styled("_", Style::default().fg(Color::Yellow)), // Cursor
+      Span::styled("_", Style::default().fg(skin().text.highlight)), // Cursor
     ]);
     let input_para = Paragraph::new(input_line);
     frame.render_widget(input_para, inner);
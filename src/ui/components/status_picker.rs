@@ -1,6 +1,8 @@
 use super::KeyResult;
 use crate::jira::types::StatusInfo;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::ui::skin;
+use crate::ui::{Action, Keymap};
+use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
 
@@ -13,13 +15,15 @@ pub enum StatusPickerEvent {
   Cancelled,
 }
 
-/// Status picker component for selecting target status in swimlane transitions
+/// Picker for a target status in swimlane transitions, or any other choice
+/// given as id and name (e.g. the values of a select field)
 #[derive(Debug, Clone, Default)]
 pub struct StatusPicker {
   active: bool,
   statuses: Vec<StatusInfo>,
   selected: usize,
   title: String,
+  keys: Keymap,
 }
 
 impl StatusPicker {
@@ -27,6 +31,11 @@ impl StatusPicker {
     Self::default()
   }
 
+  /// Set the key bindings
+  pub fn set_keymap(&mut self, keys: &Keymap) {
+    self.keys = keys.clone();
+  }
+
   /// Check if picker is currently active
   pub fn is_active(&self) -> bool {
     self.active
@@ -53,12 +62,12 @@ impl StatusPicker {
       return KeyResult::NotHandled;
     }
 
-    match key.code {
-      KeyCode::Esc | KeyCode::Char('q') => {
+    match self.keys.action(&key) {
+      Some(Action::Back) => {
         self.hide();
         KeyResult::Event(StatusPickerEvent::Cancelled)
       }
-      KeyCode::Enter => {
+      Some(Action::OpenDetail) => {
         if let Some(status) = self.statuses.get(self.selected) {
           let id = status.id.clone();
           self.hide();
@@ -68,13 +77,13 @@ impl StatusPicker {
           KeyResult::Event(StatusPickerEvent::Cancelled)
         }
       }
-      KeyCode::Char('j') | KeyCode::Down => {
+      Some(Action::MoveDown) => {
         if !self.statuses.is_empty() {
           self.selected = (self.selected + 1) % self.statuses.len();
         }
         KeyResult::Handled
       }
-      KeyCode::Char('k') | KeyCode::Up => {
+      Some(Action::MoveUp) => {
         if !self.statuses.is_empty() {
           self.selected = if self.selected == 0 {
             self.statuses.len() - 1
@@ -116,7 +125,7 @@ impl StatusPicker {
     // Draw the border/block
     let block = Block::default()
       .borders(Borders::ALL)
-      .border_style(Style::default().fg(Color::Yellow))
+      .border_style(skin().border_style(true))
       .title(format!(" {} ", self.title));
 
     let inner = block.inner(overlay_area);
@@ -133,14 +142,13 @@ impl StatusPicker {
       .map(|status| {
         let line = Line::from(vec![Span::styled(
           &status.name,
-          Style::default().fg(Color::Cyan),
+          Style::default().fg(skin().text.accent),
         )]);
         ListItem::new(line)
       })
       .collect();
 
-    let list =
-      List::new(items).highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White));
+    let list = List::new(items).highlight_style(skin().selection_style());
 
     let mut state = ListState::default();
     state.select(Some(self.selected));
@@ -1,15 +1,18 @@
 use super::filter_bar::{FilterBar, FilterBarEvent};
 use super::filter_field_picker::{FilterFieldPicker, FilterFieldPickerEvent};
 use super::filter_source::FilterSource;
+use super::issue_tree::{IssueTree, TreeRow};
 use super::key_result::KeyResult;
 use super::search_input::{SearchEvent, SearchInput};
 use crate::jira::types::{BoardColumn, IssueSummary};
-use crate::ui::ensure_valid_selection;
-use crate::ui::renderfns::{status_color, truncate};
+use crate::ui::renderfns::truncate;
+use crate::ui::skin;
 use crate::ui::view::{ShortcutInfo, ShortcutProvider};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::ui::{ensure_valid_selection, Action, Keymap, ListColumn, RecentChanges};
+use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use std::collections::HashMap;
 
 /// Events emitted by TicketPanel that parent view needs to handle
 #[derive(Debug, Clone)]
@@ -22,10 +25,16 @@ pub enum TicketPanelEvent {
   Back,
   /// Filter selection changed
   FilterChanged,
+  /// User wants to copy from a ticket, open it in the browser or get a git
+  /// branch for it (y, o, B keys); see `IssueActions`
+  IssueAction(Action, IssueSummary),
+  /// A ticket was expanded for the first time (tree mode); its children
+  /// should be loaded
+  LoadChildren(IssueSummary),
 }
 
 /// Reusable ticket panel component combining:
-/// - List/column (swimlane) view modes
+/// - List/column (swimlane) view modes, or a tree of tickets
 /// - Filter bar with tabs
 /// - Filter field picker overlay
 /// - Search overlay
@@ -34,6 +43,8 @@ pub struct TicketPanel<F: FilterSource<IssueSummary>> {
   // View mode
   column_mode: bool,
   columns: Vec<BoardColumn>,
+  // Show tickets under their parent instead of as a flat list
+  tree: Option<IssueTree>,
 
   // Selection state
   list_state: ListState,
@@ -47,6 +58,20 @@ pub struct TicketPanel<F: FilterSource<IssueSummary>> {
   // Search
   search: SearchInput,
   search_filter: Option<String>,
+
+  // Hide issues in a done status
+  hide_done: bool,
+
+  // Rows changed by the last refresh
+  changes: RecentChanges,
+
+  // Fields shown in list mode
+  list_columns: Vec<ListColumn>,
+
+  // Extra spans in the rows of some issues, by key
+  row_details: HashMap<String, Line<'static>>,
+
+  keys: Keymap,
 }
 
 impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
@@ -55,6 +80,7 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
     Self {
       column_mode: false,
       columns,
+      tree: None,
       list_state: ListState::default(),
       column_selected: 0,
       row_selected: 0,
@@ -62,6 +88,11 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
       filter_field_picker: FilterFieldPicker::new(),
       search: SearchInput::new(),
       search_filter: None,
+      hide_done: false,
+      changes: RecentChanges::new(),
+      list_columns: ListColumn::DEFAULT.to_vec(),
+      row_details: HashMap::new(),
+      keys: Keymap::default(),
     }
   }
 
@@ -70,6 +101,22 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
     Self::new(Vec::new())
   }
 
+  /// Create a tree panel: tickets are shown under their parent or epic,
+  /// and expanding one asks for its children
+  pub fn tree() -> Self {
+    Self {
+      tree: Some(IssueTree::default()),
+      ..Self::new(Vec::new())
+    }
+  }
+
+  /// Record that the children of a ticket have been loaded (tree mode)
+  pub fn set_children_loaded(&mut self, key: &str) {
+    if let Some(tree) = &mut self.tree {
+      tree.set_loaded(key);
+    }
+  }
+
   /// Update the columns (for boards with dynamic column configuration)
   pub fn set_columns(&mut self, columns: Vec<BoardColumn>) {
     self.columns = columns;
@@ -79,6 +126,47 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
     }
   }
 
+  /// Set the fields shown in list mode
+  pub fn set_list_columns(&mut self, columns: &[ListColumn]) {
+    if !columns.is_empty() {
+      self.list_columns = columns.to_vec();
+    }
+  }
+
+  /// Set extra text shown in list mode rows, by issue key. It goes before
+  /// the summary, so it should have the same width on every row.
+  pub fn set_row_details(&mut self, details: HashMap<String, Line<'static>>) {
+    self.row_details = details;
+  }
+
+  /// Set the fields offered by the filter field picker
+  pub fn set_filter_fields(&mut self, fields: Vec<F>) {
+    self.filter_field_picker.set_fields(fields);
+  }
+
+  /// Hide or show issues in a done status
+  pub fn set_hide_done(&mut self, hide_done: bool) {
+    self.hide_done = hide_done;
+  }
+
+  /// Set the key bindings, for the panel and its overlays
+  pub fn set_keymap(&mut self, keys: &Keymap) {
+    self.keys = keys.clone();
+    self.filter_bar.set_keymap(keys);
+    self.filter_field_picker.set_keymap(keys);
+    self.search.set_keymap(keys);
+  }
+
+  /// The key bindings in effect
+  pub fn keys(&self) -> &Keymap {
+    &self.keys
+  }
+
+  /// Fields shown in list mode
+  pub fn list_columns(&self) -> &[ListColumn] {
+    &self.list_columns
+  }
+
   /// Check if column mode is available
   pub fn has_columns(&self) -> bool {
     !self.columns.is_empty()
@@ -99,6 +187,13 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
     self.filter_bar.update_values(values);
   }
 
+  /// Record loaded items so rows changed since the last load are highlighted
+  pub fn track_changes(&mut self, items: &[IssueSummary]) {
+    self
+      .changes
+      .update(items.iter().map(|i| (i.key.as_str(), i.updated.as_str())));
+  }
+
   /// Set a new filter field
   fn set_filter_field(&mut self, field: F, items: &[IssueSummary]) {
     let values = field.unique_values(items);
@@ -113,12 +208,26 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
     self.row_selected = 0;
   }
 
-  /// Get filtered items based on active filter and search
+  /// Get filtered items based on active filter and search.
+  ///
+  /// In tree mode these are the visible rows, with the ancestors of
+  /// matching tickets.
   pub fn filtered_items<'a>(&self, items: &'a [IssueSummary]) -> Vec<&'a IssueSummary> {
+    if let Some(tree) = &self.tree {
+      return self
+        .tree_rows(tree, items)
+        .into_iter()
+        .map(|row| row.issue)
+        .collect();
+    }
+
     let field = self.filter_bar.field();
 
     // First apply field filter
-    let filtered = field.filter(items, self.filter_bar.selected_value());
+    let mut filtered = field.filter(items, self.filter_bar.selected_value());
+    if self.hide_done {
+      filtered.retain(|issue| !issue.is_done());
+    }
 
     // Then apply search filter
     let Some(query) = &self.search_filter else {
@@ -127,18 +236,28 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
     let query_lower = query.to_lowercase();
     filtered
       .into_iter()
-      .filter(|issue| {
-        issue.key.to_lowercase().contains(&query_lower)
-          || issue.summary.to_lowercase().contains(&query_lower)
-          || issue.status.to_lowercase().contains(&query_lower)
-          || issue
-            .assignee
-            .as_ref()
-            .map_or(false, |a| a.to_lowercase().contains(&query_lower))
-      })
+      .filter(|issue| matches_search(issue, &query_lower))
       .collect()
   }
 
+  /// Rows of the tree, filtered like the list
+  fn tree_rows<'a>(&self, tree: &IssueTree, items: &'a [IssueSummary]) -> Vec<TreeRow<'a>> {
+    let field = self.filter_bar.field();
+    let value = self.filter_bar.selected_value();
+    let query_lower = self.search_filter.as_ref().map(|q| q.to_lowercase());
+    let matches = |issue: &IssueSummary| {
+      if self.hide_done && issue.is_done() {
+        return false;
+      }
+      !field.filter(std::slice::from_ref(issue), value).is_empty()
+        && query_lower
+          .as_deref()
+          .is_none_or(|q| matches_search(issue, q))
+    };
+    let searching = value.is_some() || query_lower.is_some();
+    tree.rows(items, matches, searching)
+  }
+
   /// Get items for a specific column (by status)
   fn items_for_column<'a>(
     &self,
@@ -240,64 +359,112 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
     key: KeyEvent,
     items: &[IssueSummary],
   ) -> Option<KeyResult<TicketPanelEvent>> {
+    let action = self.keys.action(&key)?;
     if self.column_mode {
-      match key.code {
-        KeyCode::Char('h') | KeyCode::Left => {
+      match action {
+        Action::MoveLeft => {
           self.navigate_column(-1, items);
           Some(KeyResult::Handled)
         }
-        KeyCode::Char('l') | KeyCode::Right => {
+        Action::MoveRight => {
           self.navigate_column(1, items);
           Some(KeyResult::Handled)
         }
-        KeyCode::Char('j') | KeyCode::Down => {
+        Action::MoveDown => {
           self.navigate_row(1, items);
           Some(KeyResult::Handled)
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::MoveUp => {
           self.navigate_row(-1, items);
           Some(KeyResult::Handled)
         }
         _ => None,
       }
     } else {
-      match key.code {
-        KeyCode::Char('j') | KeyCode::Down => {
+      match action {
+        Action::MoveDown => {
           self.list_state.select_next();
           Some(KeyResult::Handled)
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::MoveUp => {
           self.list_state.select_previous();
           Some(KeyResult::Handled)
         }
+        Action::MoveRight if self.tree.is_some() => Some(self.expand_selected(items)),
+        Action::MoveLeft if self.tree.is_some() => {
+          self.collapse_selected(items);
+          Some(KeyResult::Handled)
+        }
         _ => None,
       }
     }
   }
 
+  /// Expand the selected ticket, asking for its children the first time
+  fn expand_selected(&mut self, items: &[IssueSummary]) -> KeyResult<TicketPanelEvent> {
+    let Some(issue) = self.selected(items) else {
+      return KeyResult::Handled;
+    };
+    let load = self
+      .tree
+      .as_mut()
+      .is_some_and(|tree| tree.expand(&issue.key));
+    if load {
+      KeyResult::Event(TicketPanelEvent::LoadChildren(issue.clone()))
+    } else {
+      KeyResult::Handled
+    }
+  }
+
+  /// Collapse the selected ticket, or if it is collapsed, go to its parent
+  fn collapse_selected(&mut self, items: &[IssueSummary]) {
+    let Some(issue) = self.selected(items) else {
+      return;
+    };
+    if let Some(tree) = self.tree.as_mut().filter(|t| t.is_expanded(&issue.key)) {
+      tree.collapse(&issue.key);
+    } else if let Some(parent) = issue.tree_parent() {
+      let rows = self.filtered_items(items);
+      if let Some(index) = rows.iter().position(|row| row.key == parent) {
+        self.list_state.select(Some(index));
+      }
+    }
+  }
+
   fn handle_actions(
     &mut self,
     key: KeyEvent,
     items: &[IssueSummary],
   ) -> Option<KeyResult<TicketPanelEvent>> {
-    match key.code {
-      KeyCode::Char('f') => {
+    match self.keys.action(&key)? {
+      Action::Filter => {
         self.filter_field_picker.show();
         Some(KeyResult::Handled)
       }
-      KeyCode::Char('s') if self.has_columns() => {
+      Action::ToggleSwimlanes if self.has_columns() => {
         self.toggle_column_mode();
         Some(KeyResult::Handled)
       }
-      KeyCode::Char('r') => Some(KeyResult::Event(TicketPanelEvent::RefreshRequested)),
-      KeyCode::Enter => {
+      Action::ToggleDone => {
+        self.hide_done = !self.hide_done;
+        self.reset_selection();
+        Some(KeyResult::Handled)
+      }
+      Action::Refresh => Some(KeyResult::Event(TicketPanelEvent::RefreshRequested)),
+      Action::OpenDetail => {
         if let Some(issue) = self.selected(items) {
           Some(KeyResult::Event(TicketPanelEvent::Selected(issue.clone())))
         } else {
           Some(KeyResult::Handled)
         }
       }
-      KeyCode::Char('q') | KeyCode::Esc => Some(KeyResult::Event(TicketPanelEvent::Back)),
+      action @ (Action::Copy | Action::GitBranch | Action::OpenInBrowser) => {
+        Some(match self.selected(items) {
+          Some(issue) => KeyResult::Event(TicketPanelEvent::IssueAction(action, issue.clone())),
+          None => KeyResult::Handled,
+        })
+      }
+      Action::Back => Some(KeyResult::Event(TicketPanelEvent::Back)),
       _ => None,
     }
   }
@@ -385,61 +552,107 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
     let len = filtered.len();
     ensure_valid_selection(&mut self.list_state, len);
 
-    let search_indicator = self
+    let mut indicators = self
       .search_filter
       .as_ref()
       .map(|q| format!(" [/{}]", q))
       .unwrap_or_default();
+    if self.hide_done {
+      indicators.push_str(" [done hidden]");
+    }
 
     let display_title = if is_loading {
       format!(" {} (loading...) ", title)
     } else {
-      format!(" {} ({} issues){} ", title, len, search_indicator)
+      format!(" {} ({} issues){} ", title, len, indicators)
     };
 
     let block = Block::default()
       .title(display_title)
       .title_alignment(Alignment::Center)
       .borders(Borders::ALL)
-      .border_style(Style::default().fg(Color::Blue));
+      .border_style(skin().border_style(false));
 
     if items.is_empty() && !is_loading {
       let content = "No issues found.";
       let paragraph = Paragraph::new(content)
         .block(block)
-        .style(Style::default().fg(Color::DarkGray));
+        .style(Style::default().fg(skin().text.dim));
       frame.render_widget(paragraph, area);
       return;
     }
 
+    let tree_prefixes: Vec<String> = match &self.tree {
+      Some(tree) => self
+        .tree_rows(tree, items)
+        .iter()
+        .map(|row| tree_prefix(tree, row))
+        .collect(),
+      None => Vec::new(),
+    };
+
     let list_items: Vec<ListItem> = filtered
       .iter()
-      .map(|issue| {
-        let color = status_color(&issue.status);
-        let line = Line::from(vec![
-          Span::styled(
-            format!("{:<15}", issue.key),
-            Style::default().fg(Color::Cyan),
-          ),
-          Span::raw(" "),
-          Span::styled(
-            format!("{:<15}", truncate(&issue.status, 15)),
-            Style::default().fg(color),
-          ),
-          Span::raw(" "),
-          Span::raw(issue.summary.clone()),
-        ]);
-        ListItem::new(line)
+      .enumerate()
+      .map(|(index, issue)| {
+        let mut spans = Vec::new();
+        if let Some(prefix) = tree_prefixes.get(index) {
+          // The marker has the ticket's status color
+          spans.push(Span::styled(
+            prefix.clone(),
+            Style::default()
+              .fg(skin().status_color(issue.status_category, issue.category_color.as_deref())),
+          ));
+        }
+        // Details go before the first column without a width (the summary),
+        // so they line up
+        let details = self.row_details.get(&issue.key);
+        let details_at = self
+          .list_columns
+          .iter()
+          .position(|c| c.width().is_none())
+          .unwrap_or(self.list_columns.len());
+        for (i, column) in self.list_columns.iter().enumerate() {
+          if i > 0 {
+            spans.push(Span::raw(" "));
+          }
+          if let Some(details) = details.filter(|_| i == details_at) {
+            spans.extend(details.spans.iter().cloned());
+            spans.push(Span::raw(" "));
+          }
+          let value = column.value(issue);
+          let text = match column.width() {
+            Some(width) => format!("{:<width$}", truncate(&value, width)),
+            None => value,
+          };
+          let style = match column {
+            ListColumn::Key => Style::default().fg(skin().text.accent),
+            ListColumn::Status => Style::default()
+              .fg(skin().status_color(issue.status_category, issue.category_color.as_deref())),
+            ListColumn::Type => skin()
+              .issue_type_color(&issue.issue_type)
+              .map_or_else(Style::default, |color| Style::default().fg(color)),
+            ListColumn::Priority => issue
+              .priority
+              .as_deref()
+              .and_then(|priority| skin().priority_color(priority))
+              .map_or_else(Style::default, |color| Style::default().fg(color)),
+            _ => Style::default(),
+          };
+          spans.push(Span::styled(text, style));
+        }
+        if let Some(details) = details.filter(|_| details_at == self.list_columns.len()) {
+          spans.push(Span::raw(" "));
+          spans.extend(details.spans.iter().cloned());
+        }
+        let line = Line::from(spans);
+        ListItem::new(line).style(self.changes.style(&issue.key))
       })
       .collect();
 
     let list = List::new(list_items)
       .block(block)
-      .highlight_style(
-        Style::default()
-          .bg(Color::DarkGray)
-          .add_modifier(Modifier::BOLD),
-      )
+      .highlight_style(skin().selection_style())
       .highlight_symbol("> ");
 
     frame.render_stateful_widget(list, area, &mut self.list_state);
@@ -458,7 +671,7 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
         .title(format!(" {} ", title))
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Blue));
+        .border_style(skin().border_style(false));
 
       let content = if is_loading {
         "Loading..."
@@ -467,7 +680,7 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
       };
       let paragraph = Paragraph::new(content)
         .block(block)
-        .style(Style::default().fg(Color::DarkGray));
+        .style(Style::default().fg(skin().text.dim));
       frame.render_widget(paragraph, area);
       return;
     }
@@ -486,41 +699,33 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
       let is_selected_column = col_idx == self.column_selected;
       let col_area = col_areas[col_idx];
 
-      let border_color = if is_selected_column {
-        Color::Yellow
-      } else {
-        Color::Blue
-      };
+      let border_style = skin().border_style(is_selected_column);
 
       let col_title = format!(" {} ({}) ", truncate(&column.name, 15), col_items.len());
       let block = Block::default()
         .title(col_title)
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(border_color));
+        .border_style(border_style);
 
       let list_items: Vec<ListItem> = col_items
         .iter()
         .map(|issue| {
           let issue_id = Line::from(vec![Span::styled(
             &issue.key,
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(skin().text.accent),
           )]);
           let issue_title = Line::from(vec![Span::raw(truncate(
             &issue.summary,
             col_area.width.saturating_sub(4) as usize,
           ))]);
-          ListItem::new(vec![issue_id, issue_title])
+          ListItem::new(vec![issue_id, issue_title]).style(self.changes.style(&issue.key))
         })
         .collect();
 
       let list = List::new(list_items)
         .block(block)
-        .highlight_style(
-          Style::default()
-            .bg(Color::DarkGray)
-            .add_modifier(Modifier::BOLD),
-        )
+        .highlight_style(skin().selection_style())
         .highlight_symbol("> ");
 
       if is_selected_column {
@@ -534,21 +739,79 @@ impl<F: FilterSource<IssueSummary>> TicketPanel<F> {
   }
 }
 
+/// Indentation and expansion marker of a tree row. Tickets whose children
+/// haven't been loaded yet may have some.
+fn tree_prefix(tree: &IssueTree, row: &TreeRow) -> String {
+  let expandable = row.has_children || !tree.is_loaded(&row.issue.key);
+  let marker = match (expandable, tree.is_expanded(&row.issue.key)) {
+    (false, _) => ' ',
+    (true, true) => '▾',
+    (true, false) => '▸',
+  };
+  format!("{}{} ", "  ".repeat(row.depth), marker)
+}
+
+/// Whether the lower case search text is in an issue's key, summary, status
+/// or assignee
+fn matches_search(issue: &IssueSummary, query_lower: &str) -> bool {
+  issue.key.to_lowercase().contains(query_lower)
+    || issue.summary.to_lowercase().contains(query_lower)
+    || issue.status.to_lowercase().contains(query_lower)
+    || issue
+      .assignee
+      .as_ref()
+      .map_or(false, |a| a.to_lowercase().contains(query_lower))
+}
+
 impl<F: FilterSource<IssueSummary>> ShortcutProvider for TicketPanel<F> {
   fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    let keys = &self.keys;
     let mut shortcuts = vec![
-      ShortcutInfo::new("r", "refresh").with_priority(100),
-      ShortcutInfo::new("f", "filter").with_priority(101),
+      keys.shortcut(Action::Refresh, "refresh").with_priority(100),
+      keys.shortcut(Action::Filter, "filter").with_priority(101),
+      keys.shortcut(Action::Copy, "copy").with_priority(103),
+      keys
+        .shortcut(Action::OpenInBrowser, "open in browser")
+        .with_priority(104),
+      keys
+        .shortcut(Action::GitBranch, "git branch")
+        .with_priority(105),
+      keys
+        .shortcut(
+          Action::ToggleDone,
+          if self.hide_done {
+            "show done"
+          } else {
+            "hide done"
+          },
+        )
+        .with_priority(106),
     ];
 
     // Filter tab navigation shortcuts
     if self.filter_bar.is_active() {
-      shortcuts.push(ShortcutInfo::new("PgUp/Dn", "filter tab").with_priority(102));
+      shortcuts.push(
+        keys
+          .shortcut_pair(Action::PrevFilterTab, Action::NextFilterTab, "filter tab")
+          .with_priority(102),
+      );
+    }
+
+    if self.tree.is_some() {
+      shortcuts.push(
+        keys
+          .shortcut_pair(Action::MoveLeft, Action::MoveRight, "collapse/expand")
+          .with_priority(110),
+      );
     }
 
     // Column mode shortcuts
     if self.has_columns() {
-      shortcuts.push(ShortcutInfo::new("s", "swimlane").with_priority(110));
+      shortcuts.push(
+        keys
+          .shortcut(Action::ToggleSwimlanes, "swimlane")
+          .with_priority(110),
+      );
     }
 
     shortcuts
@@ -0,0 +1,171 @@
+use super::KeyResult;
+use crate::clipboard;
+use crate::jira::JiraClient;
+use crate::ui::renderfns::truncate;
+use crate::ui::skin;
+use crate::ui::{Action, Keymap};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+/// Events emitted by the yank menu that parent needs to handle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum YankEvent {
+  /// Text was copied (or copying failed); message for the footer
+  Copied(String),
+  /// Menu closed without copying
+  Cancelled,
+}
+
+/// The issue to copy from
+#[derive(Debug, Clone, Default)]
+pub struct YankTarget {
+  pub key: String,
+  pub summary: String,
+  pub url: String,
+  pub branch: String,
+}
+
+impl YankTarget {
+  pub fn new(key: &str, summary: &str, branch: String, jira: &JiraClient) -> Self {
+    Self {
+      key: key.to_string(),
+      summary: summary.to_string(),
+      url: jira.browse_url(key),
+      branch,
+    }
+  }
+}
+
+/// What to copy, with its key in the menu
+const CHOICES: &[(char, &str)] = &[
+  ('y', "key"),
+  ('u', "URL"),
+  ('s', "KEY: summary"),
+  ('m', "Markdown link"),
+  ('b', "branch name"),
+];
+
+/// Menu for copying an issue's key, URL, title, link or branch name (`y`)
+#[derive(Debug, Clone, Default)]
+pub struct YankMenu {
+  target: Option<YankTarget>,
+  keys: Keymap,
+}
+
+impl YankMenu {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Set the key bindings
+  pub fn set_keymap(&mut self, keys: &Keymap) {
+    self.keys = keys.clone();
+  }
+
+  /// Show the menu for an issue
+  pub fn show(&mut self, target: YankTarget) {
+    self.target = Some(target);
+  }
+
+  /// Handle a key event
+  pub fn handle_key(&mut self, key: KeyEvent) -> KeyResult<YankEvent> {
+    let Some(target) = &self.target else {
+      return KeyResult::NotHandled;
+    };
+
+    let choice = CHOICES.iter().find(|(c, _)| key.code == KeyCode::Char(*c));
+    let (choice, label) = match (choice, self.keys.action(&key)) {
+      (Some(choice), _) => *choice,
+      (None, Some(Action::Back)) => {
+        self.target = None;
+        return KeyResult::Event(YankEvent::Cancelled);
+      }
+      // Enter takes the first choice, like `yy`
+      (None, Some(Action::OpenDetail)) => CHOICES[0],
+      _ => return KeyResult::Handled,
+    };
+
+    let text = yank_text(target, choice);
+    let message = match clipboard::copy(&text) {
+      Ok(()) => format!("Copied {}: {}", label, text),
+      Err(e) => format!("Copy failed: {}", e),
+    };
+    self.target = None;
+    KeyResult::Event(YankEvent::Copied(message))
+  }
+
+  /// Render the menu overlay if active
+  pub fn render_overlay(&self, frame: &mut Frame, area: Rect) {
+    let Some(target) = &self.target else {
+      return;
+    };
+
+    let width = 60.min(area.width.saturating_sub(4));
+    let height = (CHOICES.len() as u16 + 2).min(area.height);
+    let x = area.x + area.width.saturating_sub(width) / 2;
+    let y = area.y + area.height.saturating_sub(height) / 2;
+    let overlay_area = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(true))
+      .title(format!(" Copy {} ", target.key));
+
+    let preview_width = (width as usize).saturating_sub(20);
+    let lines: Vec<Line> = CHOICES
+      .iter()
+      .map(|(c, label)| {
+        Line::from(vec![
+          Span::styled(format!(" {} ", c), Style::default().fg(skin().text.accent)),
+          Span::raw(format!("{:<14}", label)),
+          Span::styled(
+            truncate(&yank_text(target, *c), preview_width),
+            Style::default().fg(skin().text.dim),
+          ),
+        ])
+      })
+      .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), overlay_area);
+  }
+}
+
+fn yank_text(target: &YankTarget, choice: char) -> String {
+  let title = if target.summary.is_empty() {
+    target.key.clone()
+  } else {
+    format!("{}: {}", target.key, target.summary)
+  };
+  match choice {
+    'u' => target.url.clone(),
+    's' => title,
+    'm' => format!("[{}]({})", title.replace(['[', ']'], ""), target.url),
+    'b' => target.branch.clone(),
+    _ => target.key.clone(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_yank_text() {
+    let target = YankTarget {
+      key: "A-1".to_string(),
+      summary: "Fix [the] thing".to_string(),
+      url: "https://x.atlassian.net/browse/A-1".to_string(),
+      branch: "bug/A-1-fix-the-thing".to_string(),
+    };
+    assert_eq!(yank_text(&target, 'y'), "A-1");
+    assert_eq!(yank_text(&target, 's'), "A-1: Fix [the] thing");
+    assert_eq!(
+      yank_text(&target, 'm'),
+      "[A-1: Fix the thing](https://x.atlassian.net/browse/A-1)"
+    );
+    assert_eq!(yank_text(&target, 'b'), "bug/A-1-fix-the-thing");
+  }
+}
@@ -0,0 +1,363 @@
+use crate::ui::view::ShortcutInfo;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Something a key can be bound to.
+///
+/// Views and components ask the keymap which action a key stands for
+/// instead of matching on key codes, so every binding can be changed in
+/// `hotkeys.yaml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+  MoveUp,
+  MoveDown,
+  MoveLeft,
+  MoveRight,
+  /// Open the selected item; also confirms pickers
+  OpenDetail,
+  /// Go back; also closes pickers and menus
+  Back,
+  Quit,
+  Command,
+  Search,
+  Refresh,
+  Filter,
+  PrevFilterTab,
+  NextFilterTab,
+  /// Switch between the tabs of a view (issue details and history) or its
+  /// charts
+  NextTab,
+  ToggleSwimlanes,
+  /// Show or hide issues in a done status
+  ToggleDone,
+  /// Count issues or story points (burndown)
+  ToggleUnit,
+  /// Move the selected issue to the previous/next board column
+  ChangeStatusLeft,
+  ChangeStatusRight,
+  /// Rank the selected issue above the previous one, within its board
+  /// column or the backlog
+  RankUp,
+  RankDown,
+  /// Rank the selected issue first or last
+  RankTop,
+  RankBottom,
+  AssignToMe,
+  Comment,
+  Edit,
+  Copy,
+  OpenInBrowser,
+  GitBranch,
+  Delete,
+  Retry,
+  ClearAll,
+  Vacuum,
+}
+
+/// Built-in bindings, in the hotkeys.yaml syntax
+const DEFAULTS: &[(Action, &[&str])] = &[
+  (Action::MoveUp, &["k", "Up"]),
+  (Action::MoveDown, &["j", "Down"]),
+  (Action::MoveLeft, &["h", "Left"]),
+  (Action::MoveRight, &["l", "Right"]),
+  (Action::OpenDetail, &["Enter"]),
+  (Action::Back, &["q", "Esc"]),
+  (Action::Quit, &["Ctrl-c"]),
+  (Action::Command, &[":"]),
+  (Action::Search, &["/"]),
+  (Action::Refresh, &["r"]),
+  (Action::Filter, &["f"]),
+  (Action::PrevFilterTab, &["PgUp"]),
+  (Action::NextFilterTab, &["PgDn"]),
+  (Action::NextTab, &["Tab"]),
+  (Action::ToggleSwimlanes, &["s"]),
+  (Action::ToggleDone, &["x"]),
+  (Action::ToggleUnit, &["p"]),
+  (Action::ChangeStatusLeft, &["Shift-Left", "H"]),
+  (Action::ChangeStatusRight, &["Shift-Right", "L"]),
+  (Action::RankUp, &["Shift-Up", "K"]),
+  (Action::RankDown, &["Shift-Down", "J"]),
+  (Action::RankTop, &["Shift-Home"]),
+  (Action::RankBottom, &["Shift-End"]),
+  (Action::AssignToMe, &["a"]),
+  (Action::Comment, &["c"]),
+  (Action::Edit, &["e"]),
+  (Action::Copy, &["y"]),
+  (Action::OpenInBrowser, &["o"]),
+  (Action::GitBranch, &["B"]),
+  (Action::Delete, &["d"]),
+  (Action::Retry, &["R"]),
+  (Action::ClearAll, &["X"]),
+  (Action::Vacuum, &["v"]),
+];
+
+/// A key with modifiers, e.g. `j`, `Shift-Left` or `Ctrl-r`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+  code: KeyCode,
+  modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+  /// Whether a key event is this binding. Shift is part of the character
+  /// for letters and symbols, so it only counts for other keys.
+  pub fn matches(&self, key: &KeyEvent) -> bool {
+    let relevant = match key.code {
+      KeyCode::Char(_) => KeyModifiers::CONTROL | KeyModifiers::ALT,
+      _ => KeyModifiers::SHIFT | KeyModifiers::CONTROL | KeyModifiers::ALT,
+    };
+    self.code == key.code && key.modifiers & relevant == self.modifiers & relevant
+  }
+}
+
+impl FromStr for KeyBinding {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = s.trim();
+    // "-" on its own, or as the last part of "Ctrl--", is the minus key
+    while let Some((modifier, key)) = rest.split_once('-').filter(|(_, key)| !key.is_empty()) {
+      modifiers |= match modifier.to_lowercase().as_str() {
+        "shift" | "s" => KeyModifiers::SHIFT,
+        "ctrl" | "c" => KeyModifiers::CONTROL,
+        "alt" | "a" | "m" => KeyModifiers::ALT,
+        _ => return Err(format!("unknown modifier in key {:?}", s)),
+      };
+      rest = key;
+    }
+
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+      (Some(c), None) => KeyCode::Char(c),
+      _ => match rest.to_lowercase().as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pgup" | "pageup" => KeyCode::PageUp,
+        "pgdn" | "pagedown" => KeyCode::PageDown,
+        name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+          Some(n @ 1..=24) => KeyCode::F(n),
+          _ => return Err(format!("unknown key {:?}", s)),
+        },
+      },
+    };
+
+    // Terminals report Shift-a as `A`, and Shift-1 as whatever the layout
+    // puts there, so Shift is folded into letters and refused for symbols
+    let code = match code {
+      KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+        if !c.is_alphabetic() {
+          return Err(format!(
+            "Shift can't be combined with {:?} in key {:?}; bind the shifted character instead",
+            c, s
+          ));
+        }
+        modifiers.remove(KeyModifiers::SHIFT);
+        KeyCode::Char(c.to_uppercase().next().unwrap_or(c))
+      }
+      code => code,
+    };
+    Ok(Self { code, modifiers })
+  }
+}
+
+impl fmt::Display for KeyBinding {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.modifiers.contains(KeyModifiers::CONTROL) {
+      write!(f, "C-")?;
+    }
+    if self.modifiers.contains(KeyModifiers::ALT) {
+      write!(f, "M-")?;
+    }
+    if self.modifiers.contains(KeyModifiers::SHIFT) {
+      write!(f, "S-")?;
+    }
+    match self.code {
+      KeyCode::Char(' ') => write!(f, "Space"),
+      KeyCode::Char(c) => write!(f, "{}", c),
+      KeyCode::PageUp => write!(f, "PgUp"),
+      KeyCode::PageDown => write!(f, "PgDn"),
+      KeyCode::F(n) => write!(f, "F{}", n),
+      code => write!(f, "{:?}", code),
+    }
+  }
+}
+
+/// The keys bound to an action: one key or a list in YAML
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Keys(pub Vec<KeyBinding>);
+
+impl<'de> Deserialize<'de> for Keys {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+      One(String),
+      Many(Vec<String>),
+    }
+
+    let keys = match OneOrMany::deserialize(deserializer)? {
+      OneOrMany::One(key) => vec![key],
+      OneOrMany::Many(keys) => keys,
+    };
+    keys
+      .iter()
+      .map(|key| key.parse().map_err(serde::de::Error::custom))
+      .collect::<Result<_, _>>()
+      .map(Keys)
+  }
+}
+
+/// Key bindings from hotkeys.yaml: action -> keys
+pub type Bindings = BTreeMap<Action, Keys>;
+
+/// Resolved key bindings for one view
+#[derive(Debug, Clone)]
+pub struct Keymap {
+  bindings: BTreeMap<Action, Vec<KeyBinding>>,
+}
+
+impl Default for Keymap {
+  fn default() -> Self {
+    let bindings = DEFAULTS
+      .iter()
+      .map(|(action, keys)| {
+        let keys = keys
+          .iter()
+          .map(|key| key.parse().expect("valid default key"))
+          .collect();
+        (*action, keys)
+      })
+      .collect();
+    Self { bindings }
+  }
+}
+
+impl Keymap {
+  /// Build the keymap of a view: the defaults, then the global bindings,
+  /// then the view's own
+  pub fn new(global: &Bindings, view: Option<&Bindings>) -> Self {
+    let mut keymap = Self::default();
+    keymap.apply(global);
+    if let Some(view) = view {
+      keymap.apply(view);
+    }
+    keymap
+  }
+
+  /// Replace the keys of the given actions. A key taken by an action is
+  /// removed from any other action, so new bindings win over defaults.
+  fn apply(&mut self, bindings: &Bindings) {
+    for (action, Keys(keys)) in bindings {
+      for (other, other_keys) in self.bindings.iter_mut() {
+        if other != action {
+          other_keys.retain(|k| !keys.contains(k));
+        }
+      }
+      self.bindings.insert(*action, keys.clone());
+    }
+  }
+
+  /// The action a key is bound to, if any
+  pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+    self
+      .bindings
+      .iter()
+      .find(|(_, keys)| keys.iter().any(|k| k.matches(key)))
+      .map(|(action, _)| *action)
+  }
+
+  /// Whether a key is bound to an action
+  pub fn is(&self, action: Action, key: &KeyEvent) -> bool {
+    self.action(key) == Some(action)
+  }
+
+  /// The main key of an action, for hints ("" when unbound)
+  pub fn label(&self, action: Action) -> String {
+    self
+      .bindings
+      .get(&action)
+      .and_then(|keys| keys.first())
+      .map(|key| key.to_string())
+      .unwrap_or_default()
+  }
+
+  /// A header hint showing the action's current key
+  pub fn shortcut(&self, action: Action, label: &'static str) -> ShortcutInfo {
+    ShortcutInfo::new(self.label(action), label)
+  }
+
+  /// A header hint for a pair of actions, e.g. `PgUp/PgDn`
+  pub fn shortcut_pair(&self, first: Action, second: Action, label: &'static str) -> ShortcutInfo {
+    let key = format!("{}/{}", self.label(first), self.label(second));
+    ShortcutInfo::new(key, label)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+  }
+
+  #[test]
+  fn test_parse_keys() {
+    let binding: KeyBinding = "Shift-Left".parse().unwrap();
+    assert!(binding.matches(&key(KeyCode::Left, KeyModifiers::SHIFT)));
+    assert!(!binding.matches(&key(KeyCode::Left, KeyModifiers::NONE)));
+    assert_eq!(binding.to_string(), "S-Left");
+
+    let binding: KeyBinding = "Ctrl-r".parse().unwrap();
+    assert!(binding.matches(&key(KeyCode::Char('r'), KeyModifiers::CONTROL)));
+    assert!(!binding.matches(&key(KeyCode::Char('r'), KeyModifiers::NONE)));
+
+    // Terminals report capitals with Shift held
+    let binding: KeyBinding = "B".parse().unwrap();
+    assert!(binding.matches(&key(KeyCode::Char('B'), KeyModifiers::SHIFT)));
+    let binding: KeyBinding = "Shift-b".parse().unwrap();
+    assert!(binding.matches(&key(KeyCode::Char('B'), KeyModifiers::SHIFT)));
+    assert!(!binding.matches(&key(KeyCode::Char('b'), KeyModifiers::NONE)));
+    assert_eq!(binding.to_string(), "B");
+    assert!("Shift-1".parse::<KeyBinding>().is_err());
+
+    assert_eq!("-".parse::<KeyBinding>().unwrap().to_string(), "-");
+    assert_eq!("F5".parse::<KeyBinding>().unwrap().to_string(), "F5");
+    assert!("Hyper-x".parse::<KeyBinding>().is_err());
+    assert!("Nope".parse::<KeyBinding>().is_err());
+  }
+
+  #[test]
+  fn test_overrides() {
+    let global: Bindings = serde_yaml::from_str("move_left: a\nrefresh: [h, F5]\n").unwrap();
+    let board: Bindings = serde_yaml::from_str("move_left: [Left]\n").unwrap();
+    let keymap = Keymap::new(&global, Some(&board));
+
+    let h = key(KeyCode::Char('h'), KeyModifiers::NONE);
+    let a = key(KeyCode::Char('a'), KeyModifiers::NONE);
+    // h moved to refresh, a taken from assign by the global move_left...
+    assert_eq!(keymap.action(&h), Some(Action::Refresh));
+    assert_eq!(keymap.action(&a), None);
+    // ...which the board replaced again
+    assert!(keymap.is(Action::MoveLeft, &key(KeyCode::Left, KeyModifiers::NONE)));
+    assert_eq!(keymap.label(Action::Refresh), "h");
+    assert_eq!(Keymap::default().label(Action::Refresh), "r");
+  }
+}
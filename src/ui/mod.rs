@@ -1,8 +1,17 @@
+mod columns;
 pub mod components;
+pub mod keymap;
+mod recent_changes;
 pub mod renderfns;
+pub mod skin;
 pub mod view;
 pub mod views;
 
+pub use columns::ListColumn;
+pub use keymap::{Action, Keymap};
+pub use recent_changes::RecentChanges;
+pub use skin::{set_skin, skin, Skin};
+
 use crate::app::App;
 use ratatui::prelude::*;
 use ratatui::widgets::ListState;
@@ -35,12 +44,15 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
 
   // Draw header with dynamic shortcuts
   let shortcuts = app.current_shortcuts();
+  let badge = app.connection_badge();
   draw_header(
     frame,
     chunks[0],
     app.title(),
     app.current_project(),
     &shortcuts,
+    badge.as_deref(),
+    app.unread_notifications(),
   );
 
   // Draw current view (view handles its own overlays like search)
@@ -53,5 +65,7 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
 
   // Draw footer breadcrumb
   let breadcrumb = app.view_breadcrumb();
-  draw_footer(frame, chunks[2], &breadcrumb);
+  let message = app.flash_message();
+  let updated = app.updated_indicator();
+  draw_footer(frame, chunks[2], &breadcrumb, message, updated.as_deref());
 }
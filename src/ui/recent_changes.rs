@@ -0,0 +1,89 @@
+use crate::ui::skin;
+use ratatui::prelude::*;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long a changed row stays highlighted
+const HIGHLIGHT_DURATION: Duration = Duration::from_secs(10);
+
+/// Tracks which rows changed between loads, so refreshed views can highlight them.
+///
+/// Rows are identified by key and compared by their `updated` timestamp.
+/// The first load only records what's there; nothing is highlighted.
+#[derive(Debug, Default)]
+pub struct RecentChanges {
+  seen: Option<HashMap<String, String>>,
+  changed: HashMap<String, Instant>,
+}
+
+impl RecentChanges {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Record a freshly loaded set of `(key, updated)` rows
+  pub fn update<'a>(&mut self, rows: impl IntoIterator<Item = (&'a str, &'a str)>) {
+    let rows: HashMap<String, String> = rows
+      .into_iter()
+      .map(|(key, updated)| (key.to_string(), updated.to_string()))
+      .collect();
+
+    if let Some(seen) = &self.seen {
+      let now = Instant::now();
+      for (key, updated) in &rows {
+        if seen.get(key) != Some(updated) {
+          self.changed.insert(key.clone(), now);
+        }
+      }
+    }
+
+    self
+      .changed
+      .retain(|_, at| at.elapsed() < HIGHLIGHT_DURATION);
+    self.seen = Some(rows);
+  }
+
+  /// Check if a row changed recently
+  pub fn is_changed(&self, key: &str) -> bool {
+    self
+      .changed
+      .get(key)
+      .is_some_and(|at| at.elapsed() < HIGHLIGHT_DURATION)
+  }
+
+  /// Base style for a row: tinted while it's recently changed
+  pub fn style(&self, key: &str) -> Style {
+    if self.is_changed(key) {
+      Style::default().bg(skin().selection.changed_bg)
+    } else {
+      Style::default()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_first_load_highlights_nothing() {
+    let mut changes = RecentChanges::new();
+    changes.update([("A-1", "t1"), ("A-2", "t1")]);
+    assert!(!changes.is_changed("A-1"));
+    assert!(!changes.is_changed("A-2"));
+  }
+
+  #[test]
+  fn test_updated_and_new_rows_are_changed() {
+    let mut changes = RecentChanges::new();
+    changes.update([("A-1", "t1"), ("A-2", "t1")]);
+    changes.update([("A-1", "t1"), ("A-2", "t2"), ("A-3", "t1")]);
+    assert!(!changes.is_changed("A-1"));
+    assert!(changes.is_changed("A-2"));
+    assert!(changes.is_changed("A-3"));
+
+    // Still highlighted after a reload with no further changes
+    changes.update([("A-1", "t1"), ("A-2", "t2"), ("A-3", "t1")]);
+    assert!(changes.is_changed("A-2"));
+  }
+}
@@ -1,29 +1,53 @@
+use crate::ui::skin;
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 
 /// Draw the footer bar with view breadcrumb
-pub fn draw_footer(frame: &mut Frame, area: Rect, breadcrumb: &[String]) {
+///
+/// The right side shows a transient status message if there is one,
+/// otherwise a dim note of how fresh the view's data is.
+pub fn draw_footer(
+  frame: &mut Frame,
+  area: Rect,
+  breadcrumb: &[String],
+  message: Option<&str>,
+  updated: Option<&str>,
+) {
   let mut spans = Vec::new();
 
   spans.push(Span::raw(" "));
 
   for (i, part) in breadcrumb.iter().enumerate() {
     if i > 0 {
-      spans.push(Span::styled(" > ", Style::default().fg(Color::DarkGray)));
+      spans.push(Span::styled(" > ", Style::default().fg(skin().text.dim)));
     }
 
     let style = if i == breadcrumb.len() - 1 {
       // Current view - highlighted
-      Style::default().fg(Color::Cyan).bold()
+      Style::default().fg(skin().footer.current).bold()
     } else {
-      Style::default().fg(Color::White)
+      Style::default().fg(skin().footer.breadcrumb)
     };
 
     spans.push(Span::styled(part.clone(), style));
   }
 
   let line = Line::from(spans);
-  let paragraph = Paragraph::new(line).style(Style::default().bg(Color::Black));
+  let paragraph = Paragraph::new(line).style(Style::default().bg(skin().footer.bg));
 
   frame.render_widget(paragraph, area);
+
+  // Transient status message (or data age), right-aligned over the breadcrumb
+  let right = match (message, updated) {
+    (Some(msg), _) => Some((msg, skin().footer.message)),
+    (None, Some(updated)) => Some((updated, skin().text.dim)),
+    (None, None) => None,
+  };
+  if let Some((msg, color)) = right {
+    let text = format!("{} ", msg);
+    let width = (text.chars().count() as u16).min(area.width);
+    let msg_area = Rect::new(area.x + area.width - width, area.y, width, 1);
+    let paragraph = Paragraph::new(text).style(Style::default().fg(color).bg(skin().footer.bg));
+    frame.render_widget(paragraph, msg_area);
+  }
 }
@@ -1,3 +1,4 @@
+use crate::ui::skin;
 use crate::ui::view::{ShortcutInfo, ShortcutVisibility};
 use ratatui::prelude::*;
 use ratatui::widgets::{Paragraph, Row, Table};
@@ -5,33 +6,46 @@ use ratatui::widgets::{Paragraph, Row, Table};
 /// Draw the header bar in k9s style (always 2 lines):
 /// - Left: title (line 1), project (line 2)
 /// - Middle: shortcuts in columns (2 per column, fill right)
-/// - Right: j9s logo
+/// - Right: j9s logo with the unread notification count, and an optional
+///   status badge (e.g. offline) below it
 pub fn draw_header(
   frame: &mut Frame,
   area: Rect,
   title: &str,
   project: &str,
   shortcuts: &[ShortcutInfo],
+  badge: Option<&str>,
+  unread: usize,
 ) {
+  let unread_label = (unread > 0).then(|| format!("● {}", unread));
+
+  // Right column grows to fit the badge and unread count
+  let logo_width = unread_label
+    .as_ref()
+    .map_or(6, |u| u.chars().count() as u16 + 6);
+  let right_width = badge.map_or(logo_width, |b| {
+    (b.chars().count() as u16 + 2).max(logo_width)
+  });
+
   // Split into 3 columns: left (context), middle (shortcuts), right (logo)
   let columns = Layout::default()
     .direction(Direction::Horizontal)
     .constraints([
-      Constraint::Length(30), // Left: hostname/project
-      Constraint::Min(30),    // Middle: shortcuts (flexible)
-      Constraint::Length(6),  // Right: logo
+      Constraint::Length(30),          // Left: hostname/project
+      Constraint::Min(30),             // Middle: shortcuts (flexible)
+      Constraint::Length(right_width), // Right: logo + badge
     ])
     .split(area);
 
   // === Left column: title and project (always 2 lines) ===
   let left_line1 = Line::from(vec![Span::styled(
     format!(" {}", title),
-    Style::default().fg(Color::Cyan),
+    Style::default().fg(skin().header.title),
   )]);
 
   let left_line2 = Line::from(vec![
-    Span::styled(" Project: ", Style::default().fg(Color::DarkGray)),
-    Span::styled(project, Style::default().fg(Color::Yellow).bold()),
+    Span::styled(" Project: ", Style::default().fg(skin().text.dim)),
+    Span::styled(project, Style::default().fg(skin().header.project).bold()),
   ]);
 
   let left_text = Text::from(vec![left_line1, left_line2]);
@@ -46,14 +60,17 @@ pub fn draw_header(
   visible_shortcuts.sort_by_key(|s| s.priority);
 
   // Build table with 2 rows, shortcuts filling columns vertically
-  let num_cols = (visible_shortcuts.len() + 1) / 2;
+  let num_cols = visible_shortcuts.len().div_ceil(2);
 
   let format_shortcut = |s: &ShortcutInfo| -> Line {
     Line::from(vec![
-      Span::styled(format!("<{}>", s.key), Style::default().fg(Color::Cyan)),
+      Span::styled(
+        format!("<{}>", s.key),
+        Style::default().fg(skin().header.shortcut),
+      ),
       Span::styled(
         format!(" {}", s.label),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(skin().text.dim),
       ),
     ])
   };
@@ -93,15 +110,34 @@ pub fn draw_header(
   let table = Table::new(rows, widths).column_spacing(2);
   frame.render_widget(table, table_area);
 
-  // === Right column: j9s logo ===
-  let logo = Paragraph::new(" j9s ")
-    .style(Style::default().fg(Color::Cyan).bold())
-    .alignment(Alignment::Right);
+  // === Right column: j9s logo and status badge ===
+  let mut logo_line = Vec::new();
+  if let Some(unread) = unread_label {
+    logo_line.push(Span::styled(
+      unread,
+      Style::default().fg(skin().text.highlight).bold(),
+    ));
+  }
+  logo_line.push(Span::styled(
+    " j9s ",
+    Style::default().fg(skin().header.title).bold(),
+  ));
+  let mut right_lines = vec![Line::from(logo_line)];
+  if let Some(badge) = badge {
+    right_lines.push(Line::from(Span::styled(
+      format!(" {} ", badge),
+      Style::default()
+        .fg(skin().header.badge_fg)
+        .bg(skin().header.badge_bg)
+        .bold(),
+    )));
+  }
+  let logo = Paragraph::new(right_lines).alignment(Alignment::Right);
   frame.render_widget(logo, columns[2]);
 }
 
 /// Extract domain from Jira URL
-fn extract_domain(url: &str) -> &str {
+pub fn extract_domain(url: &str) -> &str {
   url
     .strip_prefix("https://")
     .or_else(|| url.strip_prefix("http://"))
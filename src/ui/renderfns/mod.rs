@@ -3,5 +3,7 @@ pub mod header;
 pub mod utils;
 
 pub use footer::draw_footer;
-pub use header::draw_header;
-pub use utils::{status_color, truncate};
+pub use header::{draw_header, extract_domain};
+pub use utils::{
+  format_age, format_duration, format_size, format_timestamp, progress_bar, truncate,
+};
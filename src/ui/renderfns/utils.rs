@@ -1,4 +1,4 @@
-use ratatui::prelude::Color;
+use chrono::TimeDelta;
 
 /// Truncate a string to a maximum length, adding "..." if truncated
 pub fn truncate(s: &str, max_len: usize) -> String {
@@ -9,15 +9,60 @@ pub fn truncate(s: &str, max_len: usize) -> String {
   }
 }
 
-/// Get the display color for a Jira issue status
-pub fn status_color(status: &str) -> Color {
-  match status {
-    "Done" | "Closed" | "Resolved" => Color::Green,
-    "In Progress" | "In Review" => Color::Yellow,
-    _ => Color::White,
+/// Shorten a Jira timestamp to the minute:
+/// "2024-05-01T12:34:56.000+0000" -> "2024-05-01 12:34"
+pub fn format_timestamp(timestamp: &str) -> String {
+  timestamp
+    .chars()
+    .take(16)
+    .collect::<String>()
+    .replace('T', " ")
+}
+
+/// Format an age as a short human-readable string (e.g. "5m ago", "2h ago")
+pub fn format_age(age: TimeDelta) -> String {
+  let secs = age.num_seconds().max(0);
+  match secs {
+    0..60 => "just now".to_string(),
+    60..3600 => format!("{}m ago", secs / 60),
+    3600..86400 => format!("{}h ago", secs / 3600),
+    _ => format!("{}d ago", secs / 86400),
+  }
+}
+
+/// Format a byte count with a binary unit (e.g. "1.5 MB")
+pub fn format_size(bytes: u64) -> String {
+  const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+  let mut size = bytes as f64;
+  let mut unit = 0;
+  while size >= 1024.0 && unit < UNITS.len() - 1 {
+    size /= 1024.0;
+    unit += 1;
+  }
+  if unit == 0 {
+    format!("{} B", bytes)
+  } else {
+    format!("{:.1} {}", size, UNITS[unit])
+  }
+}
+
+/// Format a duration compactly (e.g. "30s", "5m", "2h")
+pub fn format_duration(d: TimeDelta) -> String {
+  let secs = d.num_seconds();
+  match secs {
+    s if s % 86400 == 0 && s > 0 => format!("{}d", s / 86400),
+    s if s % 3600 == 0 && s > 0 => format!("{}h", s / 3600),
+    s if s % 60 == 0 && s > 0 => format!("{}m", s / 60),
+    s => format!("{}s", s),
   }
 }
 
+/// A text progress bar of `width` cells for a ratio from 0 to 1
+pub fn progress_bar(ratio: f64, width: usize) -> String {
+  let filled = ((ratio.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+  format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -38,21 +83,42 @@ mod tests {
   }
 
   #[test]
-  fn test_status_color_done() {
-    assert_eq!(status_color("Done"), Color::Green);
-    assert_eq!(status_color("Closed"), Color::Green);
-    assert_eq!(status_color("Resolved"), Color::Green);
+  fn test_format_timestamp() {
+    assert_eq!(
+      format_timestamp("2024-05-01T12:34:56.000+0000"),
+      "2024-05-01 12:34"
+    );
+    assert_eq!(format_timestamp(""), "");
+  }
+
+  #[test]
+  fn test_format_age() {
+    assert_eq!(format_age(TimeDelta::seconds(5)), "just now");
+    assert_eq!(format_age(TimeDelta::minutes(5)), "5m ago");
+    assert_eq!(format_age(TimeDelta::minutes(130)), "2h ago");
+    assert_eq!(format_age(TimeDelta::days(3)), "3d ago");
+    assert_eq!(format_age(TimeDelta::seconds(-10)), "just now");
+  }
+
+  #[test]
+  fn test_format_size() {
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(1536), "1.5 KB");
+    assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
   }
 
   #[test]
-  fn test_status_color_in_progress() {
-    assert_eq!(status_color("In Progress"), Color::Yellow);
-    assert_eq!(status_color("In Review"), Color::Yellow);
+  fn test_format_duration() {
+    assert_eq!(format_duration(TimeDelta::seconds(45)), "45s");
+    assert_eq!(format_duration(TimeDelta::minutes(5)), "5m");
+    assert_eq!(format_duration(TimeDelta::hours(2)), "2h");
+    assert_eq!(format_duration(TimeDelta::days(1)), "1d");
   }
 
   #[test]
-  fn test_status_color_default() {
-    assert_eq!(status_color("To Do"), Color::White);
-    assert_eq!(status_color("Open"), Color::White);
+  fn test_progress_bar() {
+    assert_eq!(progress_bar(0.0, 4), "░░░░");
+    assert_eq!(progress_bar(0.5, 4), "██░░");
+    assert_eq!(progress_bar(1.2, 4), "████");
   }
 }
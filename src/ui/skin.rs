@@ -0,0 +1,422 @@
+use crate::jira::types::StatusCategory;
+use color_eyre::{eyre::eyre, Result};
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// The active skin, set once at startup
+static SKIN: OnceLock<Skin> = OnceLock::new();
+
+/// Get the active skin (the default one until [`set_skin`] is called)
+pub fn skin() -> &'static Skin {
+  SKIN.get_or_init(Skin::default)
+}
+
+/// Make a skin the active one. Only the first call has an effect.
+pub fn set_skin(skin: Skin) {
+  let _ = SKIN.set(skin);
+}
+
+/// Colors for the whole UI, from a skin file.
+///
+/// Every field is optional in the file; missing ones keep the dark preset's
+/// color. Colors are names (`red`, `lightblue`), `#rrggbb` or a 256-color index.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Skin {
+  pub text: TextColors,
+  pub header: HeaderColors,
+  pub footer: FooterColors,
+  pub border: BorderColors,
+  pub selection: SelectionColors,
+  pub status: StatusColors,
+  /// Priority name -> color (names as in Jira, case-insensitive)
+  pub priorities: BTreeMap<String, Color>,
+  /// Issue type name -> color (names as in Jira, case-insensitive)
+  pub issue_types: BTreeMap<String, Color>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TextColors {
+  pub normal: Color,
+  /// Labels, hints and placeholders
+  pub dim: Color,
+  /// Issue keys, ids and other identifiers
+  pub accent: Color,
+  /// Prompts and values that should stand out
+  pub highlight: Color,
+  /// Secondary highlights: issue types, comments, conflicts
+  pub emphasis: Color,
+  pub error: Color,
+  pub success: Color,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HeaderColors {
+  pub title: Color,
+  pub project: Color,
+  /// Shortcut keys; their labels use `text.dim`
+  pub shortcut: Color,
+  pub badge_fg: Color,
+  pub badge_bg: Color,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FooterColors {
+  pub bg: Color,
+  pub breadcrumb: Color,
+  pub current: Color,
+  pub message: Color,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct BorderColors {
+  pub normal: Color,
+  /// Focused columns and popups
+  pub focus: Color,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SelectionColors {
+  /// Text color of the selected row; unset keeps the row's own colors
+  pub fg: Option<Color>,
+  pub bg: Color,
+  pub tab_fg: Color,
+  pub tab_bg: Color,
+  pub inactive_tab: Color,
+  /// Background of rows changed by the last refresh
+  pub changed_bg: Color,
+}
+
+/// Colors by Jira status category, so they work for any status name
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StatusColors {
+  pub todo: Color,
+  pub in_progress: Color,
+  pub done: Color,
+  /// Use the category colors Jira itself shows instead
+  pub from_jira: bool,
+}
+
+impl Default for Skin {
+  fn default() -> Self {
+    Self::dark()
+  }
+}
+
+impl Default for TextColors {
+  fn default() -> Self {
+    Skin::dark().text
+  }
+}
+
+impl Default for HeaderColors {
+  fn default() -> Self {
+    Skin::dark().header
+  }
+}
+
+impl Default for FooterColors {
+  fn default() -> Self {
+    Skin::dark().footer
+  }
+}
+
+impl Default for BorderColors {
+  fn default() -> Self {
+    Skin::dark().border
+  }
+}
+
+impl Default for SelectionColors {
+  fn default() -> Self {
+    Skin::dark().selection
+  }
+}
+
+impl Default for StatusColors {
+  fn default() -> Self {
+    Skin::dark().status
+  }
+}
+
+fn colors(entries: &[(&str, Color)]) -> BTreeMap<String, Color> {
+  entries
+    .iter()
+    .map(|(name, color)| (name.to_string(), *color))
+    .collect()
+}
+
+impl Skin {
+  /// Built-in preset for dark terminals (the default)
+  pub fn dark() -> Self {
+    Self {
+      text: TextColors {
+        normal: Color::White,
+        dim: Color::DarkGray,
+        accent: Color::Cyan,
+        highlight: Color::Yellow,
+        emphasis: Color::Magenta,
+        error: Color::Red,
+        success: Color::Green,
+      },
+      header: HeaderColors {
+        title: Color::Cyan,
+        project: Color::Yellow,
+        shortcut: Color::Cyan,
+        badge_fg: Color::Black,
+        badge_bg: Color::Red,
+      },
+      footer: FooterColors {
+        bg: Color::Black,
+        breadcrumb: Color::White,
+        current: Color::Cyan,
+        message: Color::Yellow,
+      },
+      border: BorderColors {
+        normal: Color::Blue,
+        focus: Color::Yellow,
+      },
+      selection: SelectionColors {
+        fg: None,
+        bg: Color::DarkGray,
+        tab_fg: Color::Black,
+        tab_bg: Color::Cyan,
+        inactive_tab: Color::Gray,
+        changed_bg: Color::Indexed(22),
+      },
+      status: StatusColors {
+        todo: Color::White,
+        in_progress: Color::Yellow,
+        done: Color::Green,
+        from_jira: false,
+      },
+      priorities: colors(&[
+        ("Blocker", Color::LightRed),
+        ("Highest", Color::LightRed),
+        ("High", Color::Red),
+        ("Medium", Color::Yellow),
+        ("Low", Color::Green),
+        ("Lowest", Color::DarkGray),
+      ]),
+      issue_types: colors(&[
+        ("Bug", Color::Red),
+        ("Epic", Color::Magenta),
+        ("Story", Color::Green),
+      ]),
+    }
+  }
+
+  /// Built-in preset for light terminals
+  pub fn light() -> Self {
+    Self {
+      text: TextColors {
+        normal: Color::Black,
+        dim: Color::Indexed(244),
+        accent: Color::Blue,
+        highlight: Color::Indexed(130),
+        emphasis: Color::Magenta,
+        error: Color::Red,
+        success: Color::Indexed(28),
+      },
+      header: HeaderColors {
+        title: Color::Blue,
+        project: Color::Indexed(130),
+        shortcut: Color::Blue,
+        badge_fg: Color::White,
+        badge_bg: Color::Red,
+      },
+      footer: FooterColors {
+        bg: Color::Indexed(254),
+        breadcrumb: Color::Black,
+        current: Color::Blue,
+        message: Color::Indexed(130),
+      },
+      border: BorderColors {
+        normal: Color::Indexed(67),
+        focus: Color::Indexed(130),
+      },
+      selection: SelectionColors {
+        fg: None,
+        bg: Color::Indexed(252),
+        tab_fg: Color::White,
+        tab_bg: Color::Blue,
+        inactive_tab: Color::Indexed(240),
+        changed_bg: Color::Indexed(194),
+      },
+      status: StatusColors {
+        todo: Color::Indexed(240),
+        in_progress: Color::Indexed(130),
+        done: Color::Indexed(28),
+        from_jira: false,
+      },
+      priorities: colors(&[
+        ("Blocker", Color::Red),
+        ("Highest", Color::Red),
+        ("High", Color::Indexed(160)),
+        ("Medium", Color::Indexed(130)),
+        ("Low", Color::Indexed(28)),
+        ("Lowest", Color::Indexed(244)),
+      ]),
+      issue_types: colors(&[
+        ("Bug", Color::Red),
+        ("Epic", Color::Magenta),
+        ("Story", Color::Indexed(28)),
+      ]),
+    }
+  }
+
+  /// Load a skin: `dark` and `light` are built in, other names are files
+  /// in the skins directory (`~/.config/j9s/skins/<name>.yaml`), and
+  /// anything ending in `.yaml`/`.yml` is a path.
+  pub fn load(name: &str) -> Result<Self> {
+    match name {
+      "dark" | "default" => return Ok(Self::dark()),
+      "light" => return Ok(Self::light()),
+      _ => {}
+    }
+
+    let path = if name.ends_with(".yaml") || name.ends_with(".yml") {
+      match (name.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(name),
+      }
+    } else {
+      dirs::config_dir()
+        .ok_or_else(|| eyre!("No config directory for skin {}", name))?
+        .join("j9s")
+        .join("skins")
+        .join(format!("{}.yaml", name))
+    };
+    let contents = std::fs::read_to_string(&path)
+      .map_err(|e| eyre!("Failed to read skin {}: {}", path.display(), e))?;
+    serde_yaml::from_str(&contents)
+      .map_err(|e| eyre!("Failed to parse skin {}: {}", path.display(), e))
+  }
+
+  /// Color for a status, by its category, or by Jira's color name for the
+  /// category (`blue-gray`, `yellow`, `green`) when `status.from_jira` is set
+  pub fn status_color(&self, category: StatusCategory, jira_color: Option<&str>) -> Color {
+    if let Some(color) = jira_color
+      .filter(|_| self.status.from_jira)
+      .and_then(jira_color_name)
+    {
+      return color;
+    }
+    match category {
+      StatusCategory::ToDo => self.status.todo,
+      StatusCategory::InProgress => self.status.in_progress,
+      StatusCategory::Done => self.status.done,
+    }
+  }
+
+  /// Color for a priority, if the skin has one
+  pub fn priority_color(&self, priority: &str) -> Option<Color> {
+    lookup(&self.priorities, priority)
+  }
+
+  /// Color for an issue type, if the skin has one
+  pub fn issue_type_color(&self, issue_type: &str) -> Option<Color> {
+    lookup(&self.issue_types, issue_type)
+  }
+
+  /// Style for the selected row of a list
+  pub fn selection_style(&self) -> Style {
+    let style = Style::default()
+      .bg(self.selection.bg)
+      .add_modifier(Modifier::BOLD);
+    match self.selection.fg {
+      Some(fg) => style.fg(fg),
+      None => style,
+    }
+  }
+
+  /// Style for panel borders
+  pub fn border_style(&self, focused: bool) -> Style {
+    Style::default().fg(if focused {
+      self.border.focus
+    } else {
+      self.border.normal
+    })
+  }
+}
+
+/// Terminal color for one of Jira's category color names
+fn jira_color_name(name: &str) -> Option<Color> {
+  Some(match name {
+    "blue-gray" | "medium-gray" => Color::Gray,
+    "blue" => Color::Blue,
+    "yellow" => Color::Yellow,
+    "green" => Color::Green,
+    "red" => Color::Red,
+    "brown" => Color::Indexed(130),
+    "purple" => Color::Magenta,
+    _ => return None,
+  })
+}
+
+fn lookup(colors: &BTreeMap<String, Color>, name: &str) -> Option<Color> {
+  colors
+    .iter()
+    .find(|(key, _)| key.eq_ignore_ascii_case(name))
+    .map(|(_, color)| *color)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_skin_file_overrides_preset() {
+    let skin: Skin = serde_yaml::from_str(
+      "status:\n  done: '#00ff00'\nborder:\n  normal: '33'\npriorities:\n  Critical: red\n",
+    )
+    .unwrap();
+    assert_eq!(skin.status.done, Color::Rgb(0, 255, 0));
+    assert_eq!(skin.status.in_progress, Color::Yellow);
+    assert_eq!(skin.border.normal, Color::Indexed(33));
+    assert_eq!(skin.priority_color("critical"), Some(Color::Red));
+    assert_eq!(skin.priority_color("Medium"), None);
+  }
+
+  #[test]
+  fn test_status_color_by_category() {
+    let skin = Skin::dark();
+    assert_eq!(skin.status_color(StatusCategory::Done, None), Color::Green);
+    assert_eq!(
+      skin.status_color(StatusCategory::InProgress, Some("blue")),
+      Color::Yellow
+    );
+    assert_eq!(skin.status_color(StatusCategory::ToDo, None), Color::White);
+
+    let mut skin = skin;
+    skin.status.from_jira = true;
+    assert_eq!(
+      skin.status_color(StatusCategory::ToDo, Some("blue-gray")),
+      Color::Gray
+    );
+    assert_eq!(
+      skin.status_color(StatusCategory::Done, Some("teal")),
+      Color::Green
+    );
+    assert!(Skin::load("light").is_ok());
+    assert!(Skin::load("/nonexistent/skin.yaml").is_err());
+  }
+
+  #[test]
+  fn test_example_skin_is_dark_preset() {
+    let skin: Skin = serde_yaml::from_str(include_str!("../../skin.example.yaml")).unwrap();
+    let dark = Skin::dark();
+    assert_eq!(skin.selection.changed_bg, dark.selection.changed_bg);
+    assert_eq!(skin.priorities, dark.priorities);
+    assert_eq!(skin.issue_type_color("bug"), Some(Color::Red));
+  }
+}
@@ -1,5 +1,10 @@
+use crate::cache::CacheInfo;
+use crate::config::Config;
+use crate::jira::types::IssueSummary;
+use crate::ui::ListColumn;
 use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
+use std::time::{Duration, Instant};
 
 /// When a shortcut should be shown in the header
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -12,28 +17,28 @@ pub enum ShortcutVisibility {
 /// A keyboard shortcut hint for display in the header
 #[derive(Debug, Clone)]
 pub struct ShortcutInfo {
-  pub key: &'static str,
+  pub key: String,
   pub label: &'static str,
   pub visibility: ShortcutVisibility,
   pub priority: u8, // Lower = shown first
 }
 
 impl ShortcutInfo {
-  pub const fn new(key: &'static str, label: &'static str) -> Self {
+  pub fn new(key: impl Into<String>, label: &'static str) -> Self {
     Self {
-      key,
+      key: key.into(),
       label,
       visibility: ShortcutVisibility::Always,
       priority: 100,
     }
   }
 
-  pub const fn with_priority(mut self, priority: u8) -> Self {
+  pub fn with_priority(mut self, priority: u8) -> Self {
     self.priority = priority;
     self
   }
 
-  pub const fn when_active(mut self) -> Self {
+  pub fn when_active(mut self) -> Self {
     self.visibility = ShortcutVisibility::WhenActive;
     self
   }
@@ -83,6 +88,44 @@ pub trait View {
   /// Called on each tick to allow views to poll async queries
   fn tick(&mut self) {}
 
+  /// Reload the view's data (e.g. after switching offline mode)
+  fn refresh(&mut self) {}
+
+  /// Name of the view in the `refresh.views` config; views without one
+  /// are never auto-refreshed
+  fn refresh_name(&self) -> Option<&'static str> {
+    None
+  }
+
+  /// Refetch data older than `max_age` in the background.
+  /// Called on each tick for the visible view only.
+  fn auto_refresh(&mut self, _max_age: Duration) {}
+
+  /// When the view's data was last fetched (for the "updated" indicator)
+  fn fetched_at(&self) -> Option<Instant> {
+    None
+  }
+
+  /// Apply view settings from the config (list columns, branch template);
+  /// called when the view is shown
+  fn configure(&mut self, _config: &Config) {}
+
+  /// The issues as currently shown, filtered and in display order, with
+  /// their visible columns (for `:export`). `None` if the view has no issue list.
+  fn visible_issues(&self) -> Option<(Vec<IssueSummary>, Vec<ListColumn>)> {
+    None
+  }
+
+  /// Get where the view's current data came from (for the offline indicator)
+  fn data_source(&self) -> Option<CacheInfo> {
+    None
+  }
+
+  /// Take a one-off status message to flash in the footer
+  fn take_message(&mut self) -> Option<String> {
+    None
+  }
+
   /// Get keyboard shortcuts to display in the header
   /// Override this to provide view-specific shortcuts
   fn shortcuts(&self) -> Vec<ShortcutInfo> {
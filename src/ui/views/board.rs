@@ -1,18 +1,23 @@
-use crate::jira::types::{BoardColumn, BoardConfiguration, IssueSummary, StatusInfo};
+use crate::cache::CacheInfo;
+use crate::config::{BoardsConfig, Config};
+use crate::jira::mutations::MutationOutcome;
+use crate::jira::types::{BoardColumn, IssueSummary, StatusInfo};
 use crate::jira::JiraClient;
 use crate::query::{Query, QueryState};
 use crate::ui::components::{
-  FilterBar, FilterBarEvent, FilterFieldPicker, FilterFieldPickerEvent, IssueFilterField,
-  KeyResult, SearchEvent, SearchInput, StatusPicker, StatusPickerEvent,
+  FilterBar, FilterBarEvent, FilterFieldPicker, FilterFieldPickerEvent, IssueActions,
+  IssueFilterField, KeyResult, SearchEvent, SearchInput, StatusPicker, StatusPickerEvent,
 };
-use crate::ui::ensure_valid_selection;
-use crate::ui::renderfns::{status_color, truncate};
+use crate::ui::renderfns::truncate;
+use crate::ui::skin;
 use crate::ui::view::{ShortcutInfo, View, ViewAction};
 use crate::ui::views::IssueDetailView;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::ui::{ensure_valid_selection, Action, Keymap, RecentChanges};
+use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, VecDeque};
+use std::time::{Duration, Instant};
 use tracing::info;
 
 /// Combined board data fetched in parallel
@@ -20,6 +25,16 @@ use tracing::info;
 struct BoardData {
   issues: Vec<IssueSummary>,
   columns: Vec<BoardColumn>,
+  source: CacheInfo,
+}
+
+/// Where to move the selected issue in the board's rank order
+#[derive(Clone, Copy)]
+enum RankMove {
+  Up,
+  Down,
+  Top,
+  Bottom,
 }
 
 /// View for displaying a single board with its issues
@@ -36,6 +51,7 @@ pub struct BoardView {
 
   // Data query
   query: Query<BoardData>,
+  changes: RecentChanges, // Rows changed by the last refresh
 
   // UI state
   list_state: ListState,    // Selection for list mode
@@ -51,39 +67,41 @@ pub struct BoardView {
   search: SearchInput,
   search_filter: Option<String>,
   status_picker: StatusPicker,
+  actions: IssueActions,
+  keys: Keymap,
 
   // Status update state
   pending_issue_key: Option<String>, // Issue key waiting for status picker selection
-  status_mutation: Option<Query<()>>,
+  status_mutation: Option<Query<MutationOutcome>>,
+
+  // Rank changes (issue, other issue, before), sent one at a time so Jira
+  // applies them in order
+  rank_queue: VecDeque<(String, String, bool)>,
+  rank_mutation: Option<Query<MutationOutcome>>,
   error_message: Option<String>,
+  message: Option<String>,
 }
 
 impl BoardView {
-  pub fn new(
-    board_id: u64,
-    board_name: String,
-    jira: JiraClient,
-    hide_swimlanes: BTreeSet<String>,
-  ) -> Self {
+  pub fn new(board_id: u64, board_name: String, jira: JiraClient, boards: &BoardsConfig) -> Self {
     let jira_for_query = jira.clone();
+    let jql = boards.issue_jql();
     let mut query = Query::new(move || {
       let jira = jira_for_query.clone();
+      let jql = jql.clone();
       async move {
         // Fetch all board data in parallel
-        // Filter: unresolved issues + resolved in past 2 weeks
-        let jql = "resolution IS EMPTY OR resolved >= -2w";
         let (issues_result, config_result) = tokio::join!(
-          jira.get_board_issues(board_id, Some(jql)),
+          jira.get_board_issues(board_id, Some(&jql)),
           jira.get_board_configuration(board_id),
         );
 
         let issues = issues_result.map_err(|e| e.to_string())?;
-        let config = config_result.unwrap_or_else(|_| BoardConfiguration {
-          columns: Vec::new(),
-        });
+        let config = config_result.unwrap_or_default();
 
         Ok(BoardData {
-          issues,
+          source: issues.info(),
+          issues: issues.data,
           columns: config.columns,
         })
       }
@@ -93,11 +111,13 @@ impl BoardView {
     query.fetch();
 
     Self {
+      actions: IssueActions::new(jira.clone()),
       board_id,
       board_name,
       jira,
-      hide_swimlanes,
+      hide_swimlanes: boards.hide_swimlanes.clone(),
       query,
+      changes: RecentChanges::new(),
       list_state: ListState::default(),
       swimlane_selected: 0,
       selected_column: 0,
@@ -107,8 +127,12 @@ impl BoardView {
       search: SearchInput::new(),
       search_filter: None,
       status_picker: StatusPicker::new(),
+      keys: Keymap::default(),
       pending_issue_key: None,
       status_mutation: None,
+      rank_queue: VecDeque::new(),
+      rank_mutation: None,
+      message: None,
       error_message: None,
     }
   }
@@ -138,18 +162,19 @@ impl BoardView {
   }
 
   /// Extract the value of a filter field from an issue
-  fn get_field_value(field: IssueFilterField, issue: &IssueSummary) -> Option<String> {
+  fn get_field_value(field: &IssueFilterField, issue: &IssueSummary) -> Option<String> {
     match field {
       IssueFilterField::None => None,
       IssueFilterField::Assignee => issue.assignee.clone(),
       IssueFilterField::Epic => issue.epic.clone(),
       IssueFilterField::Status => Some(issue.status.clone()),
       IssueFilterField::Priority => issue.priority.clone(),
+      IssueFilterField::Custom(name) => issue.custom_field(name).map(String::from),
     }
   }
 
   /// Extract unique values for a filter field from all issues
-  fn extract_filter_values(&self, field: IssueFilterField) -> Vec<Option<String>> {
+  fn extract_filter_values(&self, field: &IssueFilterField) -> Vec<Option<String>> {
     if matches!(field, IssueFilterField::None) {
       return Vec::new();
     }
@@ -172,7 +197,7 @@ impl BoardView {
 
   /// Update filter bar values when data loads
   fn update_filter_values(&mut self) {
-    let values = self.extract_filter_values(self.filter_bar.field());
+    let values = self.extract_filter_values(&self.filter_bar.field());
     self.filter_bar.update_values(values);
   }
 
@@ -187,7 +212,7 @@ impl BoardView {
       issues
         .iter()
         .filter(|issue| {
-          let issue_value = Self::get_field_value(field, issue);
+          let issue_value = Self::get_field_value(&field, issue);
           issue_value == *filter_value
         })
         .collect()
@@ -244,7 +269,7 @@ impl BoardView {
       .title(title)
       .title_alignment(Alignment::Center)
       .borders(Borders::ALL)
-      .border_style(Style::default().fg(Color::Blue));
+      .border_style(skin().border_style(false));
 
     if self.issues().is_empty() && !self.is_loading() {
       let content = if self.query.is_error() {
@@ -254,7 +279,7 @@ impl BoardView {
       };
       let paragraph = Paragraph::new(content)
         .block(block)
-        .style(Style::default().fg(Color::DarkGray));
+        .style(Style::default().fg(skin().text.dim));
       frame.render_widget(paragraph, area);
       return;
     }
@@ -264,12 +289,12 @@ impl BoardView {
       .filtered_issues()
       .iter()
       .map(|issue| {
-        let color = status_color(&issue.status);
+        let color = skin().status_color(issue.status_category, issue.category_color.as_deref());
 
         let line = Line::from(vec![
           Span::styled(
             format!("{:<15}", issue.key),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(skin().text.accent),
           ),
           Span::raw(" "),
           Span::styled(
@@ -279,17 +304,13 @@ impl BoardView {
           Span::raw(" "),
           Span::raw(issue.summary.clone()),
         ]);
-        ListItem::new(line)
+        ListItem::new(line).style(self.changes.style(&issue.key))
       })
       .collect();
 
     let list = List::new(items)
       .block(block)
-      .highlight_style(
-        Style::default()
-          .bg(Color::DarkGray)
-          .add_modifier(Modifier::BOLD),
-      )
+      .highlight_style(skin().selection_style())
       .highlight_symbol("> ");
 
     frame.render_stateful_widget(list, area, &mut self.list_state);
@@ -303,7 +324,7 @@ impl BoardView {
         .title(format!(" {} ", self.board_name))
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Blue));
+        .border_style(skin().border_style(false));
 
       let content = if self.is_loading() {
         "Loading..."
@@ -312,7 +333,7 @@ impl BoardView {
       };
       let paragraph = Paragraph::new(content)
         .block(block)
-        .style(Style::default().fg(Color::DarkGray));
+        .style(Style::default().fg(skin().text.dim));
       frame.render_widget(paragraph, area);
       return;
     }
@@ -330,41 +351,33 @@ impl BoardView {
       let is_selected_column = col_idx == self.selected_column;
       let col_area = col_areas[col_idx];
 
-      let border_color = if is_selected_column {
-        Color::Yellow
-      } else {
-        Color::Blue
-      };
+      let border_style = skin().border_style(is_selected_column);
 
       let title = format!(" {} ({}) ", truncate(&column.name, 15), issues.len());
       let block = Block::default()
         .title(title)
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(border_color));
+        .border_style(border_style);
 
       let items: Vec<ListItem> = issues
         .iter()
         .map(|issue| {
           let issue_id = Line::from(vec![Span::styled(
             &issue.key,
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(skin().text.accent),
           )]);
           let issue_title = Line::from(vec![Span::raw(truncate(
             &issue.summary,
             col_area.width.saturating_sub(4) as usize,
           ))]);
-          ListItem::new(vec![issue_id, issue_title])
+          ListItem::new(vec![issue_id, issue_title]).style(self.changes.style(&issue.key))
         })
         .collect();
 
       let list = List::new(items)
         .block(block)
-        .highlight_style(
-          Style::default()
-            .bg(Color::DarkGray)
-            .add_modifier(Modifier::BOLD),
-        )
+        .highlight_style(skin().selection_style())
         .highlight_symbol("> ");
 
       if is_selected_column {
@@ -471,7 +484,7 @@ impl BoardView {
         "Updating issue {} to status {}",
         issue.key, target_statuses[0].name
       );
-      self.update_issue_status(&issue.key, &target_statuses[0].id);
+      self.update_issue_status(&issue.key, target_statuses[0].clone());
     } else {
       // Multiple statuses - show picker
       self.pending_issue_key = Some(issue.key.clone());
@@ -482,17 +495,16 @@ impl BoardView {
   }
 
   /// Update an issue's status directly
-  fn update_issue_status(&mut self, issue_key: &str, status_id: &str) {
+  fn update_issue_status(&mut self, issue_key: &str, status: StatusInfo) {
     let jira = self.jira.clone();
     let key = issue_key.to_string();
-    let sid = status_id.to_string();
     let mut query = Query::new(move || {
       let jira = jira.clone();
       let key = key.clone();
-      let sid = sid.clone();
+      let status = status.clone();
       async move {
         jira
-          .update_issue_status(&key, &sid)
+          .update_issue_status(&key, &status)
           .await
           .map_err(|e| e.to_string())
       }
@@ -515,6 +527,9 @@ impl BoardView {
     if let Some(err) = query.error() {
       self.error_message = Some(format!("Status update failed: {}", err));
     } else {
+      if query.data() == Some(&MutationOutcome::Queued) {
+        self.message = Some("Status change queued offline (see :pending)".to_string());
+      }
       // Success - refetch board data
       self.query.refetch();
     }
@@ -522,6 +537,98 @@ impl BoardView {
     self.status_mutation = None;
   }
 
+  /// Move the selected issue within its column (swimlane mode) or the
+  /// list, showing the new order right away
+  fn rank_selected(&mut self, to: RankMove) {
+    let (keys, selected): (Vec<String>, Option<usize>) = if self.swimlane_mode {
+      let Some(column) = self.columns().get(self.selected_column).copied() else {
+        return;
+      };
+      let issues = self.issues_for_column(column);
+      (
+        issues.iter().map(|i| i.key.clone()).collect(),
+        Some(self.swimlane_selected),
+      )
+    } else {
+      let issues = self.filtered_issues();
+      (
+        issues.iter().map(|i| i.key.clone()).collect(),
+        self.list_state.selected(),
+      )
+    };
+    let Some(index) = selected.filter(|i| *i < keys.len()) else {
+      return;
+    };
+
+    let last = keys.len() - 1;
+    let target = match to {
+      RankMove::Up => index.checked_sub(1),
+      RankMove::Down => (index < last).then_some(index + 1),
+      RankMove::Top => (index > 0).then_some(0),
+      RankMove::Bottom => (index < last).then_some(last),
+    };
+    let Some(target) = target else {
+      return;
+    };
+    let before = target < index;
+    let (key, other) = (keys[index].clone(), keys[target].clone());
+
+    if let Some(data) = self.query.data_mut() {
+      move_issue(&mut data.issues, &key, &other, before);
+    }
+    if self.swimlane_mode {
+      self.swimlane_selected = target;
+    } else {
+      self.list_state.select(Some(target));
+    }
+
+    self.rank_queue.push_back((key, other, before));
+    self.send_next_rank();
+  }
+
+  /// Send the next queued rank change unless one is still in flight
+  fn send_next_rank(&mut self) {
+    if self.rank_mutation.is_some() {
+      return;
+    }
+    let Some((key, other, before)) = self.rank_queue.pop_front() else {
+      return;
+    };
+    let jira = self.jira.clone();
+    let mut query = Query::new(move || {
+      let jira = jira.clone();
+      let key = key.clone();
+      let other = other.clone();
+      async move {
+        jira
+          .rank_issue(&key, &other, before)
+          .await
+          .map_err(|e| e.to_string())
+      }
+    });
+    query.fetch();
+    self.rank_mutation = Some(query);
+  }
+
+  /// Process the result of a rank mutation
+  fn process_rank_mutation(&mut self) {
+    let Some(query) = self.rank_mutation.take() else {
+      return;
+    };
+
+    if let Some(err) = query.error() {
+      self.error_message = Some(format!("Rank change failed: {}", err));
+      // Back to the order in the cache
+      self.rank_queue.clear();
+      self.query.refetch();
+      return;
+    }
+    if query.data() == Some(&MutationOutcome::Queued) {
+      self.message = Some("Rank change queued offline (see :pending)".to_string());
+    }
+    self.send_next_rank();
+  }
+
   /// Render error message if present
   fn render_error(&self, frame: &mut Frame, area: Rect) {
     if let Some(msg) = &self.error_message {
@@ -544,12 +651,12 @@ impl BoardView {
 
       let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Red))
+        .border_style(Style::default().fg(skin().text.error))
         .title(" Error - press any key to dismiss ");
 
       let paragraph = Paragraph::new(msg.as_str())
         .block(block)
-        .style(Style::default().fg(Color::Red))
+        .style(Style::default().fg(skin().text.error))
         .wrap(ratatui::widgets::Wrap { trim: false });
 
       frame.render_widget(paragraph, error_area);
@@ -562,7 +669,7 @@ impl BoardView {
     match self.filter_picker.handle_key(key) {
       KeyResult::Handled => return Some(ViewAction::None),
       KeyResult::Event(FilterFieldPickerEvent::Selected(field)) => {
-        let values = self.extract_filter_values(field);
+        let values = self.extract_filter_values(&field);
         self.filter_bar.set_field_and_values(field, values);
         return Some(ViewAction::None);
       }
@@ -587,7 +694,15 @@ impl BoardView {
       KeyResult::Handled => return Some(ViewAction::None),
       KeyResult::Event(StatusPickerEvent::Selected(status_id)) => {
         if let Some(issue_key) = self.pending_issue_key.take() {
-          self.update_issue_status(&issue_key, &status_id);
+          let status = self
+            .columns()
+            .iter()
+            .flat_map(|col| col.statuses.iter())
+            .find(|s| s.id == status_id)
+            .cloned();
+          if let Some(status) = status {
+            self.update_issue_status(&issue_key, status);
+          }
         }
         return Some(ViewAction::None);
       }
@@ -598,6 +713,11 @@ impl BoardView {
       KeyResult::NotHandled => {}
     }
 
+    // Copy menu
+    if self.actions.handle_key(key) {
+      return Some(ViewAction::None);
+    }
+
     // Search
     match self.search.handle_key(key) {
       KeyResult::Handled => return Some(ViewAction::None),
@@ -615,37 +735,38 @@ impl BoardView {
   }
 
   fn handle_navigation(&mut self, key: KeyEvent) -> Option<ViewAction> {
+    let action = self.keys.action(&key)?;
     // Mode-specific navigation
     if self.swimlane_mode {
-      match key.code {
-        KeyCode::Char('h') | KeyCode::Left => {
-          if key.modifiers.contains(KeyModifiers::SHIFT) {
-            // Shift+Left: Transition to previous column
-            if self.selected_column > 0 {
-              self.initiate_status_change(self.selected_column - 1);
-            }
-          } else {
-            self.navigate_swimlane(-1, true);
+      match action {
+        Action::MoveLeft => {
+          self.navigate_swimlane(-1, true);
+          Some(ViewAction::None)
+        }
+        Action::MoveRight => {
+          self.navigate_swimlane(1, true);
+          Some(ViewAction::None)
+        }
+        Action::ChangeStatusLeft => {
+          // Transition to previous column
+          if self.selected_column > 0 {
+            self.initiate_status_change(self.selected_column - 1);
           }
           Some(ViewAction::None)
         }
-        KeyCode::Char('l') | KeyCode::Right => {
-          if key.modifiers.contains(KeyModifiers::SHIFT) {
-            // Shift+Right: Transition to next column
-            let num_columns = self.columns().len();
-            if self.selected_column + 1 < num_columns {
-              self.initiate_status_change(self.selected_column + 1);
-            }
-          } else {
-            self.navigate_swimlane(1, true);
+        Action::ChangeStatusRight => {
+          // Transition to next column
+          let num_columns = self.columns().len();
+          if self.selected_column + 1 < num_columns {
+            self.initiate_status_change(self.selected_column + 1);
           }
           Some(ViewAction::None)
         }
-        KeyCode::Char('j') | KeyCode::Down => {
+        Action::MoveDown => {
           self.navigate_swimlane(1, false);
           Some(ViewAction::None)
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::MoveUp => {
           self.navigate_swimlane(-1, false);
           Some(ViewAction::None)
         }
@@ -653,12 +774,12 @@ impl BoardView {
       }
     } else {
       // List mode navigation
-      match key.code {
-        KeyCode::Char('j') | KeyCode::Down => {
+      match action {
+        Action::MoveDown => {
           self.navigate_list(1);
           Some(ViewAction::None)
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::MoveUp => {
           self.navigate_list(-1);
           Some(ViewAction::None)
         }
@@ -668,12 +789,12 @@ impl BoardView {
   }
 
   fn handle_toggles(&mut self, key: KeyEvent) -> Option<ViewAction> {
-    match key.code {
-      KeyCode::Char('f') => {
+    match self.keys.action(&key)? {
+      Action::Filter => {
         self.filter_picker.show();
         Some(ViewAction::None)
       }
-      KeyCode::Char('s') => {
+      Action::ToggleSwimlanes => {
         self.swimlane_mode = !self.swimlane_mode;
         self.list_state.select(Some(0));
         self.swimlane_selected = 0;
@@ -685,18 +806,40 @@ impl BoardView {
   }
 
   fn handle_actions(&mut self, key: KeyEvent) -> Option<ViewAction> {
-    match key.code {
-      KeyCode::Char('r') => {
+    match self.keys.action(&key)? {
+      Action::Refresh => {
         self.query.refetch();
         Some(ViewAction::None)
       }
-      KeyCode::Enter => self.selected_issue().map(|issue| {
+      Action::RankUp => {
+        self.rank_selected(RankMove::Up);
+        Some(ViewAction::None)
+      }
+      Action::RankDown => {
+        self.rank_selected(RankMove::Down);
+        Some(ViewAction::None)
+      }
+      Action::RankTop => {
+        self.rank_selected(RankMove::Top);
+        Some(ViewAction::None)
+      }
+      Action::RankBottom => {
+        self.rank_selected(RankMove::Bottom);
+        Some(ViewAction::None)
+      }
+      Action::OpenDetail => self.selected_issue().map(|issue| {
         ViewAction::Push(Box::new(IssueDetailView::new(
           issue.key.clone(),
           self.jira.clone(),
         )))
       }),
-      KeyCode::Char('q') | KeyCode::Esc => Some(ViewAction::Pop),
+      action @ (Action::GitBranch | Action::OpenInBrowser | Action::Copy) => {
+        if let Some(issue) = self.selected_issue().cloned() {
+          self.actions.run(action, &issue);
+        }
+        Some(ViewAction::None)
+      }
+      Action::Back => Some(ViewAction::Pop),
       _ => None,
     }
   }
@@ -748,6 +891,8 @@ impl View for BoardView {
     // Render status picker if active
     self.status_picker.render_overlay(frame, area);
 
+    self.actions.render_overlay(frame, area);
+
     // Render error message if present
     self.render_error(frame, area);
   }
@@ -767,6 +912,14 @@ impl View for BoardView {
     // Update filter values when data finishes loading
     if was_loading && !self.query.is_loading() && self.query.data().is_some() {
       self.update_filter_values();
+      if let Some(data) = self.query.data() {
+        self.changes.update(
+          data
+            .issues
+            .iter()
+            .map(|i| (i.key.as_str(), i.updated.as_str())),
+        );
+      }
     }
 
     // Poll status mutation if in progress
@@ -776,30 +929,118 @@ impl View for BoardView {
         self.process_status_mutation();
       }
     }
+
+    if let Some(ref mut query) = self.rank_mutation {
+      if query.poll() {
+        self.process_rank_mutation();
+      }
+    }
+  }
+
+  fn refresh(&mut self) {
+    self.query.refetch();
+  }
+
+  fn refresh_name(&self) -> Option<&'static str> {
+    Some("board")
+  }
+
+  fn auto_refresh(&mut self, max_age: Duration) {
+    self.query.refetch_if_older(max_age);
+  }
+
+  fn fetched_at(&self) -> Option<Instant> {
+    self.query.fetched_at()
+  }
+
+  fn data_source(&self) -> Option<CacheInfo> {
+    self.data().map(|d| d.source)
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.keys = config.keys.keymap("board");
+    self.filter_bar.set_keymap(&self.keys);
+    self.filter_picker.set_keymap(&self.keys);
+    self
+      .filter_picker
+      .set_fields(IssueFilterField::with_custom(&config.jira.custom_fields));
+    self.search.set_keymap(&self.keys);
+    self.status_picker.set_keymap(&self.keys);
+    self.actions.configure(config, &self.keys);
+  }
+
+  fn take_message(&mut self) -> Option<String> {
+    self.actions.take_message().or_else(|| self.message.take())
   }
 
   fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    let keys = &self.keys;
     let mut shortcuts = vec![
-      ShortcutInfo::new(":", "command").with_priority(10),
-      ShortcutInfo::new("/", "search").with_priority(20),
-      ShortcutInfo::new("q", "back").with_priority(30),
-      ShortcutInfo::new("r", "refresh").with_priority(100),
-      ShortcutInfo::new("f", "filter").with_priority(101),
+      keys.shortcut(Action::Command, "command").with_priority(10),
+      keys.shortcut(Action::Search, "search").with_priority(20),
+      keys.shortcut(Action::Back, "back").with_priority(30),
+      keys.shortcut(Action::Refresh, "refresh").with_priority(100),
+      keys.shortcut(Action::Filter, "filter").with_priority(101),
+      keys.shortcut(Action::Copy, "copy").with_priority(103),
+      keys
+        .shortcut(Action::OpenInBrowser, "open in browser")
+        .with_priority(104),
+      keys
+        .shortcut(Action::GitBranch, "git branch")
+        .with_priority(105),
     ];
 
     // Filter tab navigation shortcuts
     if self.filter_bar.is_active() {
-      shortcuts.push(ShortcutInfo::new("PgUp/Dn", "filter tab").with_priority(102));
+      shortcuts.push(
+        keys
+          .shortcut_pair(Action::PrevFilterTab, Action::NextFilterTab, "filter tab")
+          .with_priority(102),
+      );
+    }
+
+    if !self.issues().is_empty() {
+      shortcuts.push(
+        keys
+          .shortcut_pair(Action::RankUp, Action::RankDown, "rank")
+          .with_priority(106),
+      );
     }
 
     // Swimlane shortcuts
     if !self.columns().is_empty() {
-      shortcuts.push(ShortcutInfo::new("s", "swimlane").with_priority(110));
+      shortcuts.push(
+        keys
+          .shortcut(Action::ToggleSwimlanes, "swimlane")
+          .with_priority(110),
+      );
       if self.swimlane_mode {
-        shortcuts.push(ShortcutInfo::new("S-h/l", "transition").with_priority(111));
+        shortcuts.push(
+          keys
+            .shortcut_pair(
+              Action::ChangeStatusLeft,
+              Action::ChangeStatusRight,
+              "transition",
+            )
+            .with_priority(111),
+        );
       }
     }
 
     shortcuts
   }
 }
+
+/// Move an issue right before or after another one
+fn move_issue(issues: &mut Vec<IssueSummary>, key: &str, other: &str, before: bool) {
+  let Some(from) = issues.iter().position(|i| i.key == key) else {
+    return;
+  };
+  let issue = issues.remove(from);
+  let to = match issues.iter().position(|i| i.key == other) {
+    Some(to) if before => to,
+    Some(to) => to + 1,
+    None => from,
+  };
+  issues.insert(to, issue);
+}
@@ -1,27 +1,34 @@
+use crate::browser;
+use crate::cache::{CacheInfo, CacheResult};
+use crate::config::{BoardsConfig, Config};
 use crate::jira::types::Board;
 use crate::jira::JiraClient;
 use crate::query::{Query, QueryState};
 use crate::ui::components::{KeyResult, SearchEvent, SearchInput};
 use crate::ui::ensure_valid_selection;
-use crate::ui::view::{View, ViewAction};
+use crate::ui::skin;
+use crate::ui::view::{ShortcutInfo, View, ViewAction};
 use crate::ui::views::BoardView;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::ui::{Action, Keymap};
+use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
-use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
 
 /// View for displaying a list of boards
 pub struct BoardListView {
   jira: JiraClient,
-  hide_swimlanes: BTreeSet<String>,
-  query: Query<Vec<Board>>,
+  boards: BoardsConfig,
+  query: Query<CacheResult<Vec<Board>>>,
   list_state: ListState,
   search: SearchInput,
   search_filter: Option<String>,
+  keys: Keymap,
+  message: Option<String>,
 }
 
 impl BoardListView {
-  pub fn new(project: Option<String>, jira: JiraClient, hide_swimlanes: BTreeSet<String>) -> Self {
+  pub fn new(project: Option<String>, jira: JiraClient, boards: BoardsConfig) -> Self {
     let jira_for_query = jira.clone();
     let mut query = Query::new(move || {
       let jira = jira_for_query.clone();
@@ -39,16 +46,24 @@ impl BoardListView {
 
     Self {
       jira,
-      hide_swimlanes,
+      boards,
       query,
       list_state: ListState::default(),
       search: SearchInput::new(),
       search_filter: None,
+      keys: Keymap::default(),
+      message: None,
     }
   }
 
+  /// Start with the list filtered, as if searched with `/`
+  pub fn with_filter(mut self, query: &str) -> Self {
+    self.search_filter = Some(query.to_string()).filter(|q| !q.is_empty());
+    self
+  }
+
   fn boards(&self) -> &[Board] {
-    self.query.data().map(|v| v.as_slice()).unwrap_or(&[])
+    self.query.data().map(|r| r.data.as_slice()).unwrap_or(&[])
   }
 
   fn filtered_boards(&self) -> Vec<&Board> {
@@ -90,7 +105,7 @@ impl BoardListView {
       .title(title)
       .title_alignment(Alignment::Center)
       .borders(Borders::ALL)
-      .border_style(Style::default().fg(Color::Blue));
+      .border_style(skin().border_style(false));
 
     if self.boards().is_empty() && !self.is_loading() {
       let content = if self.query.is_error() {
@@ -100,7 +115,7 @@ impl BoardListView {
       };
       let paragraph = Paragraph::new(content)
         .block(block)
-        .style(Style::default().fg(Color::DarkGray));
+        .style(Style::default().fg(skin().text.dim));
       frame.render_widget(paragraph, area);
       return;
     }
@@ -111,11 +126,14 @@ impl BoardListView {
       .iter()
       .map(|board| {
         let line = Line::from(vec![
-          Span::styled(format!("{:<8}", board.id), Style::default().fg(Color::Cyan)),
+          Span::styled(
+            format!("{:<8}", board.id),
+            Style::default().fg(skin().text.accent),
+          ),
           Span::raw(" "),
           Span::styled(
             format!("{:<10}", board.board_type),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(skin().text.highlight),
           ),
           Span::raw(" "),
           Span::raw(board.name.clone()),
@@ -126,11 +144,7 @@ impl BoardListView {
 
     let list = List::new(items)
       .block(block)
-      .highlight_style(
-        Style::default()
-          .bg(Color::DarkGray)
-          .add_modifier(Modifier::BOLD),
-      )
+      .highlight_style(skin().selection_style())
       .highlight_symbol("> ");
 
     frame.render_stateful_widget(list, area, &mut self.list_state);
@@ -151,12 +165,12 @@ impl BoardListView {
   }
 
   fn handle_navigation(&mut self, key: KeyEvent) -> Option<ViewAction> {
-    match key.code {
-      KeyCode::Char('j') | KeyCode::Down => {
+    match self.keys.action(&key)? {
+      Action::MoveDown => {
         self.list_state.select_next();
         Some(ViewAction::None)
       }
-      KeyCode::Char('k') | KeyCode::Up => {
+      Action::MoveUp => {
         self.list_state.select_previous();
         Some(ViewAction::None)
       }
@@ -165,25 +179,36 @@ impl BoardListView {
   }
 
   fn handle_actions(&mut self, key: KeyEvent) -> Option<ViewAction> {
-    match key.code {
-      KeyCode::Char('r') => {
+    match self.keys.action(&key)? {
+      Action::Refresh => {
         self.query.refetch();
         Some(ViewAction::None)
       }
-      KeyCode::Enter => {
+      Action::OpenDetail => {
         if let Some(idx) = self.list_state.selected() {
           if let Some(board) = self.filtered_boards().get(idx) {
             return Some(ViewAction::Push(Box::new(BoardView::new(
               board.id,
               board.name.clone(),
               self.jira.clone(),
-              self.hide_swimlanes.clone(),
+              &self.boards,
             ))));
           }
         }
         None
       }
-      KeyCode::Char('q') | KeyCode::Esc => Some(ViewAction::Pop),
+      Action::OpenInBrowser => {
+        let url = self
+          .list_state
+          .selected()
+          .and_then(|idx| self.filtered_boards().get(idx).copied())
+          .map(|board| self.jira.board_url(board));
+        if let Some(url) = url {
+          self.message = Some(browser::open_with_message(&url));
+        }
+        Some(ViewAction::None)
+      }
+      Action::Back => Some(ViewAction::Pop),
       _ => None,
     }
   }
@@ -211,4 +236,51 @@ impl View for BoardListView {
   fn tick(&mut self) {
     self.query.poll();
   }
+
+  fn refresh(&mut self) {
+    self.query.refetch();
+  }
+
+  fn refresh_name(&self) -> Option<&'static str> {
+    Some("boards")
+  }
+
+  fn auto_refresh(&mut self, max_age: Duration) {
+    self.query.refetch_if_older(max_age);
+  }
+
+  fn fetched_at(&self) -> Option<Instant> {
+    self.query.fetched_at()
+  }
+
+  fn data_source(&self) -> Option<CacheInfo> {
+    self.query.data().map(|r| r.info())
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.keys = config.keys.keymap("boards");
+    self.search.set_keymap(&self.keys);
+  }
+
+  fn take_message(&mut self) -> Option<String> {
+    self.message.take()
+  }
+
+  fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    vec![
+      self
+        .keys
+        .shortcut(Action::Command, "command")
+        .with_priority(10),
+      self
+        .keys
+        .shortcut(Action::Search, "search")
+        .with_priority(20),
+      self.keys.shortcut(Action::Back, "back").with_priority(30),
+      self
+        .keys
+        .shortcut(Action::OpenInBrowser, "open in browser")
+        .with_priority(40),
+    ]
+  }
 }
@@ -0,0 +1,585 @@
+use crate::cache::CacheInfo;
+use crate::config::Config;
+use crate::jira::burndown::{BurnUnit, Burndown, SprintIssue};
+use crate::jira::metrics::parse_time;
+use crate::jira::types::Sprint;
+use crate::jira::JiraClient;
+use crate::query::{Query, QueryState};
+use crate::ui::skin;
+use crate::ui::view::{ShortcutInfo, View, ViewAction};
+use crate::ui::{Action, Keymap};
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use ratatui::symbols::Marker;
+use ratatui::widgets::{
+  Axis, Block, Borders, Chart, Dataset, GraphType, List, ListItem, Paragraph,
+};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// The issues of a sprint with their histories
+#[derive(Clone)]
+struct SprintData {
+  issues: Vec<SprintIssue>,
+  columns: usize,
+  /// Issues left out for lack of a creation time or history
+  skipped: usize,
+  source: CacheInfo,
+}
+
+/// Which lines the chart shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BurnChart {
+  /// What's left to do
+  Down,
+  /// What's done, under the scope
+  Up,
+}
+
+/// Burndown or burnup of a scrum board's active sprint
+pub struct BurndownView {
+  board_id: u64,
+  board_name: String,
+  jira: JiraClient,
+  sprints: Query<Vec<Sprint>>,
+  /// Index of the shown sprint among the active ones
+  selected: usize,
+  /// The shown sprint's issues, by sprint id
+  query: Option<(u64, Query<SprintData>)>,
+  chart: BurnChart,
+  /// Chosen with the keyboard; otherwise points when issues are estimated
+  unit: Option<BurnUnit>,
+  keys: Keymap,
+}
+
+impl BurndownView {
+  pub fn new(board_id: u64, board_name: String, jira: JiraClient) -> Self {
+    let jira_for_query = jira.clone();
+    let mut sprints = Query::new(move || {
+      let jira = jira_for_query.clone();
+      async move {
+        jira
+          .get_active_sprints(board_id)
+          .await
+          .map_err(|e| e.to_string())
+      }
+    });
+    sprints.fetch();
+
+    Self {
+      board_id,
+      board_name,
+      jira,
+      sprints,
+      selected: 0,
+      query: None,
+      chart: BurnChart::Down,
+      unit: None,
+      keys: Keymap::default(),
+    }
+  }
+
+  fn sprint(&self) -> Option<&Sprint> {
+    self.sprints.data()?.get(self.selected)
+  }
+
+  fn data(&self) -> Option<&SprintData> {
+    self.query.as_ref().and_then(|(_, q)| q.data())
+  }
+
+  /// Load the shown sprint's issues and histories, unless already loading
+  fn load_sprint(&mut self) {
+    let Some(sprint) = self.sprint().cloned() else {
+      return;
+    };
+    if self.query.as_ref().is_some_and(|(id, _)| *id == sprint.id) {
+      return;
+    }
+
+    let jira = self.jira.clone();
+    let (board_id, board_name) = (self.board_id, self.board_name.clone());
+    let sprint_id = sprint.id;
+    let mut query = Query::new(move || {
+      let (jira, board_name, sprint) = (jira.clone(), board_name.clone(), sprint.clone());
+      async move {
+        let (current, removed, config) = tokio::join!(
+          jira.get_sprint_issues(sprint.id),
+          jira.get_removed_sprint_issues(&board_name, &sprint.name),
+          jira.get_board_configuration(board_id),
+        );
+        let current = current.map_err(|e| e.to_string())?;
+        let config = config.map_err(|e| e.to_string())?;
+        // Not every Jira has the JQL function; the chart still works
+        // without removed issues, it just doesn't show them
+        let removed = removed.map(|r| r.data).unwrap_or_default();
+
+        let in_sprint: HashSet<&str> = current.data.iter().map(|i| i.key.as_str()).collect();
+        let mut issues = current.data.clone();
+        issues.extend(
+          removed
+            .into_iter()
+            .filter(|issue| !in_sprint.contains(issue.key.as_str())),
+        );
+
+        let histories: HashMap<String, _> =
+          jira.get_changelogs(&issues).await.into_iter().collect();
+        let sprint_issues: Vec<SprintIssue> = issues
+          .iter()
+          .filter_map(|issue| {
+            SprintIssue::new(
+              issue,
+              histories.get(&issue.key)?,
+              &config.columns,
+              &sprint,
+              in_sprint.contains(issue.key.as_str()),
+            )
+          })
+          .collect();
+
+        Ok(SprintData {
+          skipped: issues.len() - sprint_issues.len(),
+          issues: sprint_issues,
+          columns: config.columns.len(),
+          source: current.info(),
+        })
+      }
+    });
+    query.fetch();
+    self.query = Some((sprint_id, query));
+  }
+
+  fn unit(&self) -> BurnUnit {
+    self.unit.unwrap_or_else(|| {
+      let estimated = self
+        .data()
+        .is_some_and(|d| d.issues.iter().any(|i| i.points > 0.0));
+      if estimated {
+        BurnUnit::Points
+      } else {
+        BurnUnit::Issues
+      }
+    })
+  }
+
+  fn burndown(&self, sprint: &Sprint, data: &SprintData) -> Option<Burndown> {
+    let start = parse_time(sprint.start.as_deref()?)?;
+    let end = parse_time(sprint.end.as_deref()?)?;
+    Some(Burndown::compute(
+      &data.issues,
+      data.columns,
+      self.unit(),
+      start,
+      end,
+      Utc::now(),
+    ))
+  }
+
+  fn render_message(&self, frame: &mut Frame, area: Rect, title: String, text: String) {
+    let block = Block::default()
+      .title(title)
+      .title_alignment(Alignment::Center)
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(false));
+    let paragraph = Paragraph::new(text)
+      .block(block)
+      .style(Style::default().fg(skin().text.dim));
+    frame.render_widget(paragraph, area);
+  }
+
+  fn render_summary(&self, frame: &mut Frame, area: Rect, sprint: &Sprint, burndown: &Burndown) {
+    let count = self.sprints.data().map_or(0, Vec::len);
+    let title = if count > 1 {
+      format!(" {} ({}/{}) ", sprint.name, self.selected + 1, count)
+    } else {
+      format!(" {} ", sprint.name)
+    };
+    let block = Block::default()
+      .title(title)
+      .title_alignment(Alignment::Center)
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(false));
+
+    let label = Style::default().fg(skin().text.dim);
+    let unit = match self.unit() {
+      BurnUnit::Issues => "issues",
+      BurnUnit::Points => "points",
+    };
+    let value = |series: &[(f64, f64)]| series.last().map_or(0.0, |(_, y)| *y);
+    let day = burndown.remaining.last().map_or(0.0, |(x, _)| *x);
+    let (added, removed) =
+      burndown.changes.iter().fold(
+        (0, 0),
+        |(a, r), c| if c.added { (a + 1, r) } else { (a, r + 1) },
+      );
+
+    let mut dates = vec![
+      Span::styled("Day ", label),
+      Span::raw(format!(
+        "{:.0} of {:.0}",
+        day.ceil(),
+        burndown.length.ceil()
+      )),
+      Span::styled("  Ends ", label),
+      Span::raw(format_date(sprint.end.as_deref())),
+    ];
+    if let Some(goal) = &sprint.goal {
+      dates.push(Span::styled("  Goal ", label));
+      dates.push(Span::raw(goal.clone()));
+    }
+    let mut totals = vec![
+      Span::styled(format!("In {}: ", unit), label),
+      Span::styled("committed ", label),
+      Span::raw(format_value(burndown.committed)),
+      Span::styled("  scope ", label),
+      Span::raw(format_value(value(&burndown.scope))),
+      Span::styled("  done ", label),
+      Span::styled(
+        format_value(value(&burndown.done)),
+        Style::default().fg(skin().status.done),
+      ),
+      Span::styled("  remaining ", label),
+      Span::raw(format_value(value(&burndown.remaining))),
+      Span::styled("  changes ", label),
+      Span::styled(
+        format!("+{}", added),
+        Style::default().fg(skin().text.success),
+      ),
+      Span::raw(" "),
+      Span::styled(
+        format!("-{}", removed),
+        Style::default().fg(skin().text.error),
+      ),
+    ];
+    if let Some(skipped) = self.data().map(|d| d.skipped).filter(|s| *s > 0) {
+      totals.push(Span::styled(
+        format!("  ({} issues without history)", skipped),
+        label,
+      ));
+    }
+
+    let paragraph = Paragraph::new(vec![Line::from(dates), Line::from(totals)]).block(block);
+    frame.render_widget(paragraph, area);
+  }
+
+  fn render_chart(&self, frame: &mut Frame, area: Rect, sprint: &Sprint, burndown: &Burndown) {
+    let (title, ideal, lines) = match self.chart {
+      BurnChart::Down => (
+        " Burndown ",
+        [(0.0, burndown.committed), (burndown.length, 0.0)],
+        vec![(
+          "remaining",
+          skin().status.in_progress,
+          burndown.remaining.as_slice(),
+        )],
+      ),
+      BurnChart::Up => (
+        " Burnup ",
+        [(0.0, 0.0), (burndown.length, burndown.committed)],
+        vec![
+          ("scope", skin().status.todo, burndown.scope.as_slice()),
+          ("done", skin().status.done, burndown.done.as_slice()),
+        ],
+      ),
+    };
+    // Scope changes are marked on the line the scope shows in
+    let marked = lines[0].2;
+    let marks = |added: bool| -> Vec<(f64, f64)> {
+      burndown
+        .changes
+        .iter()
+        .filter(|c| c.added == added)
+        .map(|c| (c.day, value_at(marked, c.day)))
+        .collect()
+    };
+    let (added, removed) = (marks(true), marks(false));
+
+    let mut datasets = vec![Dataset::default()
+      .name("ideal")
+      .marker(Marker::Braille)
+      .graph_type(GraphType::Line)
+      .style(Style::default().fg(skin().text.dim))
+      .data(&ideal)];
+    for (name, color, points) in &lines {
+      datasets.push(
+        Dataset::default()
+          .name(*name)
+          .marker(Marker::Braille)
+          .graph_type(GraphType::Line)
+          .style(Style::default().fg(*color))
+          .data(points),
+      );
+    }
+    for (name, color, points) in [
+      ("added", skin().text.success, &added),
+      ("removed", skin().text.error, &removed),
+    ] {
+      if !points.is_empty() {
+        datasets.push(
+          Dataset::default()
+            .name(name)
+            .marker(Marker::Dot)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+            .data(points),
+        );
+      }
+    }
+
+    let max = lines
+      .iter()
+      .flat_map(|(_, _, points)| points.iter().map(|(_, y)| *y))
+      .fold(burndown.committed.max(1.0), f64::max);
+    let axis_style = Style::default().fg(skin().text.dim);
+    let chart = Chart::new(datasets)
+      .block(
+        Block::default()
+          .title(title)
+          .title_alignment(Alignment::Center)
+          .borders(Borders::ALL)
+          .border_style(skin().border_style(false)),
+      )
+      .x_axis(
+        Axis::default()
+          .style(axis_style)
+          .bounds([0.0, burndown.length.max(1.0)])
+          .labels([
+            format_date(sprint.start.as_deref()),
+            format_date(sprint.end.as_deref()),
+          ]),
+      )
+      .y_axis(
+        Axis::default()
+          .style(axis_style)
+          .bounds([0.0, max])
+          .labels(["0".to_string(), format_value(max)]),
+      );
+    frame.render_widget(chart, area);
+  }
+
+  fn render_changes(&self, frame: &mut Frame, area: Rect, burndown: &Burndown) {
+    let block = Block::default()
+      .title(format!(" Scope changes ({}) ", burndown.changes.len()))
+      .title_alignment(Alignment::Center)
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(false));
+
+    let items: Vec<ListItem> = burndown
+      .changes
+      .iter()
+      .rev()
+      .map(|change| {
+        let (sign, color) = if change.added {
+          ("+", skin().text.success)
+        } else {
+          ("-", skin().text.error)
+        };
+        ListItem::new(Line::from(vec![
+          Span::styled(
+            change
+              .at
+              .with_timezone(&Local)
+              .format("%m-%d %H:%M ")
+              .to_string(),
+            Style::default().fg(skin().text.dim),
+          ),
+          Span::styled(format!("{} ", sign), Style::default().fg(color)),
+          Span::styled(change.key.clone(), Style::default().fg(skin().text.accent)),
+        ]))
+      })
+      .collect();
+    frame.render_widget(List::new(items).block(block), area);
+  }
+
+  fn select_sprint(&mut self, offset: isize) {
+    let count = self.sprints.data().map_or(0, Vec::len);
+    if count > 1 {
+      self.selected = (self.selected as isize + offset).rem_euclid(count as isize) as usize;
+    }
+  }
+}
+
+/// A sprint date as "2024-05-01", in local time
+fn format_date(timestamp: Option<&str>) -> String {
+  timestamp
+    .and_then(parse_time)
+    .map(|t: DateTime<Utc>| t.with_timezone(&Local).format("%Y-%m-%d").to_string())
+    .unwrap_or_else(|| "-".to_string())
+}
+
+/// Points like 2.5 keep their fraction; counts and whole points don't
+fn format_value(value: f64) -> String {
+  format!("{}", (value * 10.0).round() / 10.0)
+}
+
+/// The value of a step line at `x`, after any step there
+fn value_at(points: &[(f64, f64)], x: f64) -> f64 {
+  points
+    .iter()
+    .take_while(|(px, _)| *px <= x)
+    .last()
+    .map_or(0.0, |(_, y)| *y)
+}
+
+impl View for BurndownView {
+  fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+    match self.keys.action(&key) {
+      Some(Action::NextTab) => {
+        self.chart = match self.chart {
+          BurnChart::Down => BurnChart::Up,
+          BurnChart::Up => BurnChart::Down,
+        };
+        ViewAction::None
+      }
+      Some(Action::ToggleUnit) => {
+        self.unit = Some(match self.unit() {
+          BurnUnit::Issues => BurnUnit::Points,
+          BurnUnit::Points => BurnUnit::Issues,
+        });
+        ViewAction::None
+      }
+      Some(Action::PrevFilterTab) => {
+        self.select_sprint(-1);
+        ViewAction::None
+      }
+      Some(Action::NextFilterTab) => {
+        self.select_sprint(1);
+        ViewAction::None
+      }
+      Some(Action::Refresh) => {
+        self.refresh();
+        ViewAction::None
+      }
+      Some(Action::Back) => ViewAction::Pop,
+      _ => ViewAction::None,
+    }
+  }
+
+  fn render(&mut self, frame: &mut Frame, area: Rect) {
+    let title = format!(" {} ", self.board_name);
+    let sprint = match (self.sprints.state(), self.sprint()) {
+      (QueryState::Error(e), _) => {
+        let text = format!("Error: {}\n\nPress 'r' to retry.", e);
+        return self.render_message(frame, area, title, text);
+      }
+      (_, Some(sprint)) => sprint.clone(),
+      (_, None) if self.sprints.is_loading() => {
+        let text = "Loading sprints...".to_string();
+        return self.render_message(frame, area, title, text);
+      }
+      (_, None) => {
+        let text = "No active sprint on this board.".to_string();
+        return self.render_message(frame, area, title, text);
+      }
+    };
+
+    let title = format!(" {} ", sprint.name);
+    let Some((_, query)) = &self.query else {
+      return;
+    };
+    let Some(data) = query.data() else {
+      let text = match query.state() {
+        QueryState::Error(e) => format!("Error: {}\n\nPress 'r' to retry.", e),
+        _ => "Loading the sprint's issues and their histories...".to_string(),
+      };
+      return self.render_message(frame, area, title, text);
+    };
+    let Some(burndown) = self.burndown(&sprint, data) else {
+      let text = "The sprint has no start or end date.".to_string();
+      return self.render_message(frame, area, title, text);
+    };
+
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(4), Constraint::Min(5)])
+      .split(area);
+    let body = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints([Constraint::Min(20), Constraint::Length(28)])
+      .split(rows[1]);
+
+    self.render_summary(frame, rows[0], &sprint, &burndown);
+    self.render_chart(frame, body[0], &sprint, &burndown);
+    self.render_changes(frame, body[1], &burndown);
+  }
+
+  fn breadcrumb_label(&self) -> String {
+    format!("{} [Burndown]", self.board_name)
+  }
+
+  fn tick(&mut self) {
+    self.sprints.poll();
+    // The sprint shown may have been closed since
+    if self.selected >= self.sprints.data().map_or(0, Vec::len) {
+      self.selected = 0;
+    }
+    self.load_sprint();
+    if let Some((_, query)) = &mut self.query {
+      query.poll();
+    }
+  }
+
+  fn refresh(&mut self) {
+    self.sprints.refetch();
+    if let Some((_, query)) = &mut self.query {
+      query.refetch();
+    }
+  }
+
+  fn refresh_name(&self) -> Option<&'static str> {
+    Some("burndown")
+  }
+
+  fn auto_refresh(&mut self, max_age: Duration) {
+    if let Some((_, query)) = &mut self.query {
+      query.refetch_if_older(max_age);
+    }
+  }
+
+  fn fetched_at(&self) -> Option<Instant> {
+    self.query.as_ref().and_then(|(_, q)| q.fetched_at())
+  }
+
+  fn data_source(&self) -> Option<CacheInfo> {
+    self.data().map(|d| d.source)
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.keys = config.keys.keymap("burndown");
+  }
+
+  fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    let keys = &self.keys;
+    let mut shortcuts = vec![
+      keys.shortcut(Action::Command, "command").with_priority(10),
+      keys.shortcut(Action::Back, "back").with_priority(30),
+      keys.shortcut(Action::Refresh, "refresh").with_priority(100),
+      keys
+        .shortcut(
+          Action::NextTab,
+          match self.chart {
+            BurnChart::Down => "burnup",
+            BurnChart::Up => "burndown",
+          },
+        )
+        .with_priority(101),
+      keys
+        .shortcut(
+          Action::ToggleUnit,
+          match self.unit() {
+            BurnUnit::Issues => "story points",
+            BurnUnit::Points => "issue count",
+          },
+        )
+        .with_priority(102),
+    ];
+    if self.sprints.data().is_some_and(|s| s.len() > 1) {
+      shortcuts.push(
+        keys
+          .shortcut_pair(Action::PrevFilterTab, Action::NextFilterTab, "sprint")
+          .with_priority(103),
+      );
+    }
+    shortcuts
+  }
+}
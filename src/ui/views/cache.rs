@@ -0,0 +1,271 @@
+use crate::cache::CacheStats;
+use crate::config::Config;
+use crate::jira::JiraClient;
+use crate::ui::ensure_valid_selection;
+use crate::ui::renderfns::{format_age, format_duration, format_size};
+use crate::ui::skin;
+use crate::ui::view::{ShortcutInfo, View, ViewAction};
+use crate::ui::{Action, Keymap};
+use chrono::Utc;
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+
+/// View showing what's in the local cache, with maintenance actions
+pub struct CacheView {
+  jira: JiraClient,
+  max_size: Option<u64>,
+  stats: CacheStats,
+  list_state: ListState,
+  error: Option<String>,
+  keys: Keymap,
+  message: Option<String>,
+}
+
+impl CacheView {
+  pub fn new(jira: JiraClient, max_size: Option<u64>) -> Self {
+    let mut view = Self {
+      jira,
+      max_size,
+      stats: CacheStats::default(),
+      list_state: ListState::default(),
+      error: None,
+      keys: Keymap::default(),
+      message: None,
+    };
+    view.reload();
+    view
+  }
+
+  /// Reload statistics from the cache database (local, so no Query needed)
+  fn reload(&mut self) {
+    match self.jira.cache().storage().stats() {
+      Ok(stats) => {
+        self.stats = stats;
+        self.error = None;
+      }
+      Err(e) => self.error = Some(e.to_string()),
+    }
+  }
+
+  /// Show the outcome of an action in the footer and reload
+  fn finish(&mut self, result: color_eyre::Result<String>) {
+    self.message = Some(result.unwrap_or_else(|e| e.to_string()));
+    self.reload();
+  }
+
+  fn render_summary(&self, frame: &mut Frame, area: Rect) {
+    let block = Block::default()
+      .title(" Cache ")
+      .title_alignment(Alignment::Center)
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(false));
+
+    let label = Style::default().fg(skin().text.dim);
+    let size = match self.max_size {
+      Some(max) => format!(
+        "{} of {}",
+        format_size(self.stats.size_bytes),
+        format_size(max)
+      ),
+      None => format_size(self.stats.size_bytes),
+    };
+
+    let mut lines = vec![Line::from(vec![
+      Span::styled("Size: ", label),
+      Span::raw(size),
+      Span::styled("  Reclaimable: ", label),
+      Span::raw(format_size(self.stats.free_bytes)),
+      Span::styled("  Unreferenced: ", label),
+      Span::raw(self.stats.unreferenced.to_string()),
+      Span::styled("  Pending changes: ", label),
+      Span::raw(self.stats.pending_mutations.to_string()),
+    ])];
+
+    for entity in &self.stats.entities {
+      let stale_time = self.jira.cache().stale_time_for(&entity.entity_type);
+      lines.push(Line::from(vec![
+        Span::styled(
+          format!("{:<16}", entity.entity_type),
+          Style::default().fg(skin().text.accent),
+        ),
+        Span::raw(format!("{:>6} ", entity.count)),
+        Span::raw(format!("{:>10}", format_size(entity.bytes))),
+        Span::styled("  stale after ", label),
+        Span::raw(format_duration(stale_time)),
+      ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+  }
+
+  fn render_queries(&mut self, frame: &mut Frame, area: Rect) {
+    ensure_valid_selection(&mut self.list_state, self.stats.queries.len());
+
+    let title = match &self.error {
+      Some(e) => format!(" Queries (error: {}) ", e),
+      None => format!(" Queries ({}) ", self.stats.queries.len()),
+    };
+
+    let block = Block::default()
+      .title(title)
+      .title_alignment(Alignment::Center)
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(false));
+
+    if self.stats.queries.is_empty() {
+      let paragraph = Paragraph::new("Nothing cached yet.")
+        .block(block)
+        .style(Style::default().fg(skin().text.dim));
+      frame.render_widget(paragraph, area);
+      return;
+    }
+
+    let now = Utc::now();
+    let items: Vec<ListItem> = self
+      .stats
+      .queries
+      .iter()
+      .map(|q| {
+        ListItem::new(Line::from(vec![
+          Span::styled(
+            format!("{:<14}", q.entity_type),
+            Style::default().fg(skin().text.highlight),
+          ),
+          Span::raw(format!("{:>6} ", q.result_count)),
+          Span::styled(
+            format!("{:<10}", format_age(now - q.cached_at)),
+            Style::default().fg(skin().text.dim),
+          ),
+          Span::raw(q.query_description.clone()),
+        ]))
+      })
+      .collect();
+
+    let list = List::new(items)
+      .block(block)
+      .highlight_style(skin().selection_style())
+      .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, area, &mut self.list_state);
+  }
+
+  // Key handling helpers for or_else chain pattern
+  fn handle_navigation(&mut self, key: KeyEvent) -> Option<ViewAction> {
+    match self.keys.action(&key)? {
+      Action::MoveDown => {
+        self.list_state.select_next();
+        Some(ViewAction::None)
+      }
+      Action::MoveUp => {
+        self.list_state.select_previous();
+        Some(ViewAction::None)
+      }
+      _ => None,
+    }
+  }
+
+  fn handle_actions(&mut self, key: KeyEvent) -> Option<ViewAction> {
+    let storage = self.jira.cache().storage().clone();
+    match self.keys.action(&key)? {
+      Action::Refresh => {
+        self.reload();
+        Some(ViewAction::None)
+      }
+      Action::Delete => {
+        let selected = self
+          .list_state
+          .selected()
+          .and_then(|i| self.stats.queries.get(i))
+          .map(|q| q.query_hash.clone());
+        if let Some(hash) = selected {
+          let result = storage
+            .remove_query(&hash)
+            .map(|()| format!("Dropped {}", hash));
+          self.finish(result);
+        }
+        Some(ViewAction::None)
+      }
+      Action::ClearAll => {
+        let result = storage.clear().map(|()| "Cache cleared".to_string());
+        self.finish(result);
+        Some(ViewAction::None)
+      }
+      Action::Vacuum => {
+        let result = storage.vacuum().map(|(before, after)| {
+          format!(
+            "Vacuumed: {} -> {}",
+            format_size(before),
+            format_size(after)
+          )
+        });
+        self.finish(result);
+        Some(ViewAction::None)
+      }
+      Action::Back => Some(ViewAction::Pop),
+      _ => None,
+    }
+  }
+}
+
+impl View for CacheView {
+  fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+    self
+      .handle_navigation(key)
+      .or_else(|| self.handle_actions(key))
+      .unwrap_or(ViewAction::None)
+  }
+
+  fn render(&mut self, frame: &mut Frame, area: Rect) {
+    let summary_height = self.stats.entities.len() as u16 + 3;
+    let chunks = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(summary_height), Constraint::Min(3)])
+      .split(area);
+
+    self.render_summary(frame, chunks[0]);
+    self.render_queries(frame, chunks[1]);
+  }
+
+  fn breadcrumb_label(&self) -> String {
+    "Cache".to_string()
+  }
+
+  fn refresh(&mut self) {
+    self.reload();
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.keys = config.keys.keymap("cache");
+  }
+
+  fn take_message(&mut self) -> Option<String> {
+    self.message.take()
+  }
+
+  fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    vec![
+      self
+        .keys
+        .shortcut(Action::Command, "command")
+        .with_priority(10),
+      self
+        .keys
+        .shortcut(Action::Delete, "drop query")
+        .with_priority(20),
+      self
+        .keys
+        .shortcut(Action::ClearAll, "clear all")
+        .with_priority(30),
+      self
+        .keys
+        .shortcut(Action::Vacuum, "vacuum")
+        .with_priority(40),
+      self
+        .keys
+        .shortcut(Action::Refresh, "reload")
+        .with_priority(50),
+      self.keys.shortcut(Action::Back, "back").with_priority(60),
+    ]
+  }
+}
@@ -1,20 +1,29 @@
+use crate::cache::{CacheInfo, CacheResult};
+use crate::config::Config;
 use crate::jira::types::{BoardColumn, IssueSummary, StatusInfo};
 use crate::jira::JiraClient;
 use crate::query::Query;
-use crate::ui::components::{IssueFilterField, KeyResult, TicketPanel, TicketPanelEvent};
+use crate::ui::components::{
+  IssueActions, IssueFilterField, KeyResult, TicketPanel, TicketPanelEvent,
+};
+use crate::ui::skin;
 use crate::ui::view::{ShortcutInfo, ShortcutProvider, View, ViewAction};
 use crate::ui::views::IssueDetailView;
+use crate::ui::{Action, ListColumn};
 use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 
 /// View for displaying epic details and its child issues
 pub struct EpicDetailView {
   jira: JiraClient,
   epic: IssueSummary,
-  query: Query<Vec<IssueSummary>>,
+  query: Query<CacheResult<Vec<IssueSummary>>>,
   panel: TicketPanel<IssueFilterField>,
+  actions: IssueActions,
+  message: Option<String>,
 }
 
 impl EpicDetailView {
@@ -36,10 +45,12 @@ impl EpicDetailView {
     query.fetch();
 
     Self {
+      actions: IssueActions::new(jira.clone()),
       jira,
       epic,
       query,
       panel: TicketPanel::new(Vec::new()), // Will set columns when data loads
+      message: None,
     }
   }
 
@@ -53,6 +64,8 @@ impl EpicDetailView {
         .or_insert_with(|| StatusInfo {
           id: issue.status_id.clone(),
           name: issue.status.clone(),
+          category: Some(issue.status_category),
+          category_color: issue.category_color.clone(),
         });
     }
 
@@ -69,7 +82,11 @@ impl EpicDetailView {
 
 impl View for EpicDetailView {
   fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
-    let items = self.query.data().map(|v| v.as_slice()).unwrap_or(&[]);
+    let items = self.query.data().map(|r| r.data.as_slice()).unwrap_or(&[]);
+
+    if self.actions.handle_key(key) {
+      return ViewAction::None;
+    }
 
     match self.panel.handle_key(key, items) {
       KeyResult::Handled => ViewAction::None,
@@ -81,7 +98,13 @@ impl View for EpicDetailView {
         ViewAction::None
       }
       KeyResult::Event(TicketPanelEvent::Back) => ViewAction::Pop,
-      KeyResult::Event(TicketPanelEvent::FilterChanged) => ViewAction::None,
+      KeyResult::Event(TicketPanelEvent::FilterChanged | TicketPanelEvent::LoadChildren(_)) => {
+        ViewAction::None
+      }
+      KeyResult::Event(TicketPanelEvent::IssueAction(action, issue)) => {
+        self.actions.run(action, &issue);
+        ViewAction::None
+      }
       KeyResult::NotHandled => ViewAction::None,
     }
   }
@@ -97,16 +120,25 @@ impl View for EpicDetailView {
     let line1 = Line::from(vec![
       Span::styled(
         format!(" {} ", self.epic.key),
-        Style::default().fg(Color::Cyan).bold(),
+        Style::default().fg(skin().text.accent).bold(),
       ),
-      Span::styled(&self.epic.summary, Style::default().fg(Color::White)),
+      Span::styled(&self.epic.summary, Style::default().fg(skin().text.normal)),
     ]);
 
     let line2 = Line::from(vec![
-      Span::styled(" Status: ", Style::default().fg(Color::DarkGray)),
-      Span::styled(&self.epic.status, Style::default().fg(Color::Yellow)),
-      Span::styled("  Type: ", Style::default().fg(Color::DarkGray)),
-      Span::styled(&self.epic.issue_type, Style::default().fg(Color::Magenta)),
+      Span::styled(" Status: ", Style::default().fg(skin().text.dim)),
+      Span::styled(
+        &self.epic.status,
+        Style::default().fg(skin().status_color(
+          self.epic.status_category,
+          self.epic.category_color.as_deref(),
+        )),
+      ),
+      Span::styled("  Type: ", Style::default().fg(skin().text.dim)),
+      Span::styled(
+        &self.epic.issue_type,
+        Style::default().fg(skin().text.emphasis),
+      ),
     ]);
 
     let text = Text::from(vec![line1, line2]);
@@ -114,11 +146,12 @@ impl View for EpicDetailView {
     frame.render_widget(para, chunks[0]);
 
     // Child issues
-    let items = self.query.data().map(|v| v.as_slice()).unwrap_or(&[]);
+    let items = self.query.data().map(|r| r.data.as_slice()).unwrap_or(&[]);
     let is_loading = self.query.is_loading();
     self
       .panel
       .render(frame, chunks[1], items, "Child Issues", is_loading);
+    self.actions.render_overlay(frame, area);
   }
 
   fn breadcrumb_label(&self) -> String {
@@ -130,20 +163,68 @@ impl View for EpicDetailView {
     self.query.poll();
 
     if was_loading && !self.query.is_loading() {
-      if let Some(data) = self.query.data() {
+      if let Some(data) = self.query.data().map(|r| &r.data) {
         // Derive columns from child issues' statuses
         let columns = Self::derive_columns(data);
         self.panel.set_columns(columns);
         self.panel.update_filter_values(data);
+        self.panel.track_changes(data);
       }
     }
   }
 
+  fn refresh(&mut self) {
+    self.query.refetch();
+  }
+
+  fn refresh_name(&self) -> Option<&'static str> {
+    Some("epic")
+  }
+
+  fn auto_refresh(&mut self, max_age: Duration) {
+    self.query.refetch_if_older(max_age);
+  }
+
+  fn fetched_at(&self) -> Option<Instant> {
+    self.query.fetched_at()
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.panel.set_list_columns(&config.list.columns);
+    self.panel.set_hide_done(config.list.hide_done);
+    self
+      .panel
+      .set_filter_fields(IssueFilterField::with_custom(&config.jira.custom_fields));
+    let keys = config.keys.keymap("epic");
+    self.panel.set_keymap(&keys);
+    self.actions.configure(config, &keys);
+  }
+
+  fn visible_issues(&self) -> Option<(Vec<IssueSummary>, Vec<ListColumn>)> {
+    let items = self.query.data().map(|r| r.data.as_slice()).unwrap_or(&[]);
+    let issues = self
+      .panel
+      .filtered_items(items)
+      .into_iter()
+      .cloned()
+      .collect();
+    Some((issues, self.panel.list_columns().to_vec()))
+  }
+
+  fn take_message(&mut self) -> Option<String> {
+    self.actions.take_message().or_else(|| self.message.take())
+  }
+
+  fn data_source(&self) -> Option<CacheInfo> {
+    self.query.data().map(|r| r.info())
+  }
+
   fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    let keys = self.panel.keys();
     let mut shortcuts = vec![
-      ShortcutInfo::new(":", "command").with_priority(10),
-      ShortcutInfo::new("/", "search").with_priority(20),
-      ShortcutInfo::new("q", "back").with_priority(30),
+      keys.shortcut(Action::Command, "command").with_priority(10),
+      keys.shortcut(Action::Search, "search").with_priority(20),
+      keys.shortcut(Action::Back, "back").with_priority(30),
     ];
 
     shortcuts.extend(self.panel.shortcuts());
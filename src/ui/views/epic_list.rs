@@ -1,25 +1,71 @@
-use crate::jira::types::IssueSummary;
+use crate::cache::{CacheInfo, CacheResult};
+use crate::config::Config;
+use crate::jira::progress::{EpicProgress, Rollup};
+use crate::jira::types::{IssueSummary, StatusCategory};
 use crate::jira::JiraClient;
 use crate::query::Query;
-use crate::ui::components::{IssueFilterField, KeyResult, TicketPanel, TicketPanelEvent};
+use crate::ui::components::{
+  IssueActions, IssueFilterField, KeyResult, TicketPanel, TicketPanelEvent,
+};
+use crate::ui::renderfns::progress_bar;
 use crate::ui::view::{ShortcutInfo, ShortcutProvider, View, ViewAction};
 use crate::ui::views::EpicDetailView;
+use crate::ui::{skin, Action, ListColumn};
 use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Progress bar, issue counts by status category and story points of an
+/// epic, at a fixed width so rows line up
+fn progress_line(progress: &EpicProgress) -> Line<'static> {
+  let skin = skin();
+  let count = |n: usize, category| {
+    Span::styled(
+      format!(" {:>3}", n),
+      Style::default().fg(skin.status_color(category, None)),
+    )
+  };
+  let points = if progress.points_total > 0.0 {
+    format!("{}/{} pts", progress.points_done, progress.points_total)
+  } else {
+    String::new()
+  };
+  Line::from(vec![
+    Span::styled(
+      progress_bar(progress.done_ratio(), 10),
+      Style::default().fg(skin.status_color(StatusCategory::Done, None)),
+    ),
+    Span::raw(format!(" {:>3.0}%", progress.done_ratio() * 100.0)),
+    count(progress.todo, StatusCategory::ToDo),
+    count(progress.in_progress, StatusCategory::InProgress),
+    count(progress.done, StatusCategory::Done),
+    Span::styled(
+      format!(" {:<13}", points),
+      Style::default().fg(skin.text.dim),
+    ),
+  ])
+}
 
 /// View for displaying a list of epics in a project
 pub struct EpicListView {
   jira: JiraClient,
   project: String,
-  query: Query<Vec<IssueSummary>>,
+  query: Query<CacheResult<Vec<IssueSummary>>>,
+  /// Issues of all the listed epics, for their progress
+  children: Option<Query<CacheResult<Vec<IssueSummary>>>>,
+  /// Progress of the epics, recounted for the issues that changed
+  rollup: Rollup,
   panel: TicketPanel<IssueFilterField>,
+  actions: IssueActions,
+  message: Option<String>,
 }
 
 impl EpicListView {
   pub fn new(project: String, jira: JiraClient) -> Self {
     let mut query = if project.is_empty() {
       // No project configured
-      Query::new(|| async { Ok(Vec::new()) })
+      Query::new(|| async { Ok(CacheResult::from_network(Vec::new())) })
     } else {
       let jira_for_query = jira.clone();
       let project_for_query = project.clone();
@@ -33,21 +79,87 @@ impl EpicListView {
     query.fetch();
 
     Self {
+      actions: IssueActions::new(jira.clone()),
       jira,
       project,
       query,
+      children: None,
+      rollup: Rollup::default(),
       panel: TicketPanel::list_only(),
+      message: None,
     }
   }
 
   fn title(&self) -> String {
     format!("Epics [{}]", self.project)
   }
+
+  /// Fetch the issues of all listed epics, to roll up their progress. The
+  /// searches are cached, so reloads only fetch what changed.
+  fn load_progress(&mut self) {
+    let keys: Vec<String> = self
+      .query
+      .data()
+      .map(|r| r.data.iter().map(|epic| epic.key.clone()).collect())
+      .unwrap_or_default();
+    if keys.is_empty() {
+      self.children = None;
+      self.panel.set_row_details(HashMap::new());
+      return;
+    }
+
+    let jira = self.jira.clone();
+    let mut query = Query::new(move || {
+      let jira = jira.clone();
+      let keys = keys.clone();
+      async move {
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+        jira
+          .get_epics_issues(&keys)
+          .await
+          .map_err(|e| e.to_string())
+      }
+    });
+    query.fetch();
+    self.children = Some(query);
+  }
+
+  /// Count the loaded issues that changed into the epics' progress, and
+  /// show it
+  fn update_progress(&mut self) {
+    let Some(children) = &self.children else {
+      return;
+    };
+    if let Some(err) = children.error() {
+      self.message = Some(format!("Failed to load epic progress: {}", err));
+      return;
+    }
+    let Some(issues) = children.data().map(|r| &r.data) else {
+      return;
+    };
+    self.rollup.update(issues);
+    let details = self
+      .query
+      .data()
+      .map(|r| r.data.as_slice())
+      .unwrap_or(&[])
+      .iter()
+      .map(|epic| {
+        let progress = self.rollup.get(&epic.key);
+        (epic.key.clone(), progress_line(&progress))
+      })
+      .collect();
+    self.panel.set_row_details(details);
+  }
 }
 
 impl View for EpicListView {
   fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
-    let items = self.query.data().map(|v| v.as_slice()).unwrap_or(&[]);
+    let items = self.query.data().map(|r| r.data.as_slice()).unwrap_or(&[]);
+
+    if self.actions.handle_key(key) {
+      return ViewAction::None;
+    }
 
     match self.panel.handle_key(key, items) {
       KeyResult::Handled => ViewAction::None,
@@ -59,17 +171,24 @@ impl View for EpicListView {
         ViewAction::None
       }
       KeyResult::Event(TicketPanelEvent::Back) => ViewAction::Pop,
-      KeyResult::Event(TicketPanelEvent::FilterChanged) => ViewAction::None,
+      KeyResult::Event(TicketPanelEvent::FilterChanged | TicketPanelEvent::LoadChildren(_)) => {
+        ViewAction::None
+      }
+      KeyResult::Event(TicketPanelEvent::IssueAction(action, issue)) => {
+        self.actions.run(action, &issue);
+        ViewAction::None
+      }
       KeyResult::NotHandled => ViewAction::None,
     }
   }
 
   fn render(&mut self, frame: &mut Frame, area: Rect) {
-    let items = self.query.data().map(|v| v.as_slice()).unwrap_or(&[]);
+    let items = self.query.data().map(|r| r.data.as_slice()).unwrap_or(&[]);
     let title = self.title();
     let is_loading = self.query.is_loading();
 
     self.panel.render(frame, area, items, &title, is_loading);
+    self.actions.render_overlay(frame, area);
   }
 
   fn breadcrumb_label(&self) -> String {
@@ -89,17 +208,74 @@ impl View for EpicListView {
     self.query.poll();
 
     if was_loading && !self.query.is_loading() {
-      if let Some(data) = self.query.data() {
+      if let Some(data) = self.query.data().map(|r| &r.data) {
         self.panel.update_filter_values(data);
+        self.panel.track_changes(data);
       }
+      self.load_progress();
     }
+
+    if let Some(children) = &mut self.children {
+      let was_loading = children.is_loading();
+      children.poll();
+      if was_loading && !children.is_loading() {
+        self.update_progress();
+      }
+    }
+  }
+
+  fn refresh(&mut self) {
+    self.query.refetch();
+  }
+
+  fn refresh_name(&self) -> Option<&'static str> {
+    Some("epics")
+  }
+
+  fn auto_refresh(&mut self, max_age: Duration) {
+    self.query.refetch_if_older(max_age);
+  }
+
+  fn fetched_at(&self) -> Option<Instant> {
+    self.query.fetched_at()
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.panel.set_list_columns(&config.list.columns);
+    self.panel.set_hide_done(config.list.hide_done);
+    self
+      .panel
+      .set_filter_fields(IssueFilterField::with_custom(&config.jira.custom_fields));
+    let keys = config.keys.keymap("epics");
+    self.panel.set_keymap(&keys);
+    self.actions.configure(config, &keys);
+  }
+
+  fn visible_issues(&self) -> Option<(Vec<IssueSummary>, Vec<ListColumn>)> {
+    let items = self.query.data().map(|r| r.data.as_slice()).unwrap_or(&[]);
+    let issues = self
+      .panel
+      .filtered_items(items)
+      .into_iter()
+      .cloned()
+      .collect();
+    Some((issues, self.panel.list_columns().to_vec()))
+  }
+
+  fn take_message(&mut self) -> Option<String> {
+    self.actions.take_message().or_else(|| self.message.take())
+  }
+
+  fn data_source(&self) -> Option<CacheInfo> {
+    self.query.data().map(|r| r.info())
   }
 
   fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    let keys = self.panel.keys();
     let mut shortcuts = vec![
-      ShortcutInfo::new(":", "command").with_priority(10),
-      ShortcutInfo::new("/", "search").with_priority(20),
-      ShortcutInfo::new("q", "back").with_priority(30),
+      keys.shortcut(Action::Command, "command").with_priority(10),
+      keys.shortcut(Action::Search, "search").with_priority(20),
+      keys.shortcut(Action::Back, "back").with_priority(30),
     ];
 
     shortcuts.extend(self.panel.shortcuts());
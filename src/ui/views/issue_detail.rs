@@ -1,22 +1,86 @@
-use crate::jira::types::Issue;
+use crate::cache::{CacheInfo, CacheResult};
+use crate::config::Config;
+use crate::jira::fields::{CustomField, FieldKind};
+use crate::jira::mutations::MutationOutcome;
+use crate::jira::types::{ChangelogEntry, Issue, StatusInfo};
 use crate::jira::JiraClient;
 use crate::query::{Query, QueryState};
+use crate::ui::components::{
+  IssueActions, KeyResult, PromptEvent, PromptInput, StatusPicker, StatusPickerEvent,
+};
+use crate::ui::renderfns::{format_timestamp, truncate};
+use crate::ui::skin;
 use crate::ui::view::{Shortcut, View, ViewAction};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::ui::{Action, Keymap};
+use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use std::time::{Duration, Instant};
+
+/// What the open prompt is editing
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PromptTarget {
+  Comment,
+  Summary,
+  Field(CustomField),
+}
+
+/// What the open picker is choosing
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PickerTarget {
+  /// The field to edit
+  Field,
+  /// A value for a select field
+  Option(CustomField),
+  /// The field to show the history of
+  HistoryField,
+}
+
+/// The tabs of the view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+  Details,
+  History,
+}
+
+type ChangelogQuery = Query<CacheResult<Vec<ChangelogEntry>>>;
+
+/// Picker id for the summary among the custom fields
+const SUMMARY_FIELD: &str = "summary";
 
 /// View for displaying issue details
 pub struct IssueDetailView {
   key: String,
-  query: Query<Issue>,
+  jira: JiraClient,
+  query: Query<CacheResult<Issue>>,
+  /// The configured custom fields, shown below the status
+  fields: Query<Vec<CustomField>>,
+  tab: Tab,
+  /// The issue's history, with the `updated` time it was loaded for
+  changelog: Option<(String, ChangelogQuery)>,
+  /// Only changes of this field are shown in the history
+  history_field: Option<String>,
+  history_state: ListState,
+
+  // Editing state
+  prompt: PromptInput,
+  prompt_target: Option<PromptTarget>,
+  picker: StatusPicker,
+  picker_target: Option<PickerTarget>,
+  /// Allowed values being loaded for a select field
+  options: Option<(CustomField, Query<Vec<StatusInfo>>)>,
+  actions: IssueActions,
+  mutation: Option<(String, Query<MutationOutcome>)>,
+  keys: Keymap,
+  message: Option<String>,
 }
 
 impl IssueDetailView {
   pub fn new(key: String, jira: JiraClient) -> Self {
     let issue_key = key.clone();
+    let jira_for_query = jira.clone();
     let mut query = Query::new(move || {
-      let jira = jira.clone();
+      let jira = jira_for_query.clone();
       let key = issue_key.clone();
       async move { jira.get_issue(&key).await.map_err(|e| e.to_string()) }
     });
@@ -24,7 +88,241 @@ impl IssueDetailView {
     // Start fetching immediately
     query.fetch();
 
-    Self { key, query }
+    let jira_for_fields = jira.clone();
+    let mut fields = Query::new(move || {
+      let jira = jira_for_fields.clone();
+      async move { Ok(jira.custom_fields().await) }
+    });
+    fields.fetch();
+
+    Self {
+      actions: IssueActions::new(jira.clone()),
+      key,
+      jira,
+      query,
+      fields,
+      tab: Tab::Details,
+      changelog: None,
+      history_field: None,
+      history_state: ListState::default(),
+      prompt: PromptInput::new(),
+      prompt_target: None,
+      picker: StatusPicker::new(),
+      picker_target: None,
+      options: None,
+      mutation: None,
+      keys: Keymap::default(),
+      message: None,
+    }
+  }
+
+  /// Run a mutation in the background, labelled for the result message
+  fn start_mutation<F, Fut>(&mut self, label: impl Into<String>, run: F)
+  where
+    F: Fn(JiraClient, String) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = color_eyre::Result<MutationOutcome>> + Send + 'static,
+  {
+    let jira = self.jira.clone();
+    let key = self.key.clone();
+    let mut query = Query::new(move || {
+      let fut = run(jira.clone(), key.clone());
+      async move { fut.await.map_err(|e| e.to_string()) }
+    });
+    query.fetch();
+    self.mutation = Some((label.into(), query));
+  }
+
+  /// Load the history when it's shown, again whenever the issue has changed
+  fn load_changelog(&mut self) {
+    if self.tab != Tab::History {
+      return;
+    }
+    let Some(updated) = self.query.data().map(|r| r.data.updated.clone()) else {
+      return;
+    };
+    if self
+      .changelog
+      .as_ref()
+      .is_some_and(|(loaded_for, _)| *loaded_for == updated)
+    {
+      return;
+    }
+
+    let jira = self.jira.clone();
+    let key = self.key.clone();
+    let updated_for_query = updated.clone();
+    let mut query = Query::new(move || {
+      let (jira, key, updated) = (jira.clone(), key.clone(), updated_for_query.clone());
+      async move {
+        jira
+          .get_changelog(&key, &updated)
+          .await
+          .map_err(|e| e.to_string())
+      }
+    });
+    query.fetch();
+    self.changelog = Some((updated, query));
+  }
+
+  /// The changes shown in the history, newest first
+  fn history(&self) -> Vec<&ChangelogEntry> {
+    let Some(entries) = self.changelog.as_ref().and_then(|(_, q)| q.data()) else {
+      return Vec::new();
+    };
+    entries
+      .data
+      .iter()
+      .rev()
+      .filter(|e| self.history_field.as_ref().is_none_or(|f| e.field == *f))
+      .collect()
+  }
+
+  /// Pick the field to show the history of
+  fn pick_history_field(&mut self) {
+    let Some(entries) = self.changelog.as_ref().and_then(|(_, q)| q.data()) else {
+      return;
+    };
+    let mut names: Vec<&str> = entries.data.iter().map(|e| e.field.as_str()).collect();
+    names.sort_by_key(|n| n.to_lowercase());
+    names.dedup();
+    let fields = std::iter::once(StatusInfo {
+      id: String::new(),
+      name: "All fields".to_string(),
+      ..Default::default()
+    })
+    .chain(names.into_iter().map(|name| StatusInfo {
+      id: name.to_string(),
+      name: name.to_string(),
+      ..Default::default()
+    }))
+    .collect();
+    self.picker.show("History of".to_string(), fields);
+    self.picker_target = Some(PickerTarget::HistoryField);
+  }
+
+  /// The configured custom fields that exist in Jira
+  fn custom_fields(&self) -> &[CustomField] {
+    self.fields.data().map(Vec::as_slice).unwrap_or_default()
+  }
+
+  /// Open the editor for a field: a prompt for typed values, a picker for
+  /// select fields
+  fn edit_field(&mut self, field_id: &str) {
+    let Some(issue) = self.query.data().map(|r| &r.data) else {
+      return;
+    };
+    if field_id == SUMMARY_FIELD {
+      let summary = issue.summary.clone();
+      self.prompt_target = Some(PromptTarget::Summary);
+      self.prompt.show("Summary", &summary);
+      return;
+    }
+    let Some(field) = self
+      .custom_fields()
+      .iter()
+      .find(|f| f.id == field_id)
+      .cloned()
+    else {
+      return;
+    };
+    let current = issue
+      .custom_fields
+      .get(&field.name)
+      .cloned()
+      .unwrap_or_default();
+
+    let title = match field.kind {
+      FieldKind::Text | FieldKind::Number => field.name.clone(),
+      FieldKind::Date => format!("{} (YYYY-MM-DD)", field.name),
+      FieldKind::User => format!("{} (name, email, me or none)", field.name),
+      FieldKind::Select => {
+        let jira = self.jira.clone();
+        let (key, id) = (self.key.clone(), field.id.clone());
+        let mut options = Query::new(move || {
+          let (jira, key, id) = (jira.clone(), key.clone(), id.clone());
+          async move {
+            jira
+              .field_options(&key, &id)
+              .await
+              .map_err(|e| e.to_string())
+          }
+        });
+        options.fetch();
+        self.options = Some((field, options));
+        return;
+      }
+      FieldKind::Other => {
+        self.message = Some(format!("{} can't be edited in j9s", field.name));
+        return;
+      }
+    };
+    self.prompt_target = Some(PromptTarget::Field(field));
+    self.prompt.show(&title, &current);
+  }
+
+  /// Show the allowed values of a select field once they're loaded
+  fn process_options(&mut self) {
+    let Some((_, query)) = &mut self.options else {
+      return;
+    };
+    query.poll();
+    if query.is_loading() {
+      return;
+    }
+    let Some((field, query)) = self.options.take() else {
+      return;
+    };
+    match (query.data(), query.error()) {
+      (_, Some(err)) => self.message = Some(format!("{}: {}", field.name, err)),
+      (Some(options), _) => {
+        self.picker.show(field.name.clone(), options.clone());
+        self.picker_target = Some(PickerTarget::Option(field));
+      }
+      _ => {}
+    }
+  }
+
+  /// Save a value typed into a custom field's prompt
+  fn submit_field(&mut self, field: CustomField, value: String) {
+    let label = field.name.clone();
+    if field.kind == FieldKind::User {
+      let user = if value.is_empty() {
+        "none".to_string()
+      } else {
+        value
+      };
+      self.start_mutation(label, move |jira, key| {
+        let (id, user) = (field.id.clone(), user.clone());
+        async move { jira.set_user_field(&key, &id, &user).await }
+      });
+      return;
+    }
+    match field.input_value(&value) {
+      Ok(json) => self.start_mutation(label, move |jira, key| {
+        let (id, json) = (field.id.clone(), json.clone());
+        async move { jira.edit_field(&key, &id, json).await }
+      }),
+      Err(e) => self.message = Some(e),
+    }
+  }
+
+  /// Report a finished mutation and reload the issue
+  fn process_mutation(&mut self) {
+    let Some((label, query)) = &mut self.mutation else {
+      return;
+    };
+    query.poll();
+    if query.is_loading() {
+      return;
+    }
+
+    self.message = Some(match (query.data(), query.error()) {
+      (_, Some(err)) => format!("{} failed: {}", label, err),
+      (Some(MutationOutcome::Queued), _) => format!("{} queued offline (see :pending)", label),
+      _ => format!("{} saved", label),
+    });
+    self.mutation = None;
+    self.query.refetch();
   }
 
   fn render_detail(&self, frame: &mut Frame, area: Rect) {
@@ -38,7 +336,7 @@ impl IssueDetailView {
       .title(title)
       .title_alignment(Alignment::Center)
       .borders(Borders::ALL)
-      .border_style(Style::default().fg(Color::Blue));
+      .border_style(skin().border_style(false));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -46,53 +344,83 @@ impl IssueDetailView {
     // Show loading or error state
     if self.query.is_loading() {
       let paragraph =
-        Paragraph::new("Loading issue details...").style(Style::default().fg(Color::DarkGray));
+        Paragraph::new("Loading issue details...").style(Style::default().fg(skin().text.dim));
       frame.render_widget(paragraph, inner);
       return;
     }
 
     if let Some(error) = self.query.error() {
       let paragraph = Paragraph::new(format!("Error: {}\n\nPress 'r' to retry.", error))
-        .style(Style::default().fg(Color::Red));
+        .style(Style::default().fg(skin().text.error));
       frame.render_widget(paragraph, inner);
       return;
     }
 
     let issue = match self.query.data() {
-      Some(issue) => issue,
+      Some(result) => &result.data,
       None => return,
     };
 
     // Layout for issue details
+    let custom_fields = self.custom_fields();
+    let header_height = if custom_fields.is_empty() { 3 } else { 4 };
     let chunks = Layout::default()
       .direction(Direction::Vertical)
       .constraints([
-        Constraint::Length(3), // Header (summary, status, assignee)
-        Constraint::Length(1), // Separator
-        Constraint::Min(1),    // Description
+        Constraint::Length(header_height), // Header (summary, status, assignee, custom fields)
+        Constraint::Length(1),             // Separator
+        Constraint::Min(1),                // Description
       ])
       .split(inner);
 
     // Header
-    let header = vec![
+    let status_color = skin().status_color(issue.status_category, issue.category_color.as_deref());
+    let mut status_line = vec![
+      Span::styled("Status: ", Style::default().fg(skin().text.dim)),
+      Span::styled(&issue.status, Style::default().fg(status_color)),
+    ];
+    if let Some(resolution) = &issue.resolution {
+      status_line.push(Span::styled(
+        format!(" ({})", resolution),
+        Style::default().fg(skin().text.dim),
+      ));
+    }
+    status_line.extend([
+      Span::raw("  "),
+      Span::styled("Assignee: ", Style::default().fg(skin().text.dim)),
+      Span::raw(issue.assignee.as_deref().unwrap_or("Unassigned")),
+    ]);
+    let mut header = vec![
       Line::from(vec![
-        Span::styled("Summary: ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Summary: ", Style::default().fg(skin().text.dim)),
         Span::raw(&issue.summary),
       ]),
-      Line::from(vec![
-        Span::styled("Status: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(&issue.status, Style::default().fg(Color::Yellow)),
-        Span::raw("  "),
-        Span::styled("Assignee: ", Style::default().fg(Color::DarkGray)),
-        Span::raw(issue.assignee.as_deref().unwrap_or("Unassigned")),
-      ]),
+      Line::from(status_line),
     ];
+    if !custom_fields.is_empty() {
+      let mut spans = Vec::new();
+      for (i, field) in custom_fields.iter().enumerate() {
+        if i > 0 {
+          spans.push(Span::raw("  "));
+        }
+        let value = issue
+          .custom_fields
+          .get(&field.name)
+          .map_or("-", String::as_str);
+        spans.push(Span::styled(
+          format!("{}: ", field.name),
+          Style::default().fg(skin().text.dim),
+        ));
+        spans.push(Span::raw(value.to_string()));
+      }
+      header.push(Line::from(spans));
+    }
     let header_para = Paragraph::new(header);
     frame.render_widget(header_para, chunks[0]);
 
     // Separator
     let sep = Paragraph::new("─".repeat(chunks[1].width as usize))
-      .style(Style::default().fg(Color::DarkGray));
+      .style(Style::default().fg(skin().text.dim));
     frame.render_widget(sep, chunks[1]);
 
     // Description
@@ -103,14 +431,233 @@ impl IssueDetailView {
     frame.render_widget(desc_para, chunks[2]);
   }
 
-  // Key handling helper for or_else chain pattern (consistency with other views)
+  fn render_tabs(&self, frame: &mut Frame, area: Rect) {
+    let mut spans = Vec::new();
+    for (i, (tab, label)) in [(Tab::Details, " Details "), (Tab::History, " History ")]
+      .into_iter()
+      .enumerate()
+    {
+      if i > 0 {
+        spans.push(Span::styled("│", Style::default().fg(skin().text.dim)));
+      }
+      let style = if self.tab == tab {
+        Style::default()
+          .fg(skin().selection.tab_fg)
+          .bg(skin().selection.tab_bg)
+      } else {
+        Style::default().fg(skin().selection.inactive_tab)
+      };
+      spans.push(Span::styled(label, style));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+  }
+
+  fn render_history(&mut self, frame: &mut Frame, area: Rect) {
+    let state = self.changelog.as_ref().map(|(_, q)| q.state());
+    let history = self.history();
+    let filter = self
+      .history_field
+      .as_ref()
+      .map(|f| format!(" [{}]", f))
+      .unwrap_or_default();
+    let title = match state {
+      None | Some(QueryState::Loading) => format!(" {} history (loading...) ", self.key),
+      Some(QueryState::Error(e)) => format!(" {} history (error: {}) ", self.key, e),
+      _ => format!(
+        " {} history ({} changes){} ",
+        self.key,
+        history.len(),
+        filter
+      ),
+    };
+
+    let block = Block::default()
+      .title(title)
+      .title_alignment(Alignment::Center)
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(false));
+
+    if history.is_empty() {
+      let text = match state {
+        Some(QueryState::Success) => "No changes.",
+        _ => "",
+      };
+      let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(skin().text.dim))
+        .block(block);
+      frame.render_widget(paragraph, area);
+      return;
+    }
+
+    let items: Vec<ListItem> = history
+      .iter()
+      .map(|entry| {
+        let author = entry.author.as_deref().unwrap_or("Automation");
+        ListItem::new(Line::from(vec![
+          Span::styled(
+            format!("{:<17}", format_timestamp(&entry.created)),
+            Style::default().fg(skin().text.dim),
+          ),
+          Span::raw(format!("{:<21}", truncate(author, 20))),
+          Span::styled(
+            format!("{:<16}", truncate(&entry.field, 15)),
+            Style::default().fg(skin().text.highlight),
+          ),
+          Span::styled(
+            entry.from.as_deref().unwrap_or("-").to_string(),
+            Style::default().fg(skin().text.dim),
+          ),
+          Span::styled(" → ", Style::default().fg(skin().text.dim)),
+          Span::raw(entry.to.as_deref().unwrap_or("-").to_string()),
+        ]))
+      })
+      .collect();
+
+    let list = List::new(items)
+      .block(block)
+      .highlight_style(skin().selection_style());
+    frame.render_stateful_widget(list, area, &mut self.history_state);
+  }
+
+  // Key handling helpers for or_else chain pattern
+  fn handle_overlays(&mut self, key: KeyEvent) -> Option<ViewAction> {
+    if self.actions.handle_key(key) {
+      return Some(ViewAction::None);
+    }
+
+    match self.picker.handle_key(key) {
+      KeyResult::Handled => return Some(ViewAction::None),
+      KeyResult::Event(StatusPickerEvent::Selected(id)) => {
+        match self.picker_target.take() {
+          Some(PickerTarget::Field) => self.edit_field(&id),
+          Some(PickerTarget::HistoryField) => {
+            self.history_field = (!id.is_empty()).then_some(id);
+            self.history_state.select(None);
+          }
+          Some(PickerTarget::Option(field)) => {
+            self.start_mutation(field.name.clone(), move |jira, key| {
+              let (field_id, value) = (field.id.clone(), serde_json::json!({ "id": id }));
+              async move { jira.edit_field(&key, &field_id, value).await }
+            })
+          }
+          None => {}
+        }
+        return Some(ViewAction::None);
+      }
+      KeyResult::Event(StatusPickerEvent::Cancelled) => {
+        self.picker_target = None;
+        return Some(ViewAction::None);
+      }
+      KeyResult::NotHandled => {}
+    }
+
+    match self.prompt.handle_key(key) {
+      KeyResult::Handled => Some(ViewAction::None),
+      KeyResult::Event(PromptEvent::Submitted(value)) => {
+        let value = value.trim().to_string();
+        match self.prompt_target.take() {
+          Some(PromptTarget::Field(field)) => self.submit_field(field, value),
+          Some(_) if value.is_empty() => {}
+          Some(PromptTarget::Comment) => self.start_mutation("Comment", move |jira, key| {
+            let body = value.clone();
+            async move { jira.add_comment(&key, &body).await }
+          }),
+          Some(PromptTarget::Summary) => self.start_mutation("Summary", move |jira, key| {
+            let summary = serde_json::Value::String(value.clone());
+            async move { jira.edit_field(&key, "summary", summary).await }
+          }),
+          None => {}
+        }
+        Some(ViewAction::None)
+      }
+      KeyResult::Event(PromptEvent::Cancelled) => {
+        self.prompt_target = None;
+        Some(ViewAction::None)
+      }
+      KeyResult::NotHandled => None,
+    }
+  }
+
   fn handle_actions(&mut self, key: KeyEvent) -> Option<ViewAction> {
-    match key.code {
-      KeyCode::Char('r') => {
+    match self.keys.action(&key)? {
+      Action::NextTab => {
+        self.tab = match self.tab {
+          Tab::Details => Tab::History,
+          Tab::History => Tab::Details,
+        };
+        self.load_changelog();
+        Some(ViewAction::None)
+      }
+      Action::MoveDown if self.tab == Tab::History => {
+        self.history_state.select_next();
+        Some(ViewAction::None)
+      }
+      Action::MoveUp if self.tab == Tab::History => {
+        self.history_state.select_previous();
+        Some(ViewAction::None)
+      }
+      Action::Filter if self.tab == Tab::History => {
+        self.pick_history_field();
+        Some(ViewAction::None)
+      }
+      Action::Refresh => {
         self.query.refetch();
         Some(ViewAction::None)
       }
-      KeyCode::Char('q') | KeyCode::Esc => Some(ViewAction::Pop),
+      Action::AssignToMe => {
+        self.start_mutation("Assignment", |jira, key| async move {
+          jira.assign_to_me(&key).await
+        });
+        Some(ViewAction::None)
+      }
+      Action::Comment => {
+        self.prompt_target = Some(PromptTarget::Comment);
+        self.prompt.show("Comment", "");
+        Some(ViewAction::None)
+      }
+      Action::Edit => {
+        let custom_fields = self.custom_fields();
+        if custom_fields.is_empty() {
+          self.edit_field(SUMMARY_FIELD);
+        } else {
+          let fields = std::iter::once(StatusInfo {
+            id: SUMMARY_FIELD.to_string(),
+            name: "Summary".to_string(),
+            ..Default::default()
+          })
+          .chain(custom_fields.iter().map(|f| StatusInfo {
+            id: f.id.clone(),
+            name: f.name.clone(),
+            ..Default::default()
+          }))
+          .collect();
+          self.picker.show("Edit field".to_string(), fields);
+          self.picker_target = Some(PickerTarget::Field);
+        }
+        Some(ViewAction::None)
+      }
+      Action::GitBranch => {
+        if let Some(issue) = self.query.data().map(|r| &r.data) {
+          self
+            .actions
+            .branch(&issue.key, &issue.issue_type, &issue.summary);
+        }
+        Some(ViewAction::None)
+      }
+      Action::OpenInBrowser => {
+        self.actions.open(&self.key);
+        Some(ViewAction::None)
+      }
+      Action::Copy => {
+        let (issue_type, summary) = self
+          .query
+          .data()
+          .map(|r| (r.data.issue_type.as_str(), r.data.summary.as_str()))
+          .unwrap_or_default();
+        self.actions.copy(&self.key, issue_type, summary);
+        Some(ViewAction::None)
+      }
+      Action::Back => Some(ViewAction::Pop),
       _ => None,
     }
   }
@@ -118,11 +665,25 @@ impl IssueDetailView {
 
 impl View for IssueDetailView {
   fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
-    self.handle_actions(key).unwrap_or(ViewAction::None)
+    self
+      .handle_overlays(key)
+      .or_else(|| self.handle_actions(key))
+      .unwrap_or(ViewAction::None)
   }
 
   fn render(&mut self, frame: &mut Frame, area: Rect) {
-    self.render_detail(frame, area);
+    let chunks = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(1), Constraint::Min(1)])
+      .split(area);
+    self.render_tabs(frame, chunks[0]);
+    match self.tab {
+      Tab::Details => self.render_detail(frame, chunks[1]),
+      Tab::History => self.render_history(frame, chunks[1]),
+    }
+    self.picker.render_overlay(frame, area);
+    self.prompt.render_overlay(frame, area);
+    self.actions.render_overlay(frame, area);
   }
 
   fn breadcrumb_label(&self) -> String {
@@ -131,9 +692,73 @@ impl View for IssueDetailView {
 
   fn tick(&mut self) {
     self.query.poll();
+    self.fields.poll();
+    self.load_changelog();
+    if let Some((_, query)) = &mut self.changelog {
+      query.poll();
+    }
+    self.process_options();
+    self.process_mutation();
+  }
+
+  fn refresh(&mut self) {
+    self.query.refetch();
+  }
+
+  fn refresh_name(&self) -> Option<&'static str> {
+    Some("issue")
+  }
+
+  fn auto_refresh(&mut self, max_age: Duration) {
+    self.query.refetch_if_older(max_age);
+  }
+
+  fn fetched_at(&self) -> Option<Instant> {
+    self.query.fetched_at()
+  }
+
+  fn data_source(&self) -> Option<CacheInfo> {
+    self.query.data().map(|r| r.info())
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.keys = config.keys.keymap("issue");
+    self.actions.configure(config, &self.keys);
+    self.picker.set_keymap(&self.keys);
+  }
+
+  fn take_message(&mut self) -> Option<String> {
+    self.actions.take_message().or_else(|| self.message.take())
   }
 
   fn shortcuts(&self) -> Vec<Shortcut> {
-    vec![Shortcut::new("r", "refresh"), Shortcut::new("q", "back")]
+    let mut shortcuts = vec![self.keys.shortcut(
+      Action::NextTab,
+      match self.tab {
+        Tab::Details => "history",
+        Tab::History => "details",
+      },
+    )];
+    if self.tab == Tab::History {
+      shortcuts.push(self.keys.shortcut(Action::Filter, "filter field"));
+    }
+    shortcuts.extend([
+      self.keys.shortcut(Action::AssignToMe, "assign me"),
+      self.keys.shortcut(Action::Comment, "comment"),
+      self.keys.shortcut(
+        Action::Edit,
+        if self.custom_fields().is_empty() {
+          "edit summary"
+        } else {
+          "edit field"
+        },
+      ),
+      self.keys.shortcut(Action::Copy, "copy"),
+      self.keys.shortcut(Action::OpenInBrowser, "open in browser"),
+      self.keys.shortcut(Action::GitBranch, "git branch"),
+      self.keys.shortcut(Action::Refresh, "refresh"),
+      self.keys.shortcut(Action::Back, "back"),
+    ]);
+    shortcuts
   }
 }
@@ -1,18 +1,28 @@
+use crate::cache::{CacheInfo, CacheResult};
+use crate::config::Config;
 use crate::jira::types::IssueSummary;
 use crate::jira::JiraClient;
 use crate::query::Query;
-use crate::ui::components::{IssueFilterField, KeyResult, TicketPanel, TicketPanelEvent};
+use crate::ui::components::{
+  IssueActions, IssueFilterField, KeyResult, TicketPanel, TicketPanelEvent,
+};
 use crate::ui::view::{ShortcutInfo, ShortcutProvider, View, ViewAction};
 use crate::ui::views::IssueDetailView;
+use crate::ui::{Action, ListColumn};
 use crossterm::event::KeyEvent;
 use ratatui::prelude::*;
+use std::time::{Duration, Instant};
 
 /// View for displaying a list of issues
 pub struct IssueListView {
   jira: JiraClient,
   project: String,
-  query: Query<Vec<IssueSummary>>,
+  /// Shown in the title and breadcrumb: the project, or the alias name
+  /// for a JQL search
+  label: String,
+  query: Query<CacheResult<Vec<IssueSummary>>>,
   panel: TicketPanel<IssueFilterField>,
+  actions: IssueActions,
 }
 
 impl IssueListView {
@@ -25,10 +35,14 @@ impl IssueListView {
         project
       )
     };
+    Self::search(project.clone(), project, jql, jira)
+  }
 
+  /// List the results of a JQL query (`:jql`, or a JQL alias)
+  pub fn search(label: String, project: String, jql: String, jira: JiraClient) -> Self {
     let mut query = if jql.is_empty() {
       // No project configured - create a query that returns empty results
-      Query::new(|| async { Ok(Vec::new()) })
+      Query::new(|| async { Ok(CacheResult::from_network(Vec::new())) })
     } else {
       // Create query with the JiraClient
       let jira_for_query = jira.clone();
@@ -43,22 +57,28 @@ impl IssueListView {
     query.fetch();
 
     Self {
+      actions: IssueActions::new(jira.clone()),
       jira,
       project,
+      label,
       query,
       panel: TicketPanel::list_only(),
     }
   }
 
   fn title(&self) -> String {
-    format!("Issues [{}]", self.project)
+    format!("Issues [{}]", self.label)
   }
 }
 
 impl View for IssueListView {
   fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
     // Get data slice directly from query to avoid self borrow
-    let items = self.query.data().map(|v| v.as_slice()).unwrap_or(&[]);
+    let items = self.query.data().map(|r| r.data.as_slice()).unwrap_or(&[]);
+
+    if self.actions.handle_key(key) {
+      return ViewAction::None;
+    }
 
     match self.panel.handle_key(key, items) {
       KeyResult::Handled => ViewAction::None,
@@ -70,24 +90,31 @@ impl View for IssueListView {
         ViewAction::None
       }
       KeyResult::Event(TicketPanelEvent::Back) => ViewAction::Pop,
-      KeyResult::Event(TicketPanelEvent::FilterChanged) => ViewAction::None,
+      KeyResult::Event(TicketPanelEvent::FilterChanged | TicketPanelEvent::LoadChildren(_)) => {
+        ViewAction::None
+      }
+      KeyResult::Event(TicketPanelEvent::IssueAction(action, issue)) => {
+        self.actions.run(action, &issue);
+        ViewAction::None
+      }
       KeyResult::NotHandled => ViewAction::None,
     }
   }
 
   fn render(&mut self, frame: &mut Frame, area: Rect) {
-    let items = self.query.data().map(|v| v.as_slice()).unwrap_or(&[]);
+    let items = self.query.data().map(|r| r.data.as_slice()).unwrap_or(&[]);
     let title = self.title();
     let is_loading = self.query.is_loading();
 
     self.panel.render(frame, area, items, &title, is_loading);
+    self.actions.render_overlay(frame, area);
   }
 
   fn breadcrumb_label(&self) -> String {
-    if self.project.is_empty() {
+    if self.label.is_empty() {
       "Issues".to_string()
     } else {
-      format!("Issues [{}]", self.project)
+      self.title()
     }
   }
 
@@ -101,17 +128,65 @@ impl View for IssueListView {
 
     // Update filter values when data finishes loading
     if was_loading && !self.query.is_loading() {
-      if let Some(data) = self.query.data() {
+      if let Some(data) = self.query.data().map(|r| &r.data) {
         self.panel.update_filter_values(data);
+        self.panel.track_changes(data);
       }
     }
   }
 
+  fn refresh(&mut self) {
+    self.query.refetch();
+  }
+
+  fn refresh_name(&self) -> Option<&'static str> {
+    Some("issues")
+  }
+
+  fn auto_refresh(&mut self, max_age: Duration) {
+    self.query.refetch_if_older(max_age);
+  }
+
+  fn fetched_at(&self) -> Option<Instant> {
+    self.query.fetched_at()
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.panel.set_list_columns(&config.list.columns);
+    self.panel.set_hide_done(config.list.hide_done);
+    self
+      .panel
+      .set_filter_fields(IssueFilterField::with_custom(&config.jira.custom_fields));
+    let keys = config.keys.keymap("issues");
+    self.panel.set_keymap(&keys);
+    self.actions.configure(config, &keys);
+  }
+
+  fn visible_issues(&self) -> Option<(Vec<IssueSummary>, Vec<ListColumn>)> {
+    let items = self.query.data().map(|r| r.data.as_slice()).unwrap_or(&[]);
+    let issues = self
+      .panel
+      .filtered_items(items)
+      .into_iter()
+      .cloned()
+      .collect();
+    Some((issues, self.panel.list_columns().to_vec()))
+  }
+
+  fn take_message(&mut self) -> Option<String> {
+    self.actions.take_message()
+  }
+
+  fn data_source(&self) -> Option<CacheInfo> {
+    self.query.data().map(|r| r.info())
+  }
+
   fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    let keys = self.panel.keys();
     let mut shortcuts = vec![
-      ShortcutInfo::new(":", "command").with_priority(10),
-      ShortcutInfo::new("/", "search").with_priority(20),
-      ShortcutInfo::new("q", "back").with_priority(30),
+      keys.shortcut(Action::Command, "command").with_priority(10),
+      keys.shortcut(Action::Search, "search").with_priority(20),
+      keys.shortcut(Action::Back, "back").with_priority(30),
     ];
 
     // Add panel shortcuts
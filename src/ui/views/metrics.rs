@@ -0,0 +1,408 @@
+use crate::cache::CacheInfo;
+use crate::config::{BoardsConfig, Config};
+use crate::jira::metrics::{percentile, FlowMetrics, IssueFlow};
+use crate::jira::JiraClient;
+use crate::query::{Query, QueryState};
+use crate::ui::renderfns::format_duration;
+use crate::ui::skin;
+use crate::ui::view::{ShortcutInfo, View, ViewAction};
+use crate::ui::{Action, Keymap};
+use chrono::{Days, TimeDelta, Utc};
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use ratatui::symbols::Marker;
+use ratatui::widgets::{
+  Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Dataset, GraphType, Paragraph,
+};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Metrics computed from the board's issues and their histories
+#[derive(Clone)]
+struct MetricsData {
+  columns: Vec<String>,
+  metrics: FlowMetrics,
+  issues: usize,
+  /// Issues left out for lack of a creation time or history
+  skipped: usize,
+  source: CacheInfo,
+}
+
+/// A line of a chart: its name, color and points
+type Series = (String, Color, Vec<(f64, f64)>);
+
+/// The chart below the summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlowChart {
+  /// Cumulative flow: issues in each column and those after it
+  Cumulative,
+  /// Issues in each column between the first and the last
+  Wip,
+}
+
+/// Flow metrics of a board: lead and cycle times, throughput, WIP and a
+/// cumulative flow diagram
+pub struct MetricsView {
+  board_name: String,
+  days: u64,
+  query: Query<MetricsData>,
+  chart: FlowChart,
+  keys: Keymap,
+}
+
+impl MetricsView {
+  pub fn new(board_id: u64, board_name: String, jira: JiraClient, boards: &BoardsConfig) -> Self {
+    let days = boards.metrics_days();
+    let jql = boards.metrics_jql();
+    let mut query = Query::new(move || {
+      let jira = jira.clone();
+      let jql = jql.clone();
+      async move {
+        let (issues, config) = tokio::join!(
+          jira.get_board_issues(board_id, Some(&jql)),
+          jira.get_board_configuration(board_id),
+        );
+        let issues = issues.map_err(|e| e.to_string())?;
+        let config = config.map_err(|e| e.to_string())?;
+
+        // Histories are cached until their issue changes, so only the
+        // issues updated since the last time are fetched again
+        let histories: HashMap<String, _> = jira
+          .get_changelogs(&issues.data)
+          .await
+          .into_iter()
+          .collect();
+        let flows: Vec<IssueFlow> = issues
+          .data
+          .iter()
+          .filter_map(|issue| IssueFlow::new(issue, histories.get(&issue.key)?, &config.columns))
+          .collect();
+
+        let to = Utc::now().date_naive();
+        let from = to - Days::new(days - 1);
+        Ok(MetricsData {
+          columns: config.columns.iter().map(|c| c.name.clone()).collect(),
+          metrics: FlowMetrics::compute(&flows, config.columns.len(), from, to),
+          issues: issues.data.len(),
+          skipped: issues.data.len() - flows.len(),
+          source: issues.info(),
+        })
+      }
+    });
+    query.fetch();
+
+    Self {
+      board_name,
+      days,
+      query,
+      chart: FlowChart::Cumulative,
+      keys: Keymap::default(),
+    }
+  }
+
+  /// The columns counted as work in progress: all but the first and the
+  /// last, unless that leaves none
+  fn wip_columns(columns: usize) -> std::ops::Range<usize> {
+    if columns > 2 {
+      1..columns - 1
+    } else {
+      0..columns
+    }
+  }
+
+  /// A color for each column: the first as to do, the last as done
+  fn column_color(index: usize, columns: usize) -> Color {
+    let skin = skin();
+    let middle = [
+      skin.status.in_progress,
+      skin.text.accent,
+      skin.text.emphasis,
+      skin.text.highlight,
+    ];
+    match index {
+      0 => skin.status.todo,
+      i if i + 1 == columns => skin.status.done,
+      i => middle[(i - 1) % middle.len()],
+    }
+  }
+
+  fn render_summary(&self, frame: &mut Frame, area: Rect, data: &MetricsData) {
+    let block = Block::default()
+      .title(format!(
+        " {} - last {} ",
+        self.board_name,
+        format_duration(TimeDelta::days(self.days as i64))
+      ))
+      .title_alignment(Alignment::Center)
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(false));
+
+    let label = Style::default().fg(skin().text.dim);
+    let metrics = &data.metrics;
+    let percentiles = |name: &'static str, sorted: &[f64]| {
+      let mut spans = vec![Span::styled(format!("{:<12}", name), label)];
+      for p in [50.0, 85.0, 95.0] {
+        let value = percentile(sorted, p).map_or("-".to_string(), |d| format!("{:.1}d", d));
+        spans.push(Span::styled(format!("p{} ", p), label));
+        spans.push(Span::raw(format!("{:<8}", value)));
+      }
+      Line::from(spans)
+    };
+
+    let wip = Self::wip_columns(data.columns.len());
+    let wip_counts: Vec<usize> = metrics
+      .daily
+      .iter()
+      .map(|(_, counts)| counts[wip.clone()].iter().sum())
+      .collect();
+    let wip_now = wip_counts.last().copied().unwrap_or(0);
+    let wip_avg = match wip_counts.len() {
+      0 => 0.0,
+      days => wip_counts.iter().sum::<usize>() as f64 / days as f64,
+    };
+
+    let mut issues = vec![
+      Span::styled(format!("{:<12}", "Issues"), label),
+      Span::raw(data.issues.to_string()),
+    ];
+    if data.skipped > 0 {
+      issues.push(Span::styled(
+        format!(" ({} without history)", data.skipped),
+        label,
+      ));
+    }
+
+    let lines = vec![
+      percentiles("Cycle time", &metrics.cycle_times),
+      percentiles("Lead time", &metrics.lead_times),
+      Line::from(vec![
+        Span::styled(format!("{:<12}", "Throughput"), label),
+        Span::raw(format!("{:.1} per week", metrics.weekly_throughput())),
+        Span::styled(format!(" ({} done)", metrics.lead_times.len()), label),
+      ]),
+      Line::from(vec![
+        Span::styled(format!("{:<12}", "WIP"), label),
+        Span::raw(format!("{} now", wip_now)),
+        Span::styled(format!(" (average {:.1})", wip_avg), label),
+      ]),
+      Line::from(issues),
+    ];
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+  }
+
+  fn render_throughput(&self, frame: &mut Frame, area: Rect, data: &MetricsData) {
+    let block = Block::default()
+      .title(" Throughput per week ")
+      .title_alignment(Alignment::Center)
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(false));
+
+    let bars: Vec<Bar> = data
+      .metrics
+      .throughput
+      .iter()
+      .map(|(week, count)| {
+        Bar::default()
+          .value(*count as u64)
+          .label(Line::from(week.format("%m-%d").to_string()))
+          .style(Style::default().fg(skin().status.done))
+      })
+      .collect();
+    let chart = BarChart::default()
+      .block(block)
+      .data(BarGroup::default().bars(&bars))
+      .bar_width(5)
+      .bar_gap(1)
+      .value_style(
+        Style::default()
+          .fg(skin().selection.tab_fg)
+          .bg(skin().status.done),
+      )
+      .label_style(Style::default().fg(skin().text.dim));
+    frame.render_widget(chart, area);
+  }
+
+  fn render_flow(&self, frame: &mut Frame, area: Rect, data: &MetricsData) {
+    let columns = data.columns.len();
+    let daily = &data.metrics.daily;
+    let (title, shown) = match self.chart {
+      FlowChart::Cumulative => (" Cumulative flow ", 0..columns),
+      FlowChart::Wip => (" WIP per column ", Self::wip_columns(columns)),
+    };
+
+    // The cumulative diagram stacks each column on the ones after it
+    let series: Vec<Series> = shown
+      .map(|column| {
+        let points = daily
+          .iter()
+          .enumerate()
+          .map(|(day, (_, counts))| {
+            let count: usize = match self.chart {
+              FlowChart::Cumulative => counts[column..].iter().sum(),
+              FlowChart::Wip => counts[column],
+            };
+            (day as f64, count as f64)
+          })
+          .collect();
+        (
+          data.columns[column].clone(),
+          Self::column_color(column, columns),
+          points,
+        )
+      })
+      .collect();
+
+    let max = series
+      .iter()
+      .flat_map(|(_, _, points)| points.iter().map(|(_, y)| *y))
+      .fold(1.0, f64::max);
+    let datasets: Vec<Dataset> = series
+      .iter()
+      .map(|(name, color, points)| {
+        Dataset::default()
+          .name(name.clone())
+          .marker(Marker::Braille)
+          .graph_type(GraphType::Line)
+          .style(Style::default().fg(*color))
+          .data(points)
+      })
+      .collect();
+
+    let date = |i: usize| {
+      daily
+        .get(i)
+        .map(|(day, _)| day.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+    };
+    let axis_style = Style::default().fg(skin().text.dim);
+    let chart = Chart::new(datasets)
+      .block(
+        Block::default()
+          .title(title)
+          .title_alignment(Alignment::Center)
+          .borders(Borders::ALL)
+          .border_style(skin().border_style(false)),
+      )
+      .x_axis(
+        Axis::default()
+          .style(axis_style)
+          .bounds([0.0, daily.len().saturating_sub(1).max(1) as f64])
+          .labels([date(0), date(daily.len().saturating_sub(1))]),
+      )
+      .y_axis(
+        Axis::default()
+          .style(axis_style)
+          .bounds([0.0, max])
+          .labels(["0".to_string(), format!("{}", max)]),
+      );
+    frame.render_widget(chart, area);
+  }
+}
+
+impl View for MetricsView {
+  fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+    match self.keys.action(&key) {
+      Some(Action::NextTab) => {
+        self.chart = match self.chart {
+          FlowChart::Cumulative => FlowChart::Wip,
+          FlowChart::Wip => FlowChart::Cumulative,
+        };
+        ViewAction::None
+      }
+      Some(Action::Refresh) => {
+        self.query.refetch();
+        ViewAction::None
+      }
+      Some(Action::Back) => ViewAction::Pop,
+      _ => ViewAction::None,
+    }
+  }
+
+  fn render(&mut self, frame: &mut Frame, area: Rect) {
+    let Some(data) = self.query.data() else {
+      let (title, text) = match self.query.state() {
+        QueryState::Error(e) => (
+          format!(" {} (error) ", self.board_name),
+          format!("Error: {}\n\nPress 'r' to retry.", e),
+        ),
+        _ => (
+          format!(" {} (loading...) ", self.board_name),
+          "Loading issues and their histories...".to_string(),
+        ),
+      };
+      let block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(skin().border_style(false));
+      let paragraph = Paragraph::new(text)
+        .block(block)
+        .style(Style::default().fg(skin().text.dim));
+      frame.render_widget(paragraph, area);
+      return;
+    };
+
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(7), Constraint::Min(5)])
+      .split(area);
+    let top = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints([Constraint::Length(60), Constraint::Min(10)])
+      .split(rows[0]);
+
+    self.render_summary(frame, top[0], data);
+    self.render_throughput(frame, top[1], data);
+    self.render_flow(frame, rows[1], data);
+  }
+
+  fn breadcrumb_label(&self) -> String {
+    format!("{} [Metrics]", self.board_name)
+  }
+
+  fn tick(&mut self) {
+    self.query.poll();
+  }
+
+  fn refresh(&mut self) {
+    self.query.refetch();
+  }
+
+  fn refresh_name(&self) -> Option<&'static str> {
+    Some("metrics")
+  }
+
+  fn auto_refresh(&mut self, max_age: Duration) {
+    self.query.refetch_if_older(max_age);
+  }
+
+  fn fetched_at(&self) -> Option<Instant> {
+    self.query.fetched_at()
+  }
+
+  fn data_source(&self) -> Option<CacheInfo> {
+    self.query.data().map(|d| d.source)
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.keys = config.keys.keymap("metrics");
+  }
+
+  fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    let keys = &self.keys;
+    vec![
+      keys.shortcut(Action::Command, "command").with_priority(10),
+      keys.shortcut(Action::Back, "back").with_priority(30),
+      keys.shortcut(Action::Refresh, "refresh").with_priority(100),
+      keys
+        .shortcut(
+          Action::NextTab,
+          match self.chart {
+            FlowChart::Cumulative => "WIP chart",
+            FlowChart::Wip => "cumulative flow",
+          },
+        )
+        .with_priority(101),
+    ]
+  }
+}
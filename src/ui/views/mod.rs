@@ -1,13 +1,25 @@
 mod board;
 mod board_list;
+mod burndown;
+mod cache;
 mod epic_detail;
 mod epic_list;
 mod issue_detail;
 mod issue_list;
+mod metrics;
+mod notifications;
+mod pending;
+mod tree;
 
 pub use board::BoardView;
 pub use board_list::BoardListView;
+pub use burndown::BurndownView;
+pub use cache::CacheView;
 pub use epic_detail::EpicDetailView;
 pub use epic_list::EpicListView;
 pub use issue_detail::IssueDetailView;
 pub use issue_list::IssueListView;
+pub use metrics::MetricsView;
+pub use notifications::NotificationsView;
+pub use pending::PendingMutationsView;
+pub use tree::TreeView;
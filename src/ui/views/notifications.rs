@@ -0,0 +1,257 @@
+use crate::cache::Notification;
+use crate::config::Config;
+use crate::jira::notifications::NotificationKind;
+use crate::jira::JiraClient;
+use crate::ui::components::IssueActions;
+use crate::ui::ensure_valid_selection;
+use crate::ui::renderfns::format_age;
+use crate::ui::skin;
+use crate::ui::view::{ShortcutInfo, View, ViewAction};
+use crate::ui::views::IssueDetailView;
+use crate::ui::{Action, Keymap};
+use chrono::Utc;
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+
+/// Notification center: changes to watched issues, newest first
+pub struct NotificationsView {
+  jira: JiraClient,
+  notifications: Vec<Notification>,
+  list_state: ListState,
+  actions: IssueActions,
+  error: Option<String>,
+  keys: Keymap,
+  message: Option<String>,
+}
+
+impl NotificationsView {
+  pub fn new(jira: JiraClient) -> Self {
+    let mut view = Self {
+      actions: IssueActions::new(jira.clone()),
+      jira,
+      notifications: Vec::new(),
+      list_state: ListState::default(),
+      error: None,
+      keys: Keymap::default(),
+      message: None,
+    };
+    view.reload();
+    view
+  }
+
+  /// Reload from the cache database and mark everything as seen.
+  /// The loaded list keeps its unread flags so new entries stand out.
+  fn reload(&mut self) {
+    match self.jira.notifications() {
+      Ok(notifications) => {
+        self.notifications = notifications;
+        self.error = self
+          .jira
+          .mark_notifications_read()
+          .err()
+          .map(|e| e.to_string());
+      }
+      Err(e) => self.error = Some(e.to_string()),
+    }
+  }
+
+  fn kind_style(kind: &str) -> Style {
+    match NotificationKind::parse(kind) {
+      Some(NotificationKind::Assigned) => Style::default().fg(skin().text.success),
+      Some(NotificationKind::Status) => Style::default().fg(skin().text.highlight),
+      Some(NotificationKind::Comment) => Style::default().fg(skin().text.emphasis),
+      Some(NotificationKind::Watching) | None => Style::default().fg(skin().text.dim),
+    }
+  }
+
+  fn render_list(&mut self, frame: &mut Frame, area: Rect) {
+    ensure_valid_selection(&mut self.list_state, self.notifications.len());
+
+    let unread = self.notifications.iter().filter(|n| !n.read).count();
+    let title = match &self.error {
+      Some(e) => format!(" Notifications (error: {}) ", e),
+      None if unread > 0 => format!(" Notifications ({} new) ", unread),
+      None => format!(" Notifications ({}) ", self.notifications.len()),
+    };
+
+    let block = Block::default()
+      .title(title)
+      .title_alignment(Alignment::Center)
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(false));
+
+    if self.notifications.is_empty() {
+      let paragraph =
+        Paragraph::new("No notifications. Set notifications.jql in the config to watch issues.")
+          .block(block)
+          .style(Style::default().fg(skin().text.dim));
+      frame.render_widget(paragraph, area);
+      return;
+    }
+
+    let now = Utc::now();
+    let items: Vec<ListItem> = self
+      .notifications
+      .iter()
+      .map(|n| {
+        let line = Line::from(vec![
+          Span::styled(
+            format!("{:<12}", n.entity_key),
+            Style::default().fg(skin().text.accent),
+          ),
+          Span::raw(" "),
+          Span::styled(format!("{:<9}", n.kind), Self::kind_style(&n.kind)),
+          Span::raw(" "),
+          Span::styled(
+            format!("{:<8}", format_age(now - n.created_at)),
+            Style::default().fg(skin().text.dim),
+          ),
+          Span::raw(" "),
+          Span::raw(n.message.clone()),
+        ]);
+        let style = if n.read {
+          Style::default()
+        } else {
+          Style::default().add_modifier(Modifier::BOLD)
+        };
+        ListItem::new(line).style(style)
+      })
+      .collect();
+
+    let list = List::new(items)
+      .block(block)
+      .highlight_style(skin().selection_style())
+      .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, area, &mut self.list_state);
+  }
+
+  // Key handling helpers for or_else chain pattern
+  fn handle_overlays(&mut self, key: KeyEvent) -> Option<ViewAction> {
+    self.actions.handle_key(key).then_some(ViewAction::None)
+  }
+
+  fn handle_navigation(&mut self, key: KeyEvent) -> Option<ViewAction> {
+    match self.keys.action(&key)? {
+      Action::MoveDown => {
+        self.list_state.select_next();
+        Some(ViewAction::None)
+      }
+      Action::MoveUp => {
+        self.list_state.select_previous();
+        Some(ViewAction::None)
+      }
+      _ => None,
+    }
+  }
+
+  fn handle_actions(&mut self, key: KeyEvent) -> Option<ViewAction> {
+    match self.keys.action(&key)? {
+      Action::OpenDetail => self
+        .list_state
+        .selected()
+        .and_then(|i| self.notifications.get(i))
+        .map(|n| {
+          ViewAction::Push(Box::new(IssueDetailView::new(
+            n.entity_key.clone(),
+            self.jira.clone(),
+          )))
+        }),
+      Action::OpenInBrowser => {
+        if let Some(n) = self
+          .list_state
+          .selected()
+          .and_then(|i| self.notifications.get(i))
+        {
+          self.actions.open(&n.entity_key);
+        }
+        Some(ViewAction::None)
+      }
+      Action::Copy => {
+        if let Some(n) = self
+          .list_state
+          .selected()
+          .and_then(|i| self.notifications.get(i))
+        {
+          // Notifications don't keep the summary
+          self.actions.copy(&n.entity_key, "", "");
+        }
+        Some(ViewAction::None)
+      }
+      Action::Refresh => {
+        self.reload();
+        Some(ViewAction::None)
+      }
+      Action::ClearAll => {
+        self.message = Some(match self.jira.clear_notifications() {
+          Ok(()) => "Notifications cleared".to_string(),
+          Err(e) => format!("Clear failed: {}", e),
+        });
+        self.reload();
+        Some(ViewAction::None)
+      }
+      Action::Back => Some(ViewAction::Pop),
+      _ => None,
+    }
+  }
+}
+
+impl View for NotificationsView {
+  fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+    self
+      .handle_overlays(key)
+      .or_else(|| self.handle_navigation(key))
+      .or_else(|| self.handle_actions(key))
+      .unwrap_or(ViewAction::None)
+  }
+
+  fn render(&mut self, frame: &mut Frame, area: Rect) {
+    self.render_list(frame, area);
+    self.actions.render_overlay(frame, area);
+  }
+
+  fn breadcrumb_label(&self) -> String {
+    "Notifications".to_string()
+  }
+
+  fn refresh(&mut self) {
+    self.reload();
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.keys = config.keys.keymap("notifications");
+    self.actions.configure(config, &self.keys);
+  }
+
+  fn take_message(&mut self) -> Option<String> {
+    self.actions.take_message().or_else(|| self.message.take())
+  }
+
+  fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    vec![
+      self
+        .keys
+        .shortcut(Action::Command, "command")
+        .with_priority(10),
+      self
+        .keys
+        .shortcut(Action::OpenDetail, "open issue")
+        .with_priority(20),
+      self.keys.shortcut(Action::Copy, "copy").with_priority(25),
+      self
+        .keys
+        .shortcut(Action::OpenInBrowser, "open")
+        .with_priority(26),
+      self
+        .keys
+        .shortcut(Action::ClearAll, "clear all")
+        .with_priority(30),
+      self
+        .keys
+        .shortcut(Action::Refresh, "reload")
+        .with_priority(40),
+      self.keys.shortcut(Action::Back, "back").with_priority(50),
+    ]
+  }
+}
@@ -0,0 +1,223 @@
+use crate::cache::{MutationState, PendingMutation};
+use crate::config::Config;
+use crate::jira::mutations::Mutation;
+use crate::jira::JiraClient;
+use crate::ui::ensure_valid_selection;
+use crate::ui::renderfns::format_age;
+use crate::ui::skin;
+use crate::ui::view::{ShortcutInfo, View, ViewAction};
+use crate::ui::{Action, Keymap};
+use chrono::Utc;
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+
+/// View listing changes queued while offline, with conflict resolution
+pub struct PendingMutationsView {
+  jira: JiraClient,
+  mutations: Vec<PendingMutation<Mutation>>,
+  list_state: ListState,
+  error: Option<String>,
+  keys: Keymap,
+  message: Option<String>,
+}
+
+impl PendingMutationsView {
+  pub fn new(jira: JiraClient) -> Self {
+    let mut view = Self {
+      jira,
+      mutations: Vec::new(),
+      list_state: ListState::default(),
+      error: None,
+      keys: Keymap::default(),
+      message: None,
+    };
+    view.reload();
+    view
+  }
+
+  /// Reload the queue from the cache database (it's local, so no Query needed)
+  fn reload(&mut self) {
+    match self.jira.pending_mutations() {
+      Ok(mutations) => {
+        self.mutations = mutations;
+        self.error = None;
+      }
+      Err(e) => self.error = Some(e.to_string()),
+    }
+  }
+
+  fn selected(&self) -> Option<&PendingMutation<Mutation>> {
+    self
+      .list_state
+      .selected()
+      .and_then(|i| self.mutations.get(i))
+  }
+
+  fn state_style(state: MutationState) -> Style {
+    match state {
+      MutationState::Pending => Style::default().fg(skin().text.highlight),
+      MutationState::Conflict => Style::default().fg(skin().text.emphasis),
+      MutationState::Failed => Style::default().fg(skin().text.error),
+    }
+  }
+
+  fn render_list(&mut self, frame: &mut Frame, area: Rect) {
+    ensure_valid_selection(&mut self.list_state, self.mutations.len());
+
+    let title = match &self.error {
+      Some(e) => format!(" Pending changes (error: {}) ", e),
+      None => format!(" Pending changes ({}) ", self.mutations.len()),
+    };
+
+    let block = Block::default()
+      .title(title)
+      .title_alignment(Alignment::Center)
+      .borders(Borders::ALL)
+      .border_style(skin().border_style(false));
+
+    if self.mutations.is_empty() {
+      let paragraph = Paragraph::new("No changes waiting to be sent.")
+        .block(block)
+        .style(Style::default().fg(skin().text.dim));
+      frame.render_widget(paragraph, area);
+      return;
+    }
+
+    let now = Utc::now();
+    let items: Vec<ListItem> = self
+      .mutations
+      .iter()
+      .map(|m| {
+        let mut spans = vec![
+          Span::styled(
+            format!("{:<12}", m.entity_key),
+            Style::default().fg(skin().text.accent),
+          ),
+          Span::raw(" "),
+          Span::styled(
+            format!("{:<9}", m.state.as_str()),
+            Self::state_style(m.state),
+          ),
+          Span::raw(" "),
+          Span::styled(
+            format!("{:<8}", format_age(now - m.created_at)),
+            Style::default().fg(skin().text.dim),
+          ),
+          Span::raw(" "),
+          Span::raw(m.mutation.describe()),
+        ];
+        if let Some(err) = &m.error {
+          spans.push(Span::styled(
+            format!("  ({})", err),
+            Style::default().fg(skin().text.dim),
+          ));
+        }
+        ListItem::new(Line::from(spans))
+      })
+      .collect();
+
+    let list = List::new(items)
+      .block(block)
+      .highlight_style(skin().selection_style())
+      .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, area, &mut self.list_state);
+  }
+
+  // Key handling helpers for or_else chain pattern
+  fn handle_navigation(&mut self, key: KeyEvent) -> Option<ViewAction> {
+    match self.keys.action(&key)? {
+      Action::MoveDown => {
+        self.list_state.select_next();
+        Some(ViewAction::None)
+      }
+      Action::MoveUp => {
+        self.list_state.select_previous();
+        Some(ViewAction::None)
+      }
+      _ => None,
+    }
+  }
+
+  fn handle_actions(&mut self, key: KeyEvent) -> Option<ViewAction> {
+    match self.keys.action(&key)? {
+      Action::Refresh => {
+        self.reload();
+        Some(ViewAction::None)
+      }
+      Action::Retry => {
+        if let Some(m) = self.selected() {
+          let (id, key) = (m.id, m.entity_key.clone());
+          self.message = Some(match self.jira.retry_mutation(id) {
+            Ok(()) => format!("{} will be retried on the next sync", key),
+            Err(e) => format!("Retry failed: {}", e),
+          });
+          self.reload();
+        }
+        Some(ViewAction::None)
+      }
+      Action::Delete => {
+        if let Some(m) = self.selected() {
+          let key = m.entity_key.clone();
+          self.message = Some(match self.jira.discard_mutation(m) {
+            Ok(()) => format!("Discarded change to {}", key),
+            Err(e) => format!("Discard failed: {}", e),
+          });
+          self.reload();
+        }
+        Some(ViewAction::None)
+      }
+      Action::Back => Some(ViewAction::Pop),
+      _ => None,
+    }
+  }
+}
+
+impl View for PendingMutationsView {
+  fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+    self
+      .handle_navigation(key)
+      .or_else(|| self.handle_actions(key))
+      .unwrap_or(ViewAction::None)
+  }
+
+  fn render(&mut self, frame: &mut Frame, area: Rect) {
+    self.render_list(frame, area);
+  }
+
+  fn breadcrumb_label(&self) -> String {
+    "Pending".to_string()
+  }
+
+  fn refresh(&mut self) {
+    self.reload();
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.keys = config.keys.keymap("pending");
+  }
+
+  fn take_message(&mut self) -> Option<String> {
+    self.message.take()
+  }
+
+  fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    vec![
+      self
+        .keys
+        .shortcut(Action::Command, "command")
+        .with_priority(10),
+      self.keys.shortcut(Action::Retry, "retry").with_priority(20),
+      self
+        .keys
+        .shortcut(Action::Delete, "discard")
+        .with_priority(30),
+      self
+        .keys
+        .shortcut(Action::Refresh, "reload")
+        .with_priority(40),
+      self.keys.shortcut(Action::Back, "back").with_priority(50),
+    ]
+  }
+}
@@ -0,0 +1,295 @@
+use crate::cache::{CacheInfo, CacheResult};
+use crate::config::Config;
+use crate::jira::types::IssueSummary;
+use crate::jira::JiraClient;
+use crate::query::Query;
+use crate::ui::components::{
+  IssueActions, IssueFilterField, KeyResult, TicketPanel, TicketPanelEvent,
+};
+use crate::ui::view::{ShortcutInfo, ShortcutProvider, View, ViewAction};
+use crate::ui::views::IssueDetailView;
+use crate::ui::{Action, ListColumn};
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+type IssuesQuery = Query<CacheResult<Vec<IssueSummary>>>;
+
+/// View of a project's hierarchy: initiatives, epics, their issues and
+/// subtasks, as a tree that loads children on expand
+pub struct TreeView {
+  jira: JiraClient,
+  project: String,
+  /// The project's epics
+  query: IssuesQuery,
+  /// The initiatives the epics are under
+  parents: Option<IssuesQuery>,
+  /// Children of expanded issues, in the order they were expanded
+  children: Vec<(String, IssuesQuery)>,
+  /// Everything loaded; the panel arranges it as a tree
+  items: Vec<IssueSummary>,
+  panel: TicketPanel<IssueFilterField>,
+  actions: IssueActions,
+  message: Option<String>,
+}
+
+impl TreeView {
+  pub fn new(project: String, jira: JiraClient) -> Self {
+    let mut query = if project.is_empty() {
+      // No project configured
+      Query::new(|| async { Ok(CacheResult::from_network(Vec::new())) })
+    } else {
+      let jira_for_query = jira.clone();
+      let project_for_query = project.clone();
+      Query::new(move || {
+        let jira = jira_for_query.clone();
+        let project = project_for_query.clone();
+        async move { jira.get_epics(&project).await.map_err(|e| e.to_string()) }
+      })
+    };
+
+    query.fetch();
+
+    Self {
+      actions: IssueActions::new(jira.clone()),
+      jira,
+      project,
+      query,
+      parents: None,
+      children: Vec::new(),
+      items: Vec::new(),
+      panel: TicketPanel::tree(),
+      message: None,
+    }
+  }
+
+  fn title(&self) -> String {
+    format!("Tree [{}]", self.project)
+  }
+
+  fn is_loading(&self) -> bool {
+    self.query.is_loading()
+      || self.parents.as_ref().is_some_and(|q| q.is_loading())
+      || self.children.iter().any(|(_, q)| q.is_loading())
+  }
+
+  /// Load the initiatives above the epics, so the epics show under them
+  fn load_parents(&mut self) {
+    let epics = self.query.data().map(|r| r.data.as_slice()).unwrap_or(&[]);
+    let epic_keys: HashSet<&str> = epics.iter().map(|e| e.key.as_str()).collect();
+    let mut keys: Vec<String> = epics
+      .iter()
+      .filter_map(|e| e.parent.clone())
+      .filter(|p| !epic_keys.contains(p.as_str()))
+      .collect();
+    keys.sort();
+    keys.dedup();
+    if keys.is_empty() {
+      self.parents = None;
+      return;
+    }
+
+    let jira = self.jira.clone();
+    let mut query = Query::new(move || {
+      let jira = jira.clone();
+      let keys = keys.clone();
+      async move {
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+        jira.get_issues(&keys).await.map_err(|e| e.to_string())
+      }
+    });
+    query.fetch();
+    self.parents = Some(query);
+  }
+
+  /// Start loading the children of an expanded issue
+  fn load_children(&mut self, issue: IssueSummary) {
+    let key = issue.key.clone();
+    // Expanded again while still loading
+    if self.children.iter().any(|(k, _)| *k == key) {
+      return;
+    }
+    let jira = self.jira.clone();
+    let mut query = Query::new(move || {
+      let jira = jira.clone();
+      let issue = issue.clone();
+      async move {
+        jira
+          .get_child_issues(&issue)
+          .await
+          .map_err(|e| e.to_string())
+      }
+    });
+    query.fetch();
+    self.children.push((key, query));
+  }
+
+  /// Collect everything loaded, initiatives first, each issue once
+  fn update_items(&mut self) {
+    let loaded = self
+      .parents
+      .iter()
+      .chain(std::iter::once(&self.query))
+      .chain(self.children.iter().map(|(_, q)| q))
+      .filter_map(|q| q.data())
+      .flat_map(|r| &r.data);
+
+    let mut seen = HashSet::new();
+    self.items = loaded
+      .filter(|issue| seen.insert(issue.key.clone()))
+      .cloned()
+      .collect();
+    self.panel.update_filter_values(&self.items);
+    self.panel.track_changes(&self.items);
+  }
+}
+
+impl View for TreeView {
+  fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
+    if self.actions.handle_key(key) {
+      return ViewAction::None;
+    }
+
+    match self.panel.handle_key(key, &self.items) {
+      KeyResult::Handled => ViewAction::None,
+      KeyResult::Event(TicketPanelEvent::Selected(issue)) => {
+        ViewAction::Push(Box::new(IssueDetailView::new(issue.key, self.jira.clone())))
+      }
+      KeyResult::Event(TicketPanelEvent::LoadChildren(issue)) => {
+        self.load_children(issue);
+        ViewAction::None
+      }
+      KeyResult::Event(TicketPanelEvent::RefreshRequested) => {
+        self.refresh();
+        ViewAction::None
+      }
+      KeyResult::Event(TicketPanelEvent::Back) => ViewAction::Pop,
+      KeyResult::Event(TicketPanelEvent::FilterChanged) => ViewAction::None,
+      KeyResult::Event(TicketPanelEvent::IssueAction(action, issue)) => {
+        self.actions.run(action, &issue);
+        ViewAction::None
+      }
+      KeyResult::NotHandled => ViewAction::None,
+    }
+  }
+
+  fn render(&mut self, frame: &mut Frame, area: Rect) {
+    let title = self.title();
+    let is_loading = self.items.is_empty() && self.is_loading();
+
+    self
+      .panel
+      .render(frame, area, &self.items, &title, is_loading);
+    self.actions.render_overlay(frame, area);
+  }
+
+  fn breadcrumb_label(&self) -> String {
+    if self.project.is_empty() {
+      "Tree".to_string()
+    } else {
+      self.title()
+    }
+  }
+
+  fn project(&self) -> Option<&str> {
+    Some(&self.project)
+  }
+
+  fn tick(&mut self) {
+    let mut changed = false;
+
+    let was_loading = self.query.is_loading();
+    self.query.poll();
+    if was_loading && !self.query.is_loading() {
+      self.load_parents();
+      changed = true;
+    }
+
+    if let Some(parents) = &mut self.parents {
+      let was_loading = parents.is_loading();
+      parents.poll();
+      changed |= was_loading && !parents.is_loading();
+    }
+
+    for (key, query) in &mut self.children {
+      let was_loading = query.is_loading();
+      query.poll();
+      if was_loading && !query.is_loading() {
+        if let Some(err) = query.error() {
+          self.message = Some(format!("Failed to load issues under {}: {}", key, err));
+        }
+        self.panel.set_children_loaded(key);
+        changed = true;
+      }
+    }
+
+    if changed {
+      self.update_items();
+    }
+  }
+
+  fn refresh(&mut self) {
+    self.query.refetch();
+    for (_, query) in &mut self.children {
+      query.refetch();
+    }
+  }
+
+  fn refresh_name(&self) -> Option<&'static str> {
+    Some("tree")
+  }
+
+  fn auto_refresh(&mut self, max_age: Duration) {
+    self.query.refetch_if_older(max_age);
+    for (_, query) in &mut self.children {
+      query.refetch_if_older(max_age);
+    }
+  }
+
+  fn fetched_at(&self) -> Option<Instant> {
+    self.query.fetched_at()
+  }
+
+  fn configure(&mut self, config: &Config) {
+    self.panel.set_list_columns(&config.list.columns);
+    self.panel.set_hide_done(config.list.hide_done);
+    self
+      .panel
+      .set_filter_fields(IssueFilterField::with_custom(&config.jira.custom_fields));
+    let keys = config.keys.keymap("tree");
+    self.panel.set_keymap(&keys);
+    self.actions.configure(config, &keys);
+  }
+
+  fn visible_issues(&self) -> Option<(Vec<IssueSummary>, Vec<ListColumn>)> {
+    let issues = self
+      .panel
+      .filtered_items(&self.items)
+      .into_iter()
+      .cloned()
+      .collect();
+    Some((issues, self.panel.list_columns().to_vec()))
+  }
+
+  fn take_message(&mut self) -> Option<String> {
+    self.actions.take_message().or_else(|| self.message.take())
+  }
+
+  fn data_source(&self) -> Option<CacheInfo> {
+    self.query.data().map(|r| r.info())
+  }
+
+  fn shortcuts(&self) -> Vec<ShortcutInfo> {
+    let keys = self.panel.keys();
+    let mut shortcuts = vec![
+      keys.shortcut(Action::Command, "command").with_priority(10),
+      keys.shortcut(Action::Search, "search").with_priority(20),
+      keys.shortcut(Action::Back, "back").with_priority(30),
+    ];
+
+    shortcuts.extend(self.panel.shortcuts());
+
+    shortcuts
+  }
+}